@@ -0,0 +1,555 @@
+//! Rust source for the schema types, their `WritableSchemaType` and
+//! `Validate` impls, and the table of elements the CSV import and SQLite
+//! storage walk
+
+use std::fmt::Write;
+
+use crate::names;
+use crate::xsd::{ComplexType, Element, Schema, SimpleType};
+
+const HEADER: &str = "\
+// Generated by aqi-nacor-codegen from schemas/AQISchema.xsd, don't edit by hand.
+// Change the schema or codegen/src/names.rs and run `cargo run -p aqi-nacor-codegen`.
+";
+
+/// How a value is written, and what it's stored as
+enum Kind {
+    /// A nested element
+    Complex,
+    /// An owned `String`
+    Text,
+    Date,
+    DateTime,
+    /// A `Copy` value that implements `Display`
+    Display,
+    /// Anything implementing `SchemaStringType` or `SchemaIntType`
+    Value,
+}
+
+struct Resolved {
+    rust_type: String,
+    kind: Kind,
+}
+
+fn resolve(schema: &Schema, type_name: &str) -> Result<Resolved, String> {
+    let builtin = |rust_type: &str, kind| {
+        Ok(Resolved {
+            rust_type: rust_type.to_string(),
+            kind,
+        })
+    };
+
+    match type_name {
+        "xs:string" | "xs:token" => return builtin("String", Kind::Text),
+        "xs:date" => return builtin("NaiveDate", Kind::Date),
+        "xs:dateTime" => return builtin("NaiveDateTime", Kind::DateTime),
+        "xs:boolean" => return builtin("bool", Kind::Display),
+        "xs:decimal" => return builtin("DecimalType", Kind::Value),
+        "xs:integer" | "xs:nonNegativeInteger" => return builtin("u64", Kind::Display),
+        "xs:unsignedByte" => return builtin("u8", Kind::Display),
+        _ => {}
+    }
+
+    if schema.complex_type(type_name).is_some() {
+        return Ok(Resolved {
+            rust_type: type_name.to_string(),
+            kind: Kind::Complex,
+        });
+    }
+
+    match schema.simple_type(type_name) {
+        Some(simple) => Ok(Resolved {
+            rust_type: type_name.to_string(),
+            kind: match simple_kind(simple)? {
+                SimpleKind::Alias(_) => Kind::Display,
+                _ => Kind::Value,
+            },
+        }),
+        None => Err(format!("Unknown type {}", type_name)),
+    }
+}
+
+enum SimpleKind {
+    HandWritten,
+    StringEnum,
+    IntEnum,
+    Pattern,
+    /// Unrestricted strings, open enums if there's a vocabulary for them
+    Open,
+    /// A restricted number, as a plain Rust integer type
+    Alias(&'static str),
+}
+
+fn simple_kind(simple: &SimpleType) -> Result<SimpleKind, String> {
+    if names::HAND_WRITTEN_TYPES.contains(&simple.name.as_str()) {
+        return Ok(SimpleKind::HandWritten);
+    }
+
+    let integer = match simple.base.as_str() {
+        "xs:string" | "xs:token" => None,
+        "xs:integer" | "xs:nonNegativeInteger" => Some("u64"),
+        "xs:unsignedByte" => Some("u8"),
+        base => return Err(format!("Unsupported base {} for {}", base, simple.name)),
+    };
+
+    match (
+        integer,
+        simple.enumerations.is_empty(),
+        simple.patterns.len(),
+    ) {
+        (None, false, 0) => Ok(SimpleKind::StringEnum),
+        (Some(_), false, 0) => Ok(SimpleKind::IntEnum),
+        (None, true, 1) => Ok(SimpleKind::Pattern),
+        (None, true, 0) => Ok(SimpleKind::Open),
+        (Some(rust_type), true, 0) => Ok(SimpleKind::Alias(rust_type)),
+        _ => Err(format!(
+            "{} mixes facets, it needs to be listed in HAND_WRITTEN_TYPES",
+            simple.name
+        )),
+    }
+}
+
+// FIXME: Normalize Options on 0-min Vecs
+fn field_type(element: &Element, rust_type: &str) -> String {
+    match (element.is_repeated(), element.is_optional()) {
+        (true, true) => format!("Option<Vec<{}>>", rust_type),
+        (true, false) => format!("Vec<{}>", rust_type),
+        (false, true) => format!("Option<{}>", rust_type),
+        (false, false) => rust_type.to_string(),
+    }
+}
+
+/// Types and enums, in schema order
+pub fn types(schema: &Schema) -> Result<String, String> {
+    let mut out = String::from(HEADER);
+    out.push_str("\nuse super::*;\n");
+
+    for complex in &schema.complex_types {
+        out.push_str("\n#[derive(Clone, Debug)]\n");
+        out.push_str("#[cfg_attr(feature = \"serde\", derive(Serialize, Deserialize))]\n");
+        writeln!(out, "pub struct {} {{", complex.name).unwrap();
+        for element in &complex.elements {
+            let resolved = resolve(schema, &element.type_name)?;
+            writeln!(
+                out,
+                "    pub {}: {},",
+                names::field_name(&complex.name, &element.name),
+                field_type(element, &resolved.rust_type)
+            )
+            .unwrap();
+        }
+        out.push_str("}\n");
+    }
+
+    for simple in &schema.simple_types {
+        simple_type(&mut out, simple)?;
+    }
+
+    Ok(out)
+}
+
+fn simple_type(out: &mut String, simple: &SimpleType) -> Result<(), String> {
+    let name = &simple.name;
+    let docs: String = names::type_docs(name)
+        .iter()
+        .map(|line| format!("/// {}\n", line))
+        .collect();
+
+    match simple_kind(simple)? {
+        SimpleKind::HandWritten => {}
+        SimpleKind::StringEnum => {
+            let variants: Vec<String> = simple
+                .enumerations
+                .iter()
+                .map(|value| {
+                    format!(
+                        "        {} => {:?}",
+                        names::variant_name(name, value),
+                        value
+                    )
+                })
+                .collect();
+            writeln!(
+                out,
+                "\n{}enum_map! {{\n    {}: SchemaStringType; value {{\n{}\n    }}\n}}",
+                docs,
+                name,
+                variants.join(",\n")
+            )
+            .unwrap();
+        }
+        SimpleKind::IntEnum => {
+            let mut variants = Vec::new();
+            for value in &simple.enumerations {
+                let number: u64 = value
+                    .parse()
+                    .map_err(|_| format!("Invalid {} value {}", name, value))?;
+                variants.push(format!(
+                    "        {} => {}",
+                    names::variant_name(name, value),
+                    number
+                ));
+            }
+            writeln!(
+                out,
+                "\n{}enum_map! {{\n    {}: SchemaIntType; value -> u64 {{\n{}\n    }}\n}}",
+                docs,
+                name,
+                variants.join(",\n")
+            )
+            .unwrap();
+        }
+        SimpleKind::Pattern => {
+            // XSD patterns always match the whole value
+            let pattern = &simple.patterns[0];
+            let regex = if pattern.contains('|') {
+                format!("^(?:{})$", pattern)
+            } else {
+                format!("^{}$", pattern)
+            };
+            let literal = if regex.contains('"') {
+                format!("r#\"{}\"#", regex)
+            } else {
+                format!("r\"{}\"", regex)
+            };
+            writeln!(
+                out,
+                "\n{}schema_pattern_type!({}, {});",
+                docs, name, literal
+            )
+            .unwrap();
+        }
+        SimpleKind::Open => match names::vocabulary(name) {
+            Some(vocabulary) => {
+                let variants: Vec<String> = vocabulary
+                    .iter()
+                    .map(|(variant, value)| format!("        {} => {:?}", variant, value))
+                    .collect();
+                writeln!(
+                    out,
+                    "\nopen_enum_map! {{\n{}    /// Examples provided in schema\n    {} {{\n{}\n    }}\n}}",
+                    docs.replace("///", "    ///"),
+                    name,
+                    variants.join(",\n")
+                )
+                .unwrap();
+            }
+            None => {
+                writeln!(
+                    out,
+                    "\nschema_string_tuple_struct!(\n{}    /// Examples provided in schema\n    {}\n);",
+                    docs.replace("///", "    ///"),
+                    name
+                )
+                .unwrap();
+            }
+        },
+        SimpleKind::Alias(rust_type) => {
+            writeln!(out, "\n{}pub type {} = {};", docs, name, rust_type).unwrap();
+        }
+    }
+
+    Ok(())
+}
+
+/// `WritableSchemaType` impls for every complex type not written by hand
+pub fn writers(schema: &Schema) -> Result<String, String> {
+    let mut out = String::from(HEADER);
+    out.push_str("\nuse super::*;\n");
+
+    for complex in &schema.complex_types {
+        if names::HAND_WRITTEN_WRITERS.contains(&complex.name.as_str()) {
+            continue;
+        }
+
+        writer(&mut out, schema, complex)?;
+    }
+
+    Ok(out)
+}
+
+fn writer(out: &mut String, schema: &Schema, complex: &ComplexType) -> Result<(), String> {
+    writeln!(
+        out,
+        "\nimpl WritableSchemaType for {} {{\n    \
+         fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {{\n        \
+         writer.write(XmlEvent::start_element(name))?;\n",
+        complex.name
+    )
+    .unwrap();
+
+    for element in &complex.elements {
+        let field = names::field_name(&complex.name, &element.name);
+        let kind = resolve(schema, &element.type_name)?.kind;
+        let statement = |binding: &str, owned: bool| {
+            write_statement(&element.name, &kind, binding, owned, element.is_repeated())
+        };
+
+        let code = if element.is_optional() {
+            format!(
+                "if let Some(ref {field}) = self.{field} {{\n{}\n}}",
+                statement(&field, false),
+                field = field
+            )
+        } else {
+            statement(&format!("self.{}", field), true)
+        };
+
+        writeln!(out, "{}\n", code).unwrap();
+    }
+
+    out.push_str("        writer.write(XmlEvent::end_element())?;\n\n        Ok(())\n    }\n}\n");
+
+    Ok(())
+}
+
+/// Writes `binding`, which is either a field of `self` (`owned`) or a reference to one
+fn write_statement(
+    element: &str,
+    kind: &Kind,
+    binding: &str,
+    owned: bool,
+    repeated: bool,
+) -> String {
+    let reference = if owned {
+        format!("&{}", binding)
+    } else {
+        binding.to_string()
+    };
+
+    if let Kind::Complex = *kind {
+        let function = if repeated {
+            "write_children"
+        } else {
+            "write_child"
+        };
+
+        return format!("{}({:?}, {}, writer)?;", function, element, reference);
+    }
+
+    if repeated {
+        return format!(
+            "for value in {} {{\n{}\n}}",
+            reference,
+            write_statement(element, kind, "value", false, false)
+        );
+    }
+
+    let value = match *kind {
+        Kind::Complex => unreachable!(),
+        Kind::Text => reference,
+        Kind::Date => format!("{}.format(\"%F\")", binding),
+        Kind::DateTime => format!("{}.format(DATE_TIME_FORMAT)", binding),
+        Kind::Display => binding.to_string(),
+        Kind::Value => format!("{}.value()", binding),
+    };
+
+    format!("write_value({:?}, {}, writer)?;", element, value)
+}
+
+/// Whether values of `type_name` are free text, which the schema requires
+/// to be non-empty when the element is required
+fn is_free_text(schema: &Schema, type_name: &str) -> Result<bool, String> {
+    match type_name {
+        "xs:string" | "xs:token" => Ok(true),
+        _ => match schema.simple_type(type_name) {
+            Some(simple) => Ok(matches!(simple_kind(simple)?, SimpleKind::Open)),
+            None => Ok(false),
+        },
+    }
+}
+
+/// Whether `complex` is made of optional elements only, and is only ever
+/// used where it can be left out or repeated, so an empty one is written as
+/// an element with no content that could have been left out instead
+fn is_optional_wrapper(schema: &Schema, complex: &ComplexType) -> bool {
+    let required_singly = schema
+        .complex_types
+        .iter()
+        .flat_map(|parent| &parent.elements)
+        .chain(&schema.elements)
+        .any(|element| {
+            element.type_name == complex.name && !element.is_optional() && !element.is_repeated()
+        });
+
+    !complex.elements.is_empty()
+        && complex.elements.iter().all(Element::is_optional)
+        && !required_singly
+}
+
+/// `Validate` impls for every complex type, checking what the types
+/// themselves can't enforce: cardinalities of repeated elements, empty
+/// required text, and optional wrappers without content
+pub fn validators(schema: &Schema) -> Result<String, String> {
+    let mut out = String::from(HEADER);
+    out.push_str("\nuse super::*;\n");
+
+    for complex in &schema.complex_types {
+        validator(&mut out, schema, complex)?;
+    }
+
+    Ok(out)
+}
+
+fn validator(out: &mut String, schema: &Schema, complex: &ComplexType) -> Result<(), String> {
+    let mut statements = Vec::new();
+
+    if is_optional_wrapper(schema, complex) {
+        let empty: Vec<String> = complex
+            .elements
+            .iter()
+            .map(|element| {
+                format!(
+                    "self.{}.is_none()",
+                    names::field_name(&complex.name, &element.name)
+                )
+            })
+            .collect();
+        statements.push(format!(
+            "check_wrapper(path, {}, violations);",
+            empty.join(" && ")
+        ));
+    }
+
+    for element in &complex.elements {
+        let field = names::field_name(&complex.name, &element.name);
+        let name = &element.name;
+        let complex_child = schema.complex_type(&element.type_name).is_some();
+        let free_text = is_free_text(schema, &element.type_name)?;
+
+        if element.is_repeated() {
+            let bound = |binding: &str| {
+                let max = match element.max {
+                    Some(max) => format!("Some({})", max),
+                    None => "None".to_string(),
+                };
+                format!(
+                    "check_count(path, {:?}, {}.len(), ({}, {}), violations);",
+                    name, binding, element.min, max
+                )
+            };
+            let mut body = Vec::new();
+            let binding = if element.is_optional() {
+                field.clone()
+            } else {
+                format!("self.{}", field)
+            };
+            if element.min > 0 || element.max.is_some() {
+                body.push(bound(&binding));
+            }
+            if complex_child {
+                let reference = if element.is_optional() {
+                    binding.clone()
+                } else {
+                    format!("&{}", binding)
+                };
+                body.push(format!(
+                    "validate_children(path, {:?}, {}, violations);",
+                    name, reference
+                ));
+            } else if free_text && !element.is_optional() {
+                body.push(format!(
+                    "for (i, value) in {}.iter().enumerate() {{\n\
+                     check_string(&format!(\"{{}}[{{}}]\", child_path(path, {:?}), i), {:?}, {}, violations);\n}}",
+                    binding,
+                    name,
+                    name,
+                    text_value(&element.type_name, "value")
+                ));
+            }
+
+            if body.is_empty() {
+                continue;
+            }
+            if element.is_optional() {
+                statements.push(format!(
+                    "if let Some(ref {field}) = self.{field} {{\n{}\n}}",
+                    body.join("\n"),
+                    field = field
+                ));
+            } else {
+                statements.extend(body);
+            }
+        } else if complex_child {
+            let function = if element.is_optional() {
+                "validate_optional_child"
+            } else {
+                "validate_child"
+            };
+            statements.push(format!(
+                "{}(path, {:?}, &self.{}, violations);",
+                function, name, field
+            ));
+        } else if free_text && !element.is_optional() {
+            statements.push(format!(
+                "check_string(path, {:?}, {}, violations);",
+                name,
+                text_value(&element.type_name, &format!("self.{}", field))
+            ));
+        }
+    }
+
+    let (path, violations) = if statements.is_empty() {
+        ("_path", "_violations")
+    } else {
+        ("path", "violations")
+    };
+    writeln!(
+        out,
+        "\nimpl Validate for {} {{\n    \
+         fn validate(&self, {}: &str, {}: &mut Vec<Violation>) {{\n{}\n}}\n}}",
+        complex.name,
+        path,
+        violations,
+        statements.join("\n\n")
+    )
+    .unwrap();
+
+    Ok(())
+}
+
+/// The `&str` of a free text `binding`
+fn text_value(type_name: &str, binding: &str) -> String {
+    match type_name {
+        "xs:string" | "xs:token" => format!("&{}", binding),
+        _ => format!("{}.value()", binding),
+    }
+}
+
+/// The elements of every complex type, with the fields they're read into, for
+/// the parts of the crate that walk the schema at runtime
+pub fn elements(schema: &Schema) -> Result<String, String> {
+    let mut out = String::from(HEADER);
+    out.push_str("\nuse super::*;\n");
+    out.push_str("\npub(super) const COMPLEX_TYPES: &[SchemaType] = &[\n");
+
+    for complex in &schema.complex_types {
+        writeln!(
+            out,
+            "SchemaType {{\nname: {:?},\nelements: &[",
+            complex.name
+        )
+        .unwrap();
+        for element in &complex.elements {
+            let kind = match resolve(schema, &element.type_name)?.kind {
+                Kind::Complex => format!("Kind::Complex({:?})", element.type_name),
+                Kind::Date => "Kind::Date".to_string(),
+                Kind::DateTime => "Kind::DateTime".to_string(),
+                _ => "Kind::Text".to_string(),
+            };
+            writeln!(
+                out,
+                "SchemaElement {{ name: {:?}, field: {:?}, kind: {}, repeated: {} }},",
+                element.name,
+                names::field_name(&complex.name, &element.name),
+                kind,
+                element.is_repeated()
+            )
+            .unwrap();
+        }
+        out.push_str("],\n},\n");
+    }
+    out.push_str("];\n");
+
+    Ok(out)
+}
@@ -0,0 +1,123 @@
+//! # aqi-nacor-codegen
+//! Generates the schema types, writer, validator and element table from
+//! `schemas/AQISchema.xsd`
+//!
+//! Run `cargo run -p aqi-nacor-codegen` after changing the schema or
+//! `names.rs`, or pass `--check` to exit with an error instead of writing
+//! when the generated files are out of date.
+
+extern crate xml;
+
+mod emit;
+mod names;
+mod xsd;
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+
+const SCHEMA: &str = "schemas/AQISchema.xsd";
+const TYPES: &str = "src/schema/generated.rs";
+const WRITERS: &str = "src/schema/writer/generated.rs";
+const VALIDATORS: &str = "src/schema/validate/generated.rs";
+const ELEMENTS: &str = "src/schema/elements/generated.rs";
+
+fn main() {
+    let check = env::args().skip(1).any(|arg| arg == "--check");
+
+    match run(check) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
+    }
+}
+
+/// Returns whether the generated files are, or are now, up to date
+fn run(check: bool) -> Result<bool, String> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("codegen is in the workspace root");
+
+    let source = fs::read_to_string(root.join(SCHEMA))
+        .map_err(|err| format!("Failed to read {}: {}", SCHEMA, err))?;
+    let schema = xsd::parse(&source).map_err(|err| format!("{}: {}", SCHEMA, err))?;
+
+    for entry in names::stale_entries(&schema) {
+        eprintln!(
+            "warning: names.rs has a {}, which isn't in the schema",
+            entry
+        );
+    }
+
+    let outputs = [
+        (TYPES, emit::types(&schema)?),
+        (WRITERS, emit::writers(&schema)?),
+        (VALIDATORS, emit::validators(&schema)?),
+        (ELEMENTS, emit::elements(&schema)?),
+    ];
+
+    let mut up_to_date = true;
+    for (path, generated) in outputs.iter() {
+        let generated = rustfmt(generated)?;
+        let current = fs::read_to_string(root.join(path)).unwrap_or_default();
+
+        if current == generated {
+            continue;
+        }
+
+        if check {
+            eprintln!("{} is out of date", path);
+            up_to_date = false;
+        } else {
+            fs::write(root.join(path), generated)
+                .map_err(|err| format!("Failed to write {}: {}", path, err))?;
+            eprintln!("Wrote {}", path);
+        }
+    }
+
+    Ok(up_to_date)
+}
+
+/// Formats the way `cargo fmt` would, so the output survives it unchanged
+fn rustfmt(source: &str) -> Result<String, String> {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2018"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to run rustfmt: {}", err))?;
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(source.as_bytes())
+        .map_err(|err| format!("Failed to run rustfmt: {}", err))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("Failed to run rustfmt: {}", err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "rustfmt failed on generated code:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_files_are_up_to_date() {
+        assert_eq!(run(true), Ok(true), "run `cargo run -p aqi-nacor-codegen`");
+    }
+}
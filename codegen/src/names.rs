@@ -0,0 +1,600 @@
+//! Rust names for things the schema names differently, or not at all
+//!
+//! Anything not listed here gets a name derived from the schema: fields are
+//! the snake case of their element and variants the camel case of the words
+//! in their value. Entries that no longer match anything in the schema are
+//! reported when generating.
+
+use crate::xsd::Schema;
+
+/// Types implemented by hand in `src/schema/mod.rs`
+pub const HAND_WRITTEN_TYPES: &[&str] = &["ICDValueType"];
+
+/// Complex types whose `WritableSchemaType` impl is in `src/schema/writer.rs`
+pub const HAND_WRITTEN_WRITERS: &[&str] = &["AnesthesiaRecordsType"];
+
+/// Doc comment lines for simple types, by type
+pub const TYPE_DOCS: &[(&str, &[&str])] = &[(
+    "TypeVendorType",
+    &[
+        "1 = Billing",
+        "2 = Quality / Outcomes",
+        "3 = AIMS only",
+        "4 = EMR/EHR (with or without AIMS)",
+    ],
+)];
+
+/// Complex type, element and field name
+pub const FIELDS: &[(&str, &str, &str)] = &[
+    (
+        "AnesthesiaRecordsType",
+        "AnesthesiaRecord",
+        "anesthesia_records",
+    ),
+    ("RecordHeaderType", "AQIXMLVersion", "aqi_xml_version"),
+    (
+        "AnesthesiaMethodType",
+        "AnesthesiaSubCategory",
+        "anesthesia_subcategory",
+    ),
+    ("MedicationType", "MixtureMedication", "mixture_medications"),
+    (
+        "MedicationsTotalSetType",
+        "MedicationsTotal",
+        "medications_totals",
+    ),
+];
+
+/// Simple type and the prefix for variants whose values don't start with a
+/// letter, `Value` for types not listed
+pub const VARIANT_PREFIXES: &[(&str, &str)] = &[("AQIXMLVersionType", "Version")];
+
+/// Simple type, value and variant name
+pub const VARIANTS: &[(&str, &str, &str)] = &[
+    ("RaceCodeType", "American Indian or Alaska Native", "Native"),
+    ("RaceCodeType", "Asian or Pacific Islander", "Asian"),
+    ("RaceCodeType", "Bi or Multi Racial", "Multiracial"),
+    ("RaceCodeType", "Black, not of Hispanic Origin", "Black"),
+    ("RaceCodeType", "Hispanic, Color Unknown", "Hispanic"),
+    ("RaceCodeType", "White, not of Hispanic Origin", "White"),
+    ("RaceCodeType", "OTHER", "Other"),
+    ("LocationTypeCodeType", "1", "Pharmacy"),
+    ("LocationTypeCodeType", "2", "Telehealth"),
+    ("LocationTypeCodeType", "3", "School"),
+    ("LocationTypeCodeType", "4", "HomelessShelter"),
+    ("LocationTypeCodeType", "5", "IndianHealthServiceFreeStandingFacility"),
+    ("LocationTypeCodeType", "6", "IndianHealthServiceProviderBasedFacility"),
+    ("LocationTypeCodeType", "7", "Tribal638FreeStandingFacility"),
+    ("LocationTypeCodeType", "8", "Tribal638ProviderBasedFacility"),
+    ("LocationTypeCodeType", "9", "Prison"),
+    ("LocationTypeCodeType", "11", "Office"),
+    ("LocationTypeCodeType", "12", "Home"),
+    ("LocationTypeCodeType", "13", "AssistedLivingFacility"),
+    ("LocationTypeCodeType", "14", "GroupHome"),
+    ("LocationTypeCodeType", "15", "MobileUnit"),
+    ("LocationTypeCodeType", "16", "TemporaryLodging"),
+    ("LocationTypeCodeType", "17", "WalkInRetailHealthClinic"),
+    ("LocationTypeCodeType", "18", "PlaceOfEmploymentWorksite"),
+    ("LocationTypeCodeType", "19", "OffCampusOutpatientHospital"),
+    ("LocationTypeCodeType", "20", "UrgentCareFacility"),
+    ("LocationTypeCodeType", "21", "InpatientHospital"),
+    ("LocationTypeCodeType", "22", "OnCampusOutpatientHospital"),
+    ("LocationTypeCodeType", "23", "EmergencyRoom"),
+    ("LocationTypeCodeType", "24", "AmbulatorySurgicalCenter"),
+    ("LocationTypeCodeType", "25", "BirthingCenter"),
+    ("LocationTypeCodeType", "26", "MilitaryTreatmentFacility"),
+    ("LocationTypeCodeType", "31", "SkilledNursingFacility"),
+    ("LocationTypeCodeType", "32", "NursingFacility"),
+    ("LocationTypeCodeType", "33", "CustodialCareFacility"),
+    ("LocationTypeCodeType", "34", "Hospice"),
+    ("LocationTypeCodeType", "41", "AmbulanceLand"),
+    ("LocationTypeCodeType", "42", "AmbulanceAirOrWater"),
+    ("LocationTypeCodeType", "49", "IndependentClinic"),
+    ("LocationTypeCodeType", "50", "FederallyQualifiedHealthCenter"),
+    ("LocationTypeCodeType", "51", "InpatientPsychiatricFacility"),
+    ("LocationTypeCodeType", "52", "PsychiatricFacilityPartialHospitalization"),
+    ("LocationTypeCodeType", "53", "CommunityMentalHealthCenter"),
+    ("LocationTypeCodeType", "54", "IntermediateCareFacility"),
+    ("LocationTypeCodeType", "55", "ResidentialSubstanceAbuseTreatmentFacility"),
+    ("LocationTypeCodeType", "56", "PsychiatricResidentialTreatmentCenter"),
+    ("LocationTypeCodeType", "57", "NonResidentialSubstanceAbuseTreatmentFacility"),
+    ("LocationTypeCodeType", "60", "MassImmunizationCenter"),
+    ("LocationTypeCodeType", "61", "ComprehensiveInpatientRehabFacility"),
+    ("LocationTypeCodeType", "62", "ComprehensiveOutpatientRehabFacility"),
+    ("LocationTypeCodeType", "65", "EndStageRenalDiseaseTreatmentFacility"),
+    ("LocationTypeCodeType", "71", "PublicHealthClinic"),
+    ("LocationTypeCodeType", "72", "RuralHealthClinic"),
+    ("LocationTypeCodeType", "81", "IndependentLaboratory"),
+    ("LocationTypeCodeType", "99", "Other"),
+    ("TransferStatusCodeType", "Transferred to another hospital", "Transferred"),
+    ("CoverageCodeType", "MD-ALONE", "MdAlone"),
+    ("CoverageCodeType", "MD-DIRECTING", "MdDirecting"),
+    ("CoverageCodeType", "MD PRESENT BUT NOT DIRECTING", "MdPresent"),
+    ("CoverageCodeType", "CRNA-ALONE", "CrnaAlone"),
+    ("CoverageCodeType", "CRNA-DIRECTED", "CrnaDirected"),
+    ("CoverageCodeType", "CRNA-SUPERVISING", "CrnaSupervising"),
+    ("CoverageCodeType", "CRNA-SUPERVISED", "CrnaSupervised"),
+    ("CoverageCodeType", "MD-SUPERVISING", "MdSupervising"),
+    ("CoverageCodeType", "MD-MD", "Md"),
+    ("CoverageCodeType", "PA-ALONE", "PaAlone"),
+    ("CoverageCodeType", "PA-DIRECTED", "PaDirected"),
+    ("CoverageCodeType", "CAA-DIRECTED", "CaaDirected"),
+    ("StaffResponsibilityCodeType", "Performing the case", "Performing"),
+    ("StaffResponsibilityCodeType", "Medically responsible", "Responsible"),
+    ("ProviderCredentialsCodeType", "Advanced Practice Nurse", "AP"),
+    ("ProviderCredentialsCodeType", "Certified Anesthesiologist Assistant", "CAA"),
+    ("ProviderCredentialsCodeType", "Certified Registered Nurse Anesthetist", "CRNA"),
+    ("ProviderCredentialsCodeType", "Dentist or Oral Surgeon", "Dentist"),
+    ("ProviderCredentialsCodeType", "Fellow (Anesthesiology)", "Fellow"),
+    ("ProviderCredentialsCodeType", "Physician Assistant", "PA"),
+    ("ProviderCredentialsCodeType", "Registered Nurse", "RN"),
+    ("ProviderCredentialsCodeType", "Resident (Anesthesia)", "Resident"),
+    ("ProviderCredentialsCodeType", "Student Registered Nurse Anesthetist", "SRNA"),
+    ("MedicalSpecialtyCodeType", "Cardiology-EP", "CardiologyEp"),
+    ("MedicalSpecialtyCodeType", "Dental/oral", "Dental"),
+    ("MedicalSpecialtyCodeType", "Orthopedics-Foot", "OrthoFoot"),
+    ("MedicalSpecialtyCodeType", "Orthopedics-Hand/Wrist", "OrthoHandWrist"),
+    ("MedicalSpecialtyCodeType", "Orthopedics-Major Joint", "OrthoMajorJoint"),
+    ("MedicalSpecialtyCodeType", "Orthopedics-Other", "OrthoOther"),
+    ("MedicalSpecialtyCodeType", "Orthopedics-Sports Med", "OrthoSportsMed"),
+    ("MedicalSpecialtyCodeType", "Orthopedics-Trauma", "OrthoTrauma"),
+    ("MedicalSpecialtyCodeType", "Rehabilitation", "Rehab"),
+    ("AnesthesiaCategoryCodeType", "No Anesthesia Provided", "NoAnesthesia"),
+    ("AnesthesiaSubCategoryCodeType", "Combined Spinal and Epidural", "Combined"),
+    ("AnesthesiaSubCategoryCodeType", "Inhalational General Anesthesia", "InhalationalGeneral"),
+    ("AnesthesiaSubCategoryCodeType", "Total Intravenous Anesthesia", "TotalIntravenous"),
+    ("ICDVersionType", "9", "Nine"),
+    ("ICDVersionType", "10", "Ten"),
+    ("PostOpDischargeCodeType", "Home or self care (routine discharge)", "Home"),
+    ("PostOpDischargeCodeType", "Short term general hospital for inpatient care", "ShortTermGeneralHospital"),
+    ("PostOpDischargeCodeType", "Skilled nursing facility (SNF)", "SkilledNursingFacility"),
+    ("PostOpDischargeCodeType", "Intermediate care facility (ICF)", "IntermediateCareFacility"),
+    ("PostOpDischargeCodeType", "Another type of institution", "OtherInstitution"),
+    ("PostOpDischargeCodeType", "Home under care of organized home health service organization", "HomeUnderOrganizedCare"),
+    ("PostOpDischargeCodeType", "Left against medical advice or discontinued care", "LeftAgainstMedicalAdvice"),
+    ("PostOpDischargeCodeType", "Home under care of Home IV provider", "HomeIVProvider"),
+    ("PostOpDischargeCodeType", "Admitted as an inpatient to this hospital", "AdmittedAsInpatient"),
+    ("PostOpDischargeCodeType", "Expired (i.e. dead)", "Expired"),
+    ("PostOpDischargeCodeType", "Still patient or expected to return for outpatient services (i.e. still a patient)", "StillPatient"),
+    ("PostOpDischargeCodeType", "Expired (i.e. died) at home", "ExpiredAtHome"),
+    ("PostOpDischargeCodeType", "Expired (i.e. died) in a medical facility; e.g., hospital, SNF, ICF, or free standing hospice", "ExpiredAtMedicalFacility"),
+    ("PostOpDischargeCodeType", "Expired (i.e. died) - place unknown", "ExpiredAtUnknown"),
+    ("PaymentMethodCodeType", "Government: Medicare Fee for Service - Part A", "GovernmentMedicareA"),
+    ("PaymentMethodCodeType", "Government: Medicare Fee for Service - Part B", "GovernmentMedicareB"),
+    ("PaymentMethodCodeType", "Government: Medicare Fee for Service - Part C", "GovernmentMedicareC"),
+    ("PaymentMethodCodeType", "Worker's Compensation", "WorkersCompensation"),
+    ("ICCategoryCodeType", "MEDICAL DEVICE/EQUIPMENT", "MedicalDeviceEquipment"),
+    ("ICCategoryCodeType", "MEDICATION", "Medication"),
+    ("ICCategoryCodeType", "INFRASTRUCTURE/SYSTEM", "InfrastructureSystem"),
+    ("ICCategoryCodeType", "ASSESSMENT/DOCUMENTATION", "AssessmentDocumentation"),
+    ("ICCategoryCodeType", "RESPIRATORY/AIRWAY", "RespiratoryAirway"),
+    ("ICCategoryCodeType", "CARDIOVASCULAR", "Cardiovascular"),
+    ("ICCategoryCodeType", "PROCEDURE RELATED", "ProcedureRelated"),
+    ("ICCategoryCodeType", "OTHER", "Other"),
+    ("ICCategoryCodeType", "UNKNOWN", "Unknown"),
+    ("OutcomeIDType", "1", "Acidemia"),
+    ("OutcomeIDType", "2", "AcuteKidneyInjury"),
+    ("OutcomeIDType", "3", "AdverseDrugReaction"),
+    ("OutcomeIDType", "4", "AirwayObstruction"),
+    ("OutcomeIDType", "5", "AirwayTrauma"),
+    ("OutcomeIDType", "6", "AmnioticFluidEmbolism"),
+    ("OutcomeIDType", "7", "Anaphylaxis"),
+    ("OutcomeIDType", "8", "Arrhythmia"),
+    ("OutcomeIDType", "9", "Aspiration"),
+    ("OutcomeIDType", "10", "Awareness"),
+    ("OutcomeIDType", "11", "Bradycardia"),
+    ("OutcomeIDType", "12", "BurnInjury"),
+    ("OutcomeIDType", "13", "CannotVentilate"),
+    ("OutcomeIDType", "14", "CardiacArrest"),
+    ("OutcomeIDType", "15", "CaseCancelledAfterAnesthesiaInductionTime"),
+    ("OutcomeIDType", "16", "CaseCancelledBeforeAnesthesiaInductionTime"),
+    ("OutcomeIDType", "17", "CaseCancelledBeforeAnesthesiaStartTime"),
+    ("OutcomeIDType", "18", "CaseDelay"),
+    ("OutcomeIDType", "19", "CentralLinePlacementInjury"),
+    ("OutcomeIDType", "20", "CentralLineAssociatedBloodstreamInfection"),
+    ("OutcomeIDType", "21", "CerebrovascularAccident"),
+    ("OutcomeIDType", "22", "Coagulopathy"),
+    ("OutcomeIDType", "23", "Coma"),
+    ("OutcomeIDType", "24", "CornealInjury"),
+    ("OutcomeIDType", "25", "Death"),
+    ("OutcomeIDType", "26", "DeepVeinThrombosis"),
+    ("OutcomeIDType", "27", "DelayedEmergence"),
+    ("OutcomeIDType", "28", "Delirium"),
+    ("OutcomeIDType", "29", "DifficultIntubation"),
+    ("OutcomeIDType", "30", "DifficultMaskVentilation"),
+    ("OutcomeIDType", "31", "ElectrolyteAbnormality"),
+    ("OutcomeIDType", "32", "EmergenceDelirium"),
+    ("OutcomeIDType", "33", "EquipmentDeviceFailureOrMalfunction"),
+    ("OutcomeIDType", "34", "FailedIntubation"),
+    ("OutcomeIDType", "35", "Fall"),
+    ("OutcomeIDType", "36", "FatEmbolism"),
+    ("OutcomeIDType", "37", "HemodynamicInstability"),
+    ("OutcomeIDType", "38", "HighSpinalOrEpidural"),
+    ("OutcomeIDType", "39", "Hypercapnia"),
+    ("OutcomeIDType", "40", "Hyperglycemia"),
+    ("OutcomeIDType", "41", "HypertensiveEpisode"),
+    ("OutcomeIDType", "42", "Hyperthermia"),
+    ("OutcomeIDType", "43", "Hypoglycemia"),
+    ("OutcomeIDType", "44", "HypotensiveEpisode"),
+    ("OutcomeIDType", "45", "Hypoxemia"),
+    ("OutcomeIDType", "46", "InadequatePainControl"),
+    ("OutcomeIDType", "47", "InadequateReversalOfNeuromuscularBlock"),
+    ("OutcomeIDType", "48", "InfectionFollowingEpiduralOrSpinalAnesthesia"),
+    ("OutcomeIDType", "49", "InfectionFollowingPeripheralNerveBlock"),
+    ("OutcomeIDType", "50", "Itching"),
+    ("OutcomeIDType", "51", "IVInfiltration"),
+    ("OutcomeIDType", "52", "KidneyFailure"),
+    ("OutcomeIDType", "53", "LocalAnestheticSystemicToxicity"),
+    ("OutcomeIDType", "54", "MalignantHyperthermia"),
+    ("OutcomeIDType", "55", "MedicationError"),
+    ("OutcomeIDType", "56", "MultipleOrganFailure"),
+    ("OutcomeIDType", "57", "MyocardiaIschemia"),
+    ("OutcomeIDType", "58", "MyocardialInfarction"),
+    ("OutcomeIDType", "59", "ORFire"),
+    ("OutcomeIDType", "60", "PerioperativeVisualLoss"),
+    ("OutcomeIDType", "61", "PeripheralNeurologicDeficitAfterRegionalAnesthesia"),
+    ("OutcomeIDType", "62", "Pneumonia"),
+    ("OutcomeIDType", "63", "Pneumothorax"),
+    ("OutcomeIDType", "64", "PositioningInjury"),
+    ("OutcomeIDType", "65", "PostDischargeNauseaAndVomiting"),
+    ("OutcomeIDType", "66", "PostDuralPunctureHeadache"),
+    ("OutcomeIDType", "67", "PostOperativeCognitiveDysfunction"),
+    ("OutcomeIDType", "68", "PostOperativeNauseaAndVomiting"),
+    ("OutcomeIDType", "69", "ProlongedNeuromuscularBlock"),
+    ("OutcomeIDType", "70", "PulmonaryEdema"),
+    ("OutcomeIDType", "71", "PulmonaryEmbolus"),
+    ("OutcomeIDType", "72", "RespiratoryArrest"),
+    ("OutcomeIDType", "73", "RespiratoryFailure"),
+    ("OutcomeIDType", "74", "Seizure"),
+    ("OutcomeIDType", "75", "Sepsis"),
+    ("OutcomeIDType", "76", "Shivering"),
+    ("OutcomeIDType", "77", "SkinOrMucousMembraneInjury"),
+    ("OutcomeIDType", "78", "SpinalCordInjury"),
+    ("OutcomeIDType", "79", "SpinalHematomaFollowingSpinalOrEpiduralAnesthesia"),
+    ("OutcomeIDType", "80", "SurgicalSiteInfection"),
+    ("OutcomeIDType", "81", "TransfusionReaction"),
+    ("OutcomeIDType", "82", "Ulcer"),
+    ("OutcomeIDType", "83", "UnanticipatedTransfusion"),
+    ("OutcomeIDType", "84", "UnplannedConversionToGeneralAnesthesia"),
+    ("OutcomeIDType", "85", "UnplannedDuralPuncture"),
+    ("OutcomeIDType", "86", "UnplannedEndobronchialIntubation"),
+    ("OutcomeIDType", "87", "UnplannedEsophagealIntubation"),
+    ("OutcomeIDType", "88", "UnplannedHospitalAdmission"),
+    ("OutcomeIDType", "89", "UnplannedHypothermia"),
+    ("OutcomeIDType", "90", "UnplannedICUAdmission"),
+    ("OutcomeIDType", "91", "UnplannedPostoperativeVentilation"),
+    ("OutcomeIDType", "92", "UnplannedReintubation"),
+    ("OutcomeIDType", "93", "UnplannedReoperation"),
+    ("OutcomeIDType", "94", "UnplannedTrachealExtubation"),
+    ("OutcomeIDType", "95", "VascularInjury"),
+    ("OutcomeIDType", "96", "VenousAirEmbolism"),
+    ("OutcomeIDType", "97", "VentilationForMoreThan24HoursPostProcedure"),
+    ("OutcomeIDType", "98", "WrongPatient"),
+    ("OutcomeIDType", "99", "WrongProcedure"),
+    ("OutcomeIDType", "100", "WrongSiteRegionalAnesthesia"),
+    ("OutcomeIDType", "101", "WrongSiteSurgery"),
+    ("OutcomeIDType", "102", "InjuryRelatedToSensoryOrMotorDeficitAfterLocalOrRegionalAnesthesia"),
+    ("OutcomeIDType", "103", "UnplannedElectricShock"),
+    ("OutcomeTimeFrameCodeType", "PACU", "Pacu"),
+    ("OutcomeTimeFrameCodeType", "24 Hour", "TwentyFourHour"),
+    ("OutcomeTimeFrameCodeType", "48 Hour", "FourtyEightHour"),
+    ("OutcomeTimeFrameCodeType", "7 Days", "SevenDays"),
+    ("OutcomeTimeFrameCodeType", "30 Days", "ThirtyDays"),
+    ("OutcomeTimeFrameCodeType", "60 Days", "SixtyDays"),
+    ("OutcomeTimeFrameCodeType", "90 Days", "NinetyDays"),
+    ("OutcomeTimeFrameCodeType", "1 Year", "OneYear"),
+    ("QCDRModifierType", "", "None"),
+    ("QCDRModifierType", "1P", "OneP"),
+    ("QCDRModifierType", "2P", "TwoP"),
+    ("QCDRModifierType", "3P", "ThreeP"),
+    ("QCDRModifierType", "8P", "EightP"),
+];
+
+/// Known values of the string types the schema only gives examples for, as
+/// variant name and value
+///
+/// These become `open_enum_map!`s, other unrestricted string types become
+/// `schema_string_tuple_struct!`s.
+pub const VOCABULARIES: &[(&str, &[(&str, &str)])] = &[
+    (
+        "MonitorCodeType",
+        &[
+            ("ArterialLine", "Arterial Line"),
+            ("BIS", "BIS"),
+            ("CentralVenousPressure", "Central Venous Pressure"),
+            ("ECG", "ECG"),
+            ("EEG", "EEG"),
+            ("EndTidalCO2", "End Tidal CO2"),
+            ("EvokedPotentials", "Evoked Potentials"),
+            ("NerveStimulator", "Nerve Stimulator"),
+            ("NoninvasiveBloodPressure", "Noninvasive Blood Pressure"),
+            ("PulmonaryArteryCatheter", "Pulmonary Artery Catheter"),
+            ("PulseOximetry", "Pulse Oximetry"),
+            ("Temperature", "Temperature"),
+            (
+                "TransesophagealEchocardiography",
+                "Transesophageal Echocardiography",
+            ),
+            ("UrinaryCatheter", "Urinary Catheter"),
+            ("Unknown", "Unknown"),
+        ],
+    ),
+    (
+        "AnesthesiaInductionCodeType",
+        &[
+            ("Inhalation", "Inhalation"),
+            ("Intravenous", "Intravenous"),
+            ("Rectal", "Rectal"),
+            ("Intramuscular", "Intramuscular"),
+            ("Unknown", "Unknown"),
+        ],
+    ),
+    (
+        "AnesthesiaMaintenanceCodeType",
+        &[
+            ("CircleSystem", "Circle System"),
+            ("NonRebreathing", "Non-Rebreathing"),
+            ("Unknown", "Unknown"),
+        ],
+    ),
+    (
+        "AirwayManagementMethodCodeType",
+        &[
+            ("AmbuBagRescue", "Ambu Bag Rescue"),
+            ("ArtificialRespiration", "Artificial Respiration"),
+            ("Awake", "Awake"),
+            ("ControlledVentilation", "Controlled Ventilation"),
+            (
+                "DoubleLumenEndotrachealtubes",
+                "Double Lumen Endotracheal Tubes",
+            ),
+            ("EndobronchialBlocker", "Endobronchial Blocker"),
+            ("EndotrachealTube", "Endotracheal Tube"),
+            ("EndotrachealAwake", "Endotracheal Awake"),
+            (
+                "EndotrachealFiberopticBronchoscope",
+                "Endotracheal Fiberoptic Bronchoscope",
+            ),
+            (
+                "EndotrachealFiberopticEndoscope",
+                "Endotracheal Fiberoptic Endoscope",
+            ),
+            (
+                "EndotrachealFiberopticLaryngoscope",
+                "Endotracheal Fiberoptic Laryngoscope",
+            ),
+            (
+                "EndotrachealVideoLaryngoscope",
+                "Endotracheal Video Laryngoscope",
+            ),
+            ("FacialMask", "Facial Mask"),
+            ("Fiberoptic", "Fiberoptic"),
+            ("FiberopticEndoscope", "Fiberoptic Endoscope"),
+            ("FiberopticLaryngoscope", "Fiberoptic Laryngoscope"),
+            ("FOBElective", "FOB Elective"),
+            ("FOBRequired", "FOB Required"),
+            ("GlidescopeElective", "Glidescope Elective"),
+            ("GlidescopeRequired", "Glidescope Required"),
+            ("LaryngealMaskAirway", "Laryngeal Mask Airway"),
+            (
+                "LaryngealMaskAirwayClassic",
+                "Laryngeal Mask Airway Classic",
+            ),
+            (
+                "LaryngealMaskAirwayNonClassic",
+                "Laryngeal Mask Airway Non-Classic",
+            ),
+            ("LightedStylet", "Lighted Stylet"),
+            ("Nasalcannula", "Nasal Cannula"),
+            ("Natural", "Natural"),
+            ("OxygenTherapy", "Oxygen Therapy"),
+            ("RoomAir", "Room Air"),
+            ("SupraglotticDevice", "Supraglottic Device"),
+            ("VideoLaryngoscope", "Video Laryngoscope"),
+            ("Unknown", "Unknown"),
+        ],
+    ),
+    (
+        "AirwayManagementSubMethodCodeType",
+        &[
+            ("InverseRatioVentilation", "Inverse Ratio Ventilation"),
+            ("HighFrequencyVentilation", "High Frequency Ventilation"),
+            (
+                "TranstrachealJetVentilation",
+                "Transtracheal Jet Ventilation",
+            ),
+            ("ContinuousFlowVentilation", "Continuous Flow Ventilation"),
+            ("Unknown", "Unknown"),
+        ],
+    ),
+    (
+        "MedicationTypeCodeType",
+        &[
+            ("Bolus", "Bolus"),
+            ("Infusion", "Infusion"),
+            ("Inhalational", "Inhalational"),
+            ("Unknown", "Unknown"),
+        ],
+    ),
+    (
+        "RouteCodeType",
+        &[
+            ("Intravenous", "IV"),
+            ("Intramuscular", "IM"),
+            ("Oral", "PO"),
+            ("Subcutaneous", "SQ"),
+            ("Inhalation", "Inhalation"),
+            ("Epidural", "Epidural"),
+            ("Intrathecal", "Intrathecal"),
+            ("Perineural", "Perineural"),
+            ("Intranasal", "Intranasal"),
+            ("Rectal", "Rectal"),
+            ("Sublingual", "Sublingual"),
+            ("Topical", "Topical"),
+            ("Transdermal", "Transdermal"),
+            ("Unknown", "Unknown"),
+        ],
+    ),
+    (
+        "OutputCodeType",
+        &[
+            ("EstimatedBloodLoss", "Estimated Blood Loss"),
+            ("Urine", "Urine"),
+            ("GastricDrainage", "Gastric Drainage"),
+            ("ChestTube", "Chest Tube"),
+            ("CellSaver", "Cell Saver"),
+            ("Unknown", "Unknown"),
+        ],
+    ),
+    (
+        "PostOpDispositionCodeType",
+        &[
+            ("PACU", "PACU"),
+            ("ICU", "ICU"),
+            ("Floor", "Floor"),
+            ("StepDownUnit", "Step Down Unit"),
+            ("Home", "Home"),
+            ("OperatingRoom", "Operating Room"),
+            ("Morgue", "Morgue"),
+            ("Unknown", "Unknown"),
+        ],
+    ),
+    (
+        "TimingMilestoneCodeType",
+        &[
+            ("PatientInRoom", "Patient In Room"),
+            ("AnesthesiaReady", "Anesthesia Ready"),
+            ("IncisionStart", "Incision Start"),
+            ("IncisionClose", "Incision Close"),
+            ("PatientOutOfRoom", "Patient Out Of Room"),
+            ("PACUArrival", "PACU Arrival"),
+            ("PACUReadyForDischarge", "PACU Ready For Discharge"),
+            ("PACUDischarge", "PACU Discharge"),
+            ("Unknown", "Unknown"),
+        ],
+    ),
+];
+
+pub fn field_name(complex_type: &str, element: &str) -> String {
+    FIELDS
+        .iter()
+        .find(|&&(parent, name, _)| parent == complex_type && name == element)
+        .map(|&(_, _, field)| field.to_string())
+        .unwrap_or_else(|| snake_case(element))
+}
+
+pub fn variant_name(simple_type: &str, value: &str) -> String {
+    if let Some(&(_, _, variant)) = VARIANTS
+        .iter()
+        .find(|&&(name, known, _)| name == simple_type && known == value)
+    {
+        return variant.to_string();
+    }
+
+    let name: String = value
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect();
+
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        let prefix = VARIANT_PREFIXES
+            .iter()
+            .find(|&&(name, _)| name == simple_type)
+            .map_or("Value", |&(_, prefix)| prefix);
+
+        format!("{}{}", prefix, name)
+    }
+}
+
+pub fn vocabulary(simple_type: &str) -> Option<&'static [(&'static str, &'static str)]> {
+    VOCABULARIES
+        .iter()
+        .find(|&&(name, _)| name == simple_type)
+        .map(|&(_, vocabulary)| vocabulary)
+}
+
+pub fn type_docs(name: &str) -> &'static [&'static str] {
+    TYPE_DOCS
+        .iter()
+        .find(|&&(type_name, _)| type_name == name)
+        .map_or(&[], |&(_, docs)| docs)
+}
+
+/// `AQIXMLVersion` -> `aqixml_version`, acronyms are only split from a
+/// following word
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars
+                .get(i + 1)
+                .is_some_and(|next| next.is_ascii_lowercase());
+
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+
+        snake.push(c.to_ascii_lowercase());
+    }
+
+    snake
+}
+
+/// Entries in the tables above that don't match anything in `schema`
+pub fn stale_entries(schema: &Schema) -> Vec<String> {
+    let mut stale = Vec::new();
+
+    for &(complex_type, element, _) in FIELDS {
+        let exists = schema
+            .complex_type(complex_type)
+            .is_some_and(|complex| complex.elements.iter().any(|e| e.name == element));
+        if !exists {
+            stale.push(format!("field for {}/{}", complex_type, element));
+        }
+    }
+
+    for &(simple_type, value, _) in VARIANTS {
+        let exists = schema
+            .simple_type(simple_type)
+            .is_some_and(|simple| simple.enumerations.iter().any(|e| e == value));
+        if !exists {
+            stale.push(format!("variant for {} {:?}", simple_type, value));
+        }
+    }
+
+    let names = VARIANT_PREFIXES
+        .iter()
+        .map(|&(name, _)| name)
+        .chain(VOCABULARIES.iter().map(|&(name, _)| name))
+        .chain(TYPE_DOCS.iter().map(|&(name, _)| name))
+        .chain(HAND_WRITTEN_TYPES.iter().cloned());
+    for name in names {
+        if schema.simple_type(name).is_none() {
+            stale.push(format!("entry for {}", name));
+        }
+    }
+
+    for &name in HAND_WRITTEN_WRITERS {
+        if schema.complex_type(name).is_none() {
+            stale.push(format!("hand written writer for {}", name));
+        }
+    }
+
+    stale
+}
@@ -0,0 +1,34 @@
+//! `AQISchema.xsd`, read with the same parser as the crate's validator
+//!
+//! Only global elements, named complex types made of a single `xs:sequence`
+//! and named simple types restricting a builtin are supported. Anything else
+//! is an error rather than being skipped.
+
+// Length and range facets are only checked by the validator
+#[allow(dead_code)]
+#[path = "../../src/schema/xsd/parse.rs"]
+mod parse;
+
+pub use self::parse::*;
+
+impl Schema {
+    pub fn complex_type(&self, name: &str) -> Option<&ComplexType> {
+        self.complex_types
+            .iter()
+            .find(|complex| complex.name == name)
+    }
+
+    pub fn simple_type(&self, name: &str) -> Option<&SimpleType> {
+        self.simple_types.iter().find(|simple| simple.name == name)
+    }
+}
+
+impl Element {
+    pub fn is_repeated(&self) -> bool {
+        self.max.map_or(true, |max| max > 1)
+    }
+
+    pub fn is_optional(&self) -> bool {
+        self.min == 0
+    }
+}
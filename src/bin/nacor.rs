@@ -0,0 +1,1008 @@
+//! # nacor
+//! Validates, converts, summarizes, splits, merges, and de-identifies NACOR
+//! submissions
+//!
+//! Build with `cargo build --features cli`, then run `nacor help` for the
+//! commands and `nacor <command> --help` for their options. Input is read
+//! from a file, or from stdin if it's omitted or `-`, and output is written
+//! to stdout unless `-o` says otherwise.
+//!
+//! The exit status is 0 if everything was read and is valid, 1 if any
+//! records were invalid or couldn't be used, and 2 for usage and I/O errors,
+//! in which case nothing useful was written.
+
+use aqi_nacor_schema::schema::deidentify::{self, Action, DateShiftOptions, RecordReport};
+use aqi_nacor_schema::schema::export;
+use aqi_nacor_schema::schema::import::{self, Mapping};
+use aqi_nacor_schema::schema::reader::RecordReader;
+use aqi_nacor_schema::schema::temporal::{self, TemporalOptions};
+use aqi_nacor_schema::schema::validate::Validate;
+use aqi_nacor_schema::schema::writer::SubmissionWriter;
+use aqi_nacor_schema::schema::*;
+use aqi_nacor_schema::AQIError;
+
+use chrono::Duration;
+use getopts::{Matches, Options};
+use xml::writer::EmitterConfig;
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::process;
+
+const USAGE: &str = "Usage: nacor <command> [options] [FILE]
+
+Commands:
+    validate     Check records against the schema and the order of their times
+    convert      Convert between XML, JSON, and CSV
+    stats        Count cases and how they're distributed
+    split        Split a submission into files of at most N records
+    merge        Combine submissions with the same PracticeID and AQIXMLVersion
+    deidentify   Remove the Safe Harbor identifiers, or write a limited data
+                 set with shifted dates and pseudonymized IDs
+
+Run `nacor <command> --help` for a command's options.
+
+Reads FILE, or stdin if it's omitted or -, and writes stdout unless -o is
+given. Exits with 0 if everything was valid, 1 if any records were invalid or
+couldn't be used, and 2 for usage and I/O errors.";
+
+/// What a command found, for the exit status
+enum Outcome {
+    Clean,
+    Problems,
+}
+
+enum Failure {
+    Usage(String),
+    Error(AQIError),
+}
+
+type Command = fn(&Matches) -> Result<Outcome, Failure>;
+
+impl From<AQIError> for Failure {
+    fn from(err: AQIError) -> Failure {
+        Failure::Error(err)
+    }
+}
+
+impl From<io::Error> for Failure {
+    fn from(err: io::Error) -> Failure {
+        Failure::Error(AQIError::from(err))
+    }
+}
+
+impl From<getopts::Fail> for Failure {
+    fn from(err: getopts::Fail) -> Failure {
+        Failure::Usage(err.to_string())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let status = match run(&args) {
+        Ok(Outcome::Clean) => 0,
+        Ok(Outcome::Problems) => 1,
+        Err(Failure::Usage(message)) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            2
+        }
+        Err(Failure::Error(err)) => {
+            eprintln!("error: {}", err);
+            2
+        }
+    };
+
+    process::exit(status);
+}
+
+fn run(args: &[String]) -> Result<Outcome, Failure> {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => return Err(Failure::Usage("Missing command".to_string())),
+    };
+
+    let (brief, mut options, command): (_, _, Command) = match command {
+        "validate" => ("validate [options] [FILE]", validate_options(), validate),
+        "convert" => ("convert [options] [FILE]", convert_options(), convert),
+        "stats" => ("stats [options] [FILE]", stats_options(), stats),
+        "split" => ("split -n N [options] [FILE]", split_options(), split),
+        "merge" => ("merge [options] FILE...", merge_options(), merge),
+        "deidentify" => (
+            "deidentify [options] [FILE]",
+            deidentify_options(),
+            deidentify,
+        ),
+        "help" | "-h" | "--help" => {
+            println!("{}", USAGE);
+            return Ok(Outcome::Clean);
+        }
+        "version" | "-V" | "--version" => {
+            println!("nacor {}", env!("CARGO_PKG_VERSION"));
+            return Ok(Outcome::Clean);
+        }
+        _ => return Err(Failure::Usage(format!("Unknown command {}", command))),
+    };
+
+    options.optflag("h", "help", "print this help");
+    let matches = options.parse(args)?;
+    if matches.opt_present("help") {
+        print!("{}", options.usage(&format!("Usage: nacor {}", brief)));
+        return Ok(Outcome::Clean);
+    }
+
+    command(&matches)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Xml,
+    Json,
+    Csv,
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl Format {
+    fn parse(name: &str) -> Result<Format, Failure> {
+        match name.to_lowercase().as_str() {
+            "xml" => Ok(Format::Xml),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            #[cfg(feature = "parquet")]
+            "parquet" => Ok(Format::Parquet),
+            _ => Err(Failure::Usage(format!("Unknown format {}", name))),
+        }
+    }
+
+    fn from_extension(path: &str) -> Option<Format> {
+        Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| Format::parse(extension).ok())
+    }
+}
+
+fn input_options(options: &mut Options) {
+    options.optopt(
+        "",
+        "from",
+        "input format, by default from FILE's extension or its contents",
+        "xml|json|csv",
+    );
+    options.optopt(
+        "",
+        "mapping",
+        "TOML or YAML mapping of CSV columns to elements, for CSV input",
+        "FILE",
+    );
+    options.optopt(
+        "",
+        "header",
+        "NACOR XML file whose RecordHeader to use for CSV input",
+        "FILE",
+    );
+}
+
+fn output_options(options: &mut Options) {
+    options.optopt("o", "output", "write to FILE instead of stdout", "FILE");
+    options.optflag("", "pretty", "indent XML output");
+}
+
+/// The only FILE argument, if there is one
+fn single_input(matches: &Matches) -> Result<Option<&str>, Failure> {
+    match matches.free.as_slice() {
+        [] => Ok(None),
+        [path] => Ok(Some(path.as_str())),
+        _ => Err(Failure::Usage("Expected at most one FILE".to_string())),
+    }
+}
+
+fn open_input(path: Option<&str>) -> Result<BufReader<Box<dyn Read>>, Failure> {
+    let source: Box<dyn Read> = match path {
+        None | Some("-") => Box::new(io::stdin()),
+        Some(path) => {
+            Box::new(File::open(path).map_err(|err| AQIError::from(err).in_element(path))?)
+        }
+    };
+
+    Ok(BufReader::new(source))
+}
+
+fn open_output(path: Option<&str>) -> Result<Box<dyn Write>, Failure> {
+    Ok(match path {
+        None | Some("-") => Box::new(BufWriter::new(io::stdout())),
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).map_err(|err| AQIError::from(err).in_element(path))?,
+        )),
+    })
+}
+
+/// Guesses XML or JSON from the first character of the input
+fn sniff(reader: &mut BufReader<Box<dyn Read>>) -> Result<Option<Format>, Failure> {
+    let start = reader.fill_buf()?;
+    let start = start.strip_prefix(b"\xef\xbb\xbf").unwrap_or(start);
+
+    Ok(
+        match start.iter().find(|byte| !byte.is_ascii_whitespace()) {
+            Some(b'<') => Some(Format::Xml),
+            Some(b'{') => Some(Format::Json),
+            _ => None,
+        },
+    )
+}
+
+/// A submission read from any input format, with whatever couldn't be read
+struct Source {
+    records: AnesthesiaRecordsType,
+    problems: Vec<String>,
+}
+
+fn read_source(matches: &Matches) -> Result<Source, Failure> {
+    let path = single_input(matches)?;
+    let mut reader = open_input(path)?;
+
+    let format = match matches.opt_str("from") {
+        Some(format) => Format::parse(&format)?,
+        None => match path.and_then(Format::from_extension) {
+            Some(format) => format,
+            None => sniff(&mut reader)?.ok_or_else(|| {
+                Failure::Usage("Couldn't tell the input format, pass --from".to_string())
+            })?,
+        },
+    };
+
+    match format {
+        Format::Xml => {
+            let mut records = RecordReader::new(reader)?;
+            let mut anesthesia_records = Vec::new();
+            let mut problems = Vec::new();
+
+            for record in records.by_ref() {
+                match record {
+                    Ok(record) => anesthesia_records.push(record),
+                    Err(err) => problems.push(err.to_string()),
+                }
+            }
+
+            Ok(Source {
+                records: AnesthesiaRecordsType {
+                    record_header: records.record_header().clone(),
+                    anesthesia_records,
+                },
+                problems,
+            })
+        }
+        Format::Json => Ok(Source {
+            records: serde_json::from_reader(reader)
+                .map_err(|err| AQIError::parse(format!("Invalid JSON: {}", err)))?,
+            problems: Vec::new(),
+        }),
+        Format::Csv => {
+            let mapping = matches
+                .opt_str("mapping")
+                .ok_or_else(|| Failure::Usage("CSV input needs --mapping".to_string()))?;
+            let header = matches
+                .opt_str("header")
+                .ok_or_else(|| Failure::Usage("CSV input needs --header".to_string()))?;
+
+            let mapping = Mapping::load(&mapping).map_err(|err| err.in_element(&mapping))?;
+            let record_header = RecordReader::new(open_input(Some(&header))?)
+                .map_err(|err| err.in_element(&header))?
+                .record_header()
+                .clone();
+            let imported = import::import(reader, &mapping)?;
+
+            Ok(Source {
+                records: AnesthesiaRecordsType {
+                    record_header,
+                    anesthesia_records: imported.records,
+                },
+                problems: imported
+                    .errors
+                    .iter()
+                    .map(|err| match err.column {
+                        Some(ref column) => {
+                            format!("line {}, column {}: {}", err.row, column, err.error)
+                        }
+                        None => format!("line {}: {}", err.row, err.error),
+                    })
+                    .collect(),
+            })
+        }
+        #[cfg(feature = "parquet")]
+        Format::Parquet => Err(Failure::Usage(
+            "Parquet is only supported as an output format".to_string(),
+        )),
+    }
+}
+
+/// Prints every problem to stderr, returning the outcome they add up to
+fn report(problems: &[String]) -> Outcome {
+    for problem in problems {
+        eprintln!("{}", problem);
+    }
+
+    if problems.is_empty() {
+        Outcome::Clean
+    } else {
+        Outcome::Problems
+    }
+}
+
+fn write_xml<W: Write>(
+    records: &AnesthesiaRecordsType,
+    mut sink: W,
+    pretty: bool,
+) -> Result<(), Failure> {
+    records.write_document(&mut sink, pretty)?;
+    writeln!(sink)?;
+    sink.flush()?;
+
+    Ok(())
+}
+
+fn validate_options() -> Options {
+    let mut options = Options::new();
+    input_options(&mut options);
+    options.optopt(
+        "",
+        "tolerance",
+        "minutes times may be out of order or outside the case window",
+        "MINUTES",
+    );
+    options.optflag("q", "quiet", "only set the exit status");
+
+    options
+}
+
+fn validate(matches: &Matches) -> Result<Outcome, Failure> {
+    let tolerance = match matches.opt_str("tolerance") {
+        Some(minutes) => minutes
+            .parse()
+            .ok()
+            .and_then(Duration::try_minutes)
+            .ok_or_else(|| {
+                Failure::Usage(format!(
+                    "--tolerance must be a number of minutes, not {}",
+                    minutes
+                ))
+            })?,
+        None => Duration::zero(),
+    };
+    let temporal_options = TemporalOptions {
+        order_tolerance: tolerance,
+        window_tolerance: tolerance,
+    };
+
+    let source = read_source(matches)?;
+    let records = &source.records;
+
+    let mut problems = source.problems.clone();
+    problems.extend(records.violations().iter().map(ToString::to_string));
+    problems.extend(
+        temporal::validate_records(records, &temporal_options)
+            .iter()
+            .map(ToString::to_string),
+    );
+
+    if !matches.opt_present("quiet") {
+        let mut stdout = io::stdout().lock();
+        for problem in &problems {
+            writeln!(stdout, "{}", problem)?;
+        }
+        eprintln!(
+            "{} records, {} problems",
+            records.anesthesia_records.len(),
+            problems.len()
+        );
+    }
+
+    Ok(if problems.is_empty() {
+        Outcome::Clean
+    } else {
+        Outcome::Problems
+    })
+}
+
+fn convert_options() -> Options {
+    let mut options = Options::new();
+    input_options(&mut options);
+    output_options(&mut options);
+    #[cfg(not(feature = "parquet"))]
+    let formats = "xml|json|csv";
+    #[cfg(feature = "parquet")]
+    let formats = "xml|json|csv|parquet";
+    options.optopt(
+        "",
+        "to",
+        "output format, by default from the output's extension; csv and parquet \
+         write a directory of tables",
+        formats,
+    );
+
+    options
+}
+
+fn convert(matches: &Matches) -> Result<Outcome, Failure> {
+    let output = matches.opt_str("output");
+    let format = match matches.opt_str("to") {
+        Some(format) => Format::parse(&format)?,
+        None => output
+            .as_deref()
+            .and_then(Format::from_extension)
+            .ok_or_else(|| {
+                Failure::Usage("Couldn't tell the output format, pass --to".to_string())
+            })?,
+    };
+
+    let source = read_source(matches)?;
+    let records = &source.records;
+
+    match format {
+        Format::Xml => write_xml(
+            records,
+            open_output(output.as_deref())?,
+            matches.opt_present("pretty"),
+        )?,
+        Format::Json => {
+            let mut sink = open_output(output.as_deref())?;
+            serde_json::to_writer_pretty(&mut sink, records)
+                .map_err(|err| AQIError::parse(format!("Couldn't write JSON: {}", err)))?;
+            writeln!(sink)?;
+            sink.flush()?;
+        }
+        Format::Csv => {
+            let directory = output
+                .ok_or_else(|| Failure::Usage("CSV output needs a directory in -o".to_string()))?;
+            export::write_csv(&export::tables(records), &directory)?;
+        }
+        #[cfg(feature = "parquet")]
+        Format::Parquet => {
+            let directory = output.ok_or_else(|| {
+                Failure::Usage("Parquet output needs a directory in -o".to_string())
+            })?;
+            export::write_parquet(&export::tables(records), &directory)?;
+        }
+    }
+
+    Ok(report(&source.problems))
+}
+
+fn stats_options() -> Options {
+    let mut options = Options::new();
+    input_options(&mut options);
+    options.optflag("", "json", "print the counts as JSON");
+
+    options
+}
+
+/// How many cases have each value, for one kind of value
+struct Distribution {
+    name: &'static str,
+    counts: BTreeMap<String, usize>,
+}
+
+impl Distribution {
+    fn new(name: &'static str) -> Distribution {
+        Distribution {
+            name,
+            counts: BTreeMap::new(),
+        }
+    }
+
+    fn add<S: Into<String>>(&mut self, value: S) {
+        *self.counts.entry(value.into()).or_insert(0) += 1;
+    }
+}
+
+const NONE: &str = "(none)";
+
+fn age_group(age: u64) -> &'static str {
+    match age {
+        0..=17 => "0-17",
+        18..=39 => "18-39",
+        40..=64 => "40-64",
+        65..=89 => "65-89",
+        _ => "90+",
+    }
+}
+
+fn stats(matches: &Matches) -> Result<Outcome, Failure> {
+    let source = read_source(matches)?;
+    let records = &source.records.anesthesia_records;
+
+    let mut facilities = Distribution::new("Facility");
+    let mut months = Distribution::new("Procedure month");
+    let mut sexes = Distribution::new("Patient sex");
+    let mut ages = Distribution::new("Age");
+    let mut asa_classes = Distribution::new("ASA class");
+    let mut admission_statuses = Distribution::new("Admission status");
+    let mut proc_statuses = Distribution::new("Procedure status");
+    let mut categories = Distribution::new("Anesthesia category");
+    let mut credentials = Distribution::new("Provider credentials");
+    let mut payment_methods = Distribution::new("Payment method");
+
+    let mut patients = HashSet::new();
+    let mut staff = HashSet::new();
+
+    for record in records {
+        let procedure = &record.procedure;
+        let case = &record.anesthesia_case;
+
+        if let Some(ref patient_id) = record.demographic.patient_id {
+            patients.insert(patient_id.as_str());
+        }
+        facilities.add(procedure.facility_id.as_str());
+        months.add(match procedure.proc_start_time {
+            Some(time) => time.format("%Y-%m").to_string(),
+            None => NONE.to_string(),
+        });
+        sexes.add(record.demographic.patient_sex.value());
+        ages.add(age_group(record.pre_op.age));
+        asa_classes.add(record.pre_op.asa_class.value());
+        admission_statuses.add(
+            procedure
+                .admission_status
+                .as_ref()
+                .map_or(NONE, |status| status.value()),
+        );
+        proc_statuses.add(procedure.proc_status.value());
+
+        // Cases count once per value, however many methods, staff or payers have it
+        let case_categories: BTreeSet<_> = case
+            .anesthesia_method_set
+            .anesthesia_method
+            .iter()
+            .map(|method| method.anesthesia_category.value())
+            .collect();
+        for category in case_categories {
+            categories.add(category);
+        }
+        let case_credentials: BTreeSet<_> = case
+            .anesthesia_staff_set
+            .anesthesia_staff
+            .iter()
+            .map(|staff| staff.provider_credentials.value())
+            .collect();
+        for credential in case_credentials {
+            credentials.add(credential);
+        }
+        for member in &case.anesthesia_staff_set.anesthesia_staff {
+            staff.insert(member.npi.value());
+        }
+
+        let case_payment_codes: BTreeSet<_> = record
+            .post_op
+            .payment_method
+            .iter()
+            .map(|payment_method| payment_method.payment_code.value())
+            .collect();
+        if case_payment_codes.is_empty() {
+            payment_methods.add(NONE);
+        }
+        for payment_code in case_payment_codes {
+            payment_methods.add(payment_code);
+        }
+    }
+
+    let distributions = [
+        facilities,
+        months,
+        sexes,
+        ages,
+        asa_classes,
+        admission_statuses,
+        proc_statuses,
+        categories,
+        credentials,
+        payment_methods,
+    ];
+
+    let mut stdout = io::stdout().lock();
+    if matches.opt_present("json") {
+        let mut json = serde_json::Map::new();
+        json.insert("Records".to_string(), records.len().into());
+        json.insert("Patients".to_string(), patients.len().into());
+        json.insert("Staff".to_string(), staff.len().into());
+        for distribution in &distributions {
+            json.insert(
+                distribution.name.to_string(),
+                serde_json::to_value(&distribution.counts).expect("counts are always valid JSON"),
+            );
+        }
+
+        serde_json::to_writer_pretty(&mut stdout, &json)
+            .map_err(|err| AQIError::parse(format!("Couldn't write JSON: {}", err)))?;
+        writeln!(stdout)?;
+    } else {
+        // Wide enough for the longest value, payment codes especially
+        let width = distributions
+            .iter()
+            .flat_map(|distribution| distribution.counts.keys())
+            .map(|value| value.chars().count() + 1)
+            .fold(22, usize::max);
+
+        writeln!(stdout, "{:<2$}{:>8}", "Records", records.len(), width + 2)?;
+        writeln!(stdout, "{:<2$}{:>8}", "Patients", patients.len(), width + 2)?;
+        writeln!(stdout, "{:<2$}{:>8}", "Staff", staff.len(), width + 2)?;
+        for distribution in &distributions {
+            writeln!(stdout, "\n{}", distribution.name)?;
+            for (value, count) in &distribution.counts {
+                writeln!(stdout, "  {:<2$}{:>8}", value, count, width)?;
+            }
+        }
+    }
+
+    Ok(report(&source.problems))
+}
+
+fn split_options() -> Options {
+    let mut options = Options::new();
+    options.reqopt("n", "records", "records per file", "N");
+    options.optopt(
+        "o",
+        "output",
+        "write PREFIX-001.xml and so on, by default named after FILE",
+        "PREFIX",
+    );
+    options.optflag("", "pretty", "indent XML output");
+
+    options
+}
+
+/// Writes each file's records as they're read, listing the files on stdout
+///
+/// Files are written under temporary names and only renamed into place once
+/// every one of them is complete, so a failed split leaves nothing behind.
+fn split(matches: &Matches) -> Result<Outcome, Failure> {
+    let per_file: usize = matches
+        .opt_str("records")
+        .and_then(|n| n.parse().ok())
+        .filter(|&n| n > 0)
+        .ok_or_else(|| Failure::Usage("-n must be a positive number of records".to_string()))?;
+    let path = single_input(matches)?;
+    let prefix = match matches.opt_str("output") {
+        Some(prefix) => prefix,
+        None => path
+            .filter(|&path| path != "-")
+            .and_then(|path| Path::new(path).file_stem())
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("split")
+            .to_string(),
+    };
+
+    let mut files = Vec::new();
+    let mut renamed = 0;
+    let result = write_split(
+        path,
+        &prefix,
+        per_file,
+        matches.opt_present("pretty"),
+        &mut files,
+    )
+    .and_then(|problems| {
+        if files.is_empty() {
+            report(&problems);
+            return Err(Failure::Error(AQIError::parse(
+                "No records to split".to_string(),
+            )));
+        }
+        for (temporary, name) in &files {
+            fs::rename(temporary, name).map_err(|err| AQIError::from(err).in_element(name))?;
+            renamed += 1;
+        }
+
+        Ok(problems)
+    });
+
+    match result {
+        Ok(problems) => {
+            for (_, name) in &files {
+                println!("{}", name);
+            }
+
+            Ok(report(&problems))
+        }
+        Err(err) => {
+            for (i, (temporary, name)) in files.iter().enumerate() {
+                let _ = fs::remove_file(if i < renamed { name } else { temporary });
+            }
+
+            Err(err)
+        }
+    }
+}
+
+/// Writes the split files under temporary names, adding each to `files` with
+/// the name it's renamed to as it's opened, and returns the records that
+/// couldn't be read
+fn write_split(
+    path: Option<&str>,
+    prefix: &str,
+    per_file: usize,
+    pretty: bool,
+    files: &mut Vec<(String, String)>,
+) -> Result<Vec<String>, Failure> {
+    let mut records = RecordReader::new(open_input(path)?)?;
+    let header = records.record_header().clone();
+    let mut problems = Vec::new();
+    let mut current: Option<(SubmissionWriter<Box<dyn Write>>, usize)> = None;
+
+    for record in records.by_ref() {
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                problems.push(err.to_string());
+                continue;
+            }
+        };
+
+        if current
+            .as_ref()
+            .map_or(true, |(_, count)| *count >= per_file)
+        {
+            if let Some((writer, _)) = current.take() {
+                writer.finish()?.flush()?;
+            }
+
+            let name = format!("{}-{:03}.xml", prefix, files.len() + 1);
+            let temporary = format!("{}.{}.tmp", name, process::id());
+            let file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&temporary)
+                .map_err(|err| AQIError::from(err).in_element(&temporary))?;
+            files.push((temporary, name));
+
+            let writer = EmitterConfig::new()
+                .write_document_declaration(true)
+                .perform_indent(pretty)
+                .create_writer(Box::new(BufWriter::new(file)) as Box<dyn Write>);
+            current = Some((SubmissionWriter::new(writer, &header)?, 0));
+        }
+
+        let (writer, count) = current.as_mut().expect("opened above");
+        writer.push(&record)?;
+        *count += 1;
+    }
+
+    if let Some((writer, _)) = current {
+        writer.finish()?.flush()?;
+    }
+
+    Ok(problems)
+}
+
+fn merge_options() -> Options {
+    let mut options = Options::new();
+    output_options(&mut options);
+
+    options
+}
+
+/// Streams every record into one submission under the first file's header
+///
+/// Files with a different PracticeID or AQIXMLVersion, and records whose
+/// AnesthesiaRecordID was already merged, are left out.
+fn merge(matches: &Matches) -> Result<Outcome, Failure> {
+    if matches.free.is_empty() {
+        return Err(Failure::Usage("merge needs at least one FILE".to_string()));
+    }
+
+    let mut problems = Vec::new();
+    let mut writer: Option<SubmissionWriter<Box<dyn Write>>> = None;
+    let mut header: Option<RecordHeaderType> = None;
+    let mut ids = HashSet::new();
+
+    for path in &matches.free {
+        let mut records =
+            RecordReader::new(open_input(Some(path))?).map_err(|err| err.in_element(path))?;
+
+        match header {
+            None => {
+                let config = EmitterConfig::new()
+                    .write_document_declaration(true)
+                    .perform_indent(matches.opt_present("pretty"));
+                writer = Some(SubmissionWriter::new(
+                    config.create_writer(open_output(matches.opt_str("output").as_deref())?),
+                    records.record_header(),
+                )?);
+                header = Some(records.record_header().clone());
+            }
+            Some(ref header) => {
+                let other = records.record_header();
+                if other.practice_id.value() != header.practice_id.value()
+                    || other.aqi_xml_version != header.aqi_xml_version
+                {
+                    problems.push(format!(
+                        "{}: PracticeID {} and AQIXMLVersion {} don't match {} and {}, skipped",
+                        path,
+                        other.practice_id.value(),
+                        other.aqi_xml_version.value(),
+                        header.practice_id.value(),
+                        header.aqi_xml_version.value()
+                    ));
+                    continue;
+                }
+            }
+        }
+
+        let writer = writer.as_mut().expect("opened with the first file");
+        for (i, record) in records.by_ref().enumerate() {
+            match record {
+                Ok(record) => {
+                    let id = record.anesthesia_case.anesthesia_record_id.clone();
+                    if ids.insert(id.clone()) {
+                        writer.push(&record)?;
+                    } else {
+                        problems.push(format!(
+                            "{}: AnesthesiaRecord[{}]: AnesthesiaRecordID {} already merged, skipped",
+                            path, i, id
+                        ));
+                    }
+                }
+                Err(err) => problems.push(format!("{}: {}", path, err)),
+            }
+        }
+    }
+
+    if let Some(writer) = writer {
+        let mut sink = writer.finish()?;
+        writeln!(sink)?;
+        sink.flush()?;
+    }
+
+    Ok(report(&problems))
+}
+
+fn deidentify_options() -> Options {
+    let mut options = Options::new();
+    input_options(&mut options);
+    output_options(&mut options);
+    options.optflag(
+        "",
+        "limited",
+        "write a limited data set, shifting dates and pseudonymizing IDs instead of \
+         reducing dates to the year and removing IDs",
+    );
+    options.optopt("", "key-file", "secret key for --limited", "FILE");
+    options.optopt(
+        "",
+        "max-days",
+        "largest date offset for --limited, 365 by default",
+        "DAYS",
+    );
+    options.optopt(
+        "",
+        "crosswalk",
+        "save the pseudonyms from --limited and the IDs they replaced as CSV, \
+         readable only by you",
+        "FILE",
+    );
+    options.optflag("v", "verbose", "list every change on stderr");
+
+    options
+}
+
+/// Applies Safe Harbor, or for `--limited` shifts dates, removes notes and
+/// pseudonymizes the IDs, which keeps intervals and links between cases
+fn deidentify(matches: &Matches) -> Result<Outcome, Failure> {
+    let limited = matches.opt_present("limited");
+    for option in ["key-file", "max-days", "crosswalk"].iter() {
+        if matches.opt_present(option) && !limited {
+            return Err(Failure::Usage(format!("--{} needs --limited", option)));
+        }
+    }
+
+    let key = match matches.opt_str("key-file") {
+        Some(path) => {
+            let key = fs::read(&path).map_err(|err| AQIError::from(err).in_element(&path))?;
+            let start = key
+                .iter()
+                .position(|byte| !byte.is_ascii_whitespace())
+                .unwrap_or(key.len());
+            let end = key
+                .iter()
+                .rposition(|byte| !byte.is_ascii_whitespace())
+                .map_or(start, |i| i + 1);
+            let key = key[start..end].to_vec();
+            if key.is_empty() {
+                return Err(Failure::Usage(format!("Key file {} is empty", path)));
+            }
+            Some(key)
+        }
+        None if limited => {
+            return Err(Failure::Usage("--limited needs --key-file".to_string()));
+        }
+        None => None,
+    };
+    let mut shift_options = DateShiftOptions::default();
+    if let Some(days) = matches.opt_str("max-days") {
+        shift_options.max_days =
+            days.parse().ok().filter(|&days| days > 0).ok_or_else(|| {
+                Failure::Usage("--max-days must be a positive number".to_string())
+            })?;
+    }
+
+    let source = read_source(matches)?;
+    let mut records = source.records;
+
+    let (reports, crosswalk) = match key {
+        Some(ref key) => {
+            // Shifted first, so offsets are keyed by the original PatientIDs
+            deidentify::shift_dates(&mut records, key, &shift_options);
+            let reports = records
+                .anesthesia_records
+                .iter_mut()
+                .enumerate()
+                .map(|(index, record)| RecordReport {
+                    index,
+                    changes: deidentify::remove_free_text(record),
+                })
+                .collect();
+
+            (reports, Some(deidentify::pseudonymize(&mut records, key)))
+        }
+        None => {
+            let deidentified = deidentify::safe_harbor(records);
+            records = deidentified.records;
+
+            (deidentified.reports, None)
+        }
+    };
+
+    let changes: usize = reports.iter().map(|report| report.changes.len()).sum();
+    if matches.opt_present("verbose") {
+        for report in &reports {
+            for change in &report.changes {
+                match change.action {
+                    Action::Removed => {
+                        eprintln!(
+                            "AnesthesiaRecord[{}]/{}: removed",
+                            report.index, change.path
+                        )
+                    }
+                    Action::Generalized(ref value) => eprintln!(
+                        "AnesthesiaRecord[{}]/{}: generalized to {}",
+                        report.index, change.path, value
+                    ),
+                    Action::Replaced(ref value) => eprintln!(
+                        "AnesthesiaRecord[{}]/{}: replaced with {}",
+                        report.index, change.path, value
+                    ),
+                }
+            }
+        }
+    }
+
+    match crosswalk {
+        Some(crosswalk) => {
+            eprintln!(
+                "{} records, dates shifted, {} notes removed, {} IDs pseudonymized",
+                records.anesthesia_records.len(),
+                changes,
+                crosswalk.len()
+            );
+            if let Some(path) = matches.opt_str("crosswalk") {
+                crosswalk.save(&path).map_err(|err| err.in_element(&path))?;
+            }
+        }
+        None => eprintln!(
+            "{} records, {} values removed, generalized or replaced",
+            records.anesthesia_records.len(),
+            changes
+        ),
+    }
+
+    write_xml(
+        &records,
+        open_output(matches.opt_str("output").as_deref())?,
+        matches.opt_present("pretty"),
+    )?;
+
+    Ok(report(&source.problems))
+}
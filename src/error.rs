@@ -0,0 +1,226 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// An error along with the element path where it occurred
+///
+/// `path` is slash-separated and relative to the value being read or
+/// written, with zero-based indices for repeated elements, e.g.
+/// `AnesthesiaRecord[3]/AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff[0]/NPI`.
+/// It's empty for errors that aren't tied to an element.
+#[derive(Debug)]
+pub struct AQIError {
+    pub path: String,
+    pub kind: ErrorKind,
+}
+
+/// What went wrong
+///
+/// New kinds may be added, including by optional features such as `sqlite`,
+/// so matches need a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A value that doesn't match the pattern of its type
+    PatternMismatch {
+        type_name: &'static str,
+        value: String,
+    },
+    /// A value that isn't one of the values of an enumerated type
+    UnknownEnumValue {
+        type_name: &'static str,
+        value: String,
+    },
+    /// An element occurring fewer or more times than the schema allows
+    Cardinality {
+        element: String,
+        min: usize,
+        max: Option<usize>,
+        found: usize,
+    },
+    /// A value or document that couldn't be parsed for some other reason
+    Parse(String),
+    XmlParse(xml::reader::Error),
+    XmlEmit(xml::writer::Error),
+    Io(io::Error),
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
+}
+
+impl AQIError {
+    pub fn new(kind: ErrorKind) -> AQIError {
+        AQIError {
+            path: String::new(),
+            kind,
+        }
+    }
+
+    pub fn parse<S: Into<String>>(message: S) -> AQIError {
+        AQIError::new(ErrorKind::Parse(message.into()))
+    }
+
+    /// Prefixes the path with the element the error occurred in
+    pub fn in_element(mut self, name: &str) -> AQIError {
+        self.path = if self.path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", name, self.path)
+        };
+
+        self
+    }
+}
+
+impl fmt::Display for AQIError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+
+        match self.kind {
+            ErrorKind::PatternMismatch {
+                type_name,
+                ref value,
+            } => write!(f, "{:?} doesn't match the pattern of {}", value, type_name),
+            ErrorKind::UnknownEnumValue {
+                type_name,
+                ref value,
+            } => write!(f, "{:?} is not a known {} value", value, type_name),
+            ErrorKind::Cardinality {
+                ref element,
+                min,
+                max,
+                found,
+            } => match max {
+                Some(max) if found > max => {
+                    write!(f, "expected at most {} {}, found {}", max, element, found)
+                }
+                _ => write!(f, "expected at least {} {}, found {}", min, element, found),
+            },
+            ErrorKind::Parse(ref message) => write!(f, "{}", message),
+            ErrorKind::XmlParse(ref err) => write!(f, "XML parse error: {}", err),
+            ErrorKind::XmlEmit(ref err) => write!(f, "XML emit error: {}", err),
+            ErrorKind::Io(ref err) => write!(f, "I/O error: {}", err),
+            #[cfg(feature = "sqlite")]
+            ErrorKind::Sqlite(ref err) => write!(f, "SQLite error: {}", err),
+        }
+    }
+}
+
+impl Error for AQIError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self.kind {
+            ErrorKind::XmlParse(ref err) => Some(err),
+            ErrorKind::XmlEmit(ref err) => Some(err),
+            ErrorKind::Io(ref err) => Some(err),
+            #[cfg(feature = "sqlite")]
+            ErrorKind::Sqlite(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<xml::reader::Error> for AQIError {
+    fn from(err: xml::reader::Error) -> AQIError {
+        AQIError::new(ErrorKind::XmlParse(err))
+    }
+}
+
+impl From<xml::writer::Error> for AQIError {
+    fn from(err: xml::writer::Error) -> AQIError {
+        match err {
+            xml::writer::Error::Io(err) => AQIError::new(ErrorKind::Io(err)),
+            err => AQIError::new(ErrorKind::XmlEmit(err)),
+        }
+    }
+}
+
+impl From<io::Error> for AQIError {
+    fn from(err: io::Error) -> AQIError {
+        AQIError::new(ErrorKind::Io(err))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for AQIError {
+    fn from(err: rusqlite::Error) -> AQIError {
+        AQIError::new(ErrorKind::Sqlite(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_element_prefixes_the_path() {
+        let err = AQIError::parse("bad")
+            .in_element("NPI")
+            .in_element("AnesthesiaStaff[0]")
+            .in_element("AnesthesiaRecord[2]");
+
+        assert_eq!(err.path, "AnesthesiaRecord[2]/AnesthesiaStaff[0]/NPI");
+        assert_eq!(
+            err.to_string(),
+            "AnesthesiaRecord[2]/AnesthesiaStaff[0]/NPI: bad"
+        );
+        assert_eq!(AQIError::parse("bad").to_string(), "bad");
+    }
+
+    #[test]
+    fn describes_each_kind() {
+        let cases = vec![
+            (
+                ErrorKind::PatternMismatch {
+                    type_name: "NPIType",
+                    value: "123".to_string(),
+                },
+                "\"123\" doesn't match the pattern of NPIType",
+            ),
+            (
+                ErrorKind::UnknownEnumValue {
+                    type_name: "USStateCodeType",
+                    value: "XX".to_string(),
+                },
+                "\"XX\" is not a known USStateCodeType value",
+            ),
+            (
+                ErrorKind::Cardinality {
+                    element: "CPT".to_string(),
+                    min: 1,
+                    max: None,
+                    found: 0,
+                },
+                "expected at least 1 CPT, found 0",
+            ),
+            (
+                ErrorKind::Cardinality {
+                    element: "Demographic".to_string(),
+                    min: 1,
+                    max: Some(1),
+                    found: 2,
+                },
+                "expected at most 1 Demographic, found 2",
+            ),
+        ];
+
+        for (kind, message) in cases {
+            assert_eq!(AQIError::new(kind).to_string(), message);
+        }
+    }
+
+    #[test]
+    fn wraps_io_errors() {
+        let err = AQIError::from(io::Error::new(io::ErrorKind::WriteZero, "disk full"));
+
+        assert!(matches!(err.kind, ErrorKind::Io(_)));
+        assert_eq!(err.to_string(), "I/O error: disk full");
+        assert!(err.source().is_some());
+
+        let err = AQIError::from(xml::writer::Error::Io(io::Error::new(
+            io::ErrorKind::WriteZero,
+            "disk full",
+        )));
+        assert!(matches!(err.kind, ErrorKind::Io(_)));
+    }
+}
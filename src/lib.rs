@@ -4,9 +4,7 @@ extern crate lazy_static;
 #[macro_use]
 mod macros;
 
+mod error;
 pub mod schema;
 
-#[derive(Debug)]
-pub enum AQIError {
-    RegexError(String),
-}
+pub use crate::error::{AQIError, ErrorKind};
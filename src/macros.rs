@@ -1,7 +1,7 @@
 #[macro_export]
 macro_rules! enum_map {
 	(
-        $NAME:ident: $TRAIT:ident; $FUNCTION:ident -> $TYPE:ty {
+        @enum $NAME:ident: $TRAIT:ident; $FUNCTION:ident -> $TYPE:ty {
             $(
                 $VARIANT:ident => $OUT:expr
             ),*
@@ -21,6 +21,89 @@ macro_rules! enum_map {
         		}
         	}
         }
+
+        impl $NAME {
+            /// Every variant, in schema order
+            pub const VARIANTS: &'static [$NAME] = &[$($NAME::$VARIANT),*];
+
+            pub fn iter() -> impl Iterator<Item = $NAME> {
+                $NAME::VARIANTS.iter().cloned()
+            }
+
+            /// Looks up the variant for a schema value
+            pub fn from_value(value: $TYPE) -> Option<$NAME> {
+                $(
+                    if value == $OUT {
+                        return Some($NAME::$VARIANT);
+                    }
+                )*
+
+                None
+            }
+        }
+    );
+
+	(
+        $NAME:ident: $TRAIT:ident; $FUNCTION:ident -> u64 {
+            $(
+                $VARIANT:ident => $OUT:expr
+            ),*
+        }
+    ) => (
+        enum_map! {
+            @enum $NAME: $TRAIT; $FUNCTION -> u64 {
+                $(
+                    $VARIANT => $OUT
+                ),*
+            }
+        }
+
+        impl ::std::convert::TryFrom<u64> for $NAME {
+            type Error = AQIError;
+
+            fn try_from(value: u64) -> Result<$NAME, AQIError> {
+                $NAME::from_value(value).ok_or_else(|| {
+                    AQIError::new($crate::ErrorKind::UnknownEnumValue {
+                        type_name: stringify!($NAME),
+                        value: value.to_string(),
+                    })
+                })
+            }
+        }
+
+        /// Parses a schema value, ignoring surrounding whitespace like XML
+        /// does for token values
+        impl ::std::str::FromStr for $NAME {
+            type Err = AQIError;
+
+            fn from_str(value: &str) -> Result<$NAME, AQIError> {
+                let number = value.trim().parse::<u64>().map_err(|_| {
+                    AQIError::new($crate::ErrorKind::UnknownEnumValue {
+                        type_name: stringify!($NAME),
+                        value: value.to_string(),
+                    })
+                })?;
+
+                <$NAME as ::std::convert::TryFrom<u64>>::try_from(number)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $NAME {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_u64(self.$FUNCTION())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $NAME {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<$NAME, D::Error> {
+                let value = <u64 as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                <$NAME as ::std::convert::TryFrom<u64>>::try_from(value)
+                    .map_err(::serde::de::Error::custom)
+            }
+        }
     );
 
 	(
@@ -31,12 +114,59 @@ macro_rules! enum_map {
         }
     ) => (
         enum_map! {
-			$NAME: $TRAIT; $FUNCTION -> &str {
+			@enum $NAME: $TRAIT; $FUNCTION -> &str {
 	            $(
 	                $VARIANT => $OUT
 	            ),*
 	        }
 		}
+
+        impl $NAME {
+            /// Looks up the variant for a schema value, ignoring ASCII case
+            pub fn from_value_ignore_case(value: &str) -> Option<$NAME> {
+                $NAME::iter().find(|variant| variant.$FUNCTION().eq_ignore_ascii_case(value))
+            }
+        }
+
+        impl<'a> ::std::convert::TryFrom<&'a str> for $NAME {
+            type Error = AQIError;
+
+            fn try_from(value: &str) -> Result<$NAME, AQIError> {
+                $NAME::from_value(value).ok_or_else(|| {
+                    AQIError::new($crate::ErrorKind::UnknownEnumValue {
+                        type_name: stringify!($NAME),
+                        value: value.to_string(),
+                    })
+                })
+            }
+        }
+
+        /// Parses a schema value, ignoring surrounding whitespace like XML
+        /// does for token values
+        impl ::std::str::FromStr for $NAME {
+            type Err = AQIError;
+
+            fn from_str(value: &str) -> Result<$NAME, AQIError> {
+                <$NAME as ::std::convert::TryFrom<&str>>::try_from(value.trim())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $NAME {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.$FUNCTION())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $NAME {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<$NAME, D::Error> {
+                let value = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                <$NAME as ::std::convert::TryFrom<&str>>::try_from(&value)
+                    .map_err(::serde::de::Error::custom)
+            }
+        }
     );
 }
 
@@ -45,7 +175,7 @@ macro_rules! schema_pattern_type {
     (
 		$NAME:ident, $REGEX:expr
 	) => {
-        #[derive(Debug)]
+        #[derive(Clone, Debug)]
         pub struct $NAME(String);
 
         impl SchemaStringType for $NAME {
@@ -62,21 +192,43 @@ macro_rules! schema_pattern_type {
                 }
                 match RE.is_match(val) {
                     true => Ok($NAME(val.to_string())),
-                    _ => Err(AQIError::RegexError(format!(
-                        "Failed matching {}: {}",
-                        $REGEX, val
-                    ))),
+                    _ => Err(AQIError::new($crate::ErrorKind::PatternMismatch {
+                        type_name: stringify!($NAME),
+                        value: val.to_string(),
+                    })),
                 }
             }
         }
+
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $NAME {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $NAME {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$NAME, D::Error> {
+                let value = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                <$NAME as SchemaRegexInput>::from_str(&value).map_err(::serde::de::Error::custom)
+            }
+        }
     };
 }
 
 #[macro_export]
 macro_rules! schema_string_tuple_struct {
     (
+		$(#[$META:meta])*
 		$NAME:ident
 	) => {
+        $(#[$META])*
+        #[derive(Clone, Debug)]
+        #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
         pub struct $NAME(pub String);
 
         impl<'a> SchemaStringType for $NAME {
@@ -86,3 +238,132 @@ macro_rules! schema_string_tuple_struct {
         }
     };
 }
+
+/// An `enum_map!` for open vocabularies, where the schema only gives
+/// examples and any other string is still valid
+///
+/// Values that aren't one of the listed variants, including the schema's own
+/// "Other", are kept as-is in `Other(String)`. Values compare and hash by
+/// their schema value, so an `Other` holding a listed value, which only
+/// comes from building it directly, equals the variant; `normalize` turns it
+/// into the variant for matching.
+#[macro_export]
+macro_rules! open_enum_map {
+    (
+        $(#[$META:meta])*
+        $NAME:ident {
+            $(
+                $VARIANT:ident => $OUT:expr
+            ),*
+        }
+    ) => {
+        $(#[$META])*
+        #[derive(Clone, Debug)]
+        pub enum $NAME {
+            $($VARIANT,)*
+            Other(String),
+        }
+
+        impl PartialEq for $NAME {
+            fn eq(&self, other: &$NAME) -> bool {
+                self.value() == other.value()
+            }
+        }
+
+        impl Eq for $NAME {}
+
+        impl ::std::hash::Hash for $NAME {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                self.value().hash(state);
+            }
+        }
+
+        impl SchemaStringType for $NAME {
+            fn value(&self) -> &str {
+                match *self {
+                    $(
+                        $NAME::$VARIANT => $OUT,
+                    )*
+                    $NAME::Other(ref value) => value,
+                }
+            }
+        }
+
+        impl $NAME {
+            /// Every known variant, in schema order
+            pub const VARIANTS: &'static [$NAME] = &[$($NAME::$VARIANT),*];
+
+            pub fn iter() -> impl Iterator<Item = $NAME> {
+                $NAME::VARIANTS.iter().cloned()
+            }
+
+            /// Looks up the variant for a schema value, falling back to `Other`
+            pub fn from_value(value: &str) -> $NAME {
+                $(
+                    if value == $OUT {
+                        return $NAME::$VARIANT;
+                    }
+                )*
+
+                $NAME::Other(value.to_string())
+            }
+
+            /// Looks up the variant for a schema value ignoring ASCII case,
+            /// falling back to `Other`
+            pub fn from_value_ignore_case(value: &str) -> $NAME {
+                $NAME::iter()
+                    .find(|variant| variant.value().eq_ignore_ascii_case(value))
+                    .unwrap_or_else(|| $NAME::Other(value.to_string()))
+            }
+
+            /// Whether this is one of the schema's example values
+            pub fn is_known(&self) -> bool {
+                $NAME::VARIANTS.iter().any(|variant| variant.value() == self.value())
+            }
+
+            /// Replaces an `Other` holding one of the listed values with its variant
+            pub fn normalize(self) -> $NAME {
+                match self {
+                    $NAME::Other(value) => $NAME::from_value(&value),
+                    known => known,
+                }
+            }
+        }
+
+        impl<'a> From<&'a str> for $NAME {
+            fn from(value: &str) -> $NAME {
+                $NAME::from_value(value)
+            }
+        }
+
+        impl From<String> for $NAME {
+            fn from(value: String) -> $NAME {
+                $NAME::from_value(&value)
+            }
+        }
+
+        impl ::std::str::FromStr for $NAME {
+            type Err = ::std::convert::Infallible;
+
+            fn from_str(value: &str) -> Result<$NAME, ::std::convert::Infallible> {
+                Ok($NAME::from_value(value))
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $NAME {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.value())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $NAME {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<$NAME, D::Error> {
+                let value = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                Ok($NAME::from_value(&value))
+            }
+        }
+    };
+}
@@ -0,0 +1,993 @@
+//! # Builders
+//! Assemble records section by section
+//!
+//! Each builder's `build` checks that every required element was set,
+//! returning all of the missing ones at once rather than stopping at the
+//! first. Repeated elements are collected one at a time through `add_*`
+//! methods, and optional sets are left out entirely when nothing was added.
+
+use super::*;
+
+use std::error::Error;
+use std::fmt;
+
+/// Required elements that weren't set when `build` was called
+///
+/// Paths are relative to the element being built, e.g.
+/// `Procedure/FacilityID` for an `AnesthesiaRecordBuilder`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MissingElements {
+    pub paths: Vec<String>,
+}
+
+impl fmt::Display for MissingElements {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "missing required elements: {}", self.paths.join(", "))
+    }
+}
+
+impl Error for MissingElements {}
+
+/// Generates a setter for each optional or not-yet-set field
+macro_rules! setters {
+    ($($FIELD:ident: $TYPE:ty),* $(,)*) => {
+        $(
+            pub fn $FIELD(mut self, value: $TYPE) -> Self {
+                self.$FIELD = Some(value);
+                self
+            }
+        )*
+    };
+}
+
+/// Like `setters!`, for `String` fields that accept anything `Into<String>`
+macro_rules! string_setters {
+    ($($FIELD:ident),* $(,)*) => {
+        $(
+            pub fn $FIELD<S: Into<String>>(mut self, value: S) -> Self {
+                self.$FIELD = Some(value.into());
+                self
+            }
+        )*
+    };
+}
+
+fn require<T>(missing: &mut Vec<String>, name: &str, value: Option<T>) -> Option<T> {
+    if value.is_none() {
+        missing.push(name.to_string());
+    }
+
+    value
+}
+
+fn require_some<T>(missing: &mut Vec<String>, name: &str, values: &[T]) {
+    if values.is_empty() {
+        missing.push(name.to_string());
+    }
+}
+
+fn non_empty<T>(values: Vec<T>) -> Option<Vec<T>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
+
+fn section<T>(
+    missing: &mut Vec<String>,
+    name: &str,
+    result: Result<T, MissingElements>,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(err) => {
+            missing.extend(
+                err.paths
+                    .into_iter()
+                    .map(|path| validate::child_path(name, &path)),
+            );
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AnesthesiaRecordBuilder {
+    demographic: DemographicBuilder,
+    procedure: ProcedureBuilder,
+    anesthesia_case: AnesthesiaCaseBuilder,
+    pre_op: PreOpBuilder,
+    intra_op: IntraOpBuilder,
+    post_op: PostOpBuilder,
+    timing_milestones: Vec<TimingMilestoneType>,
+    outcomes_events: Option<OutcomesEventsBuilder>,
+    anesthesia_details: Option<AnesthesiaDetailsBuilder>,
+}
+
+impl AnesthesiaRecordBuilder {
+    pub fn new() -> AnesthesiaRecordBuilder {
+        Default::default()
+    }
+
+    pub fn demographic(mut self, demographic: DemographicBuilder) -> Self {
+        self.demographic = demographic;
+        self
+    }
+
+    pub fn procedure(mut self, procedure: ProcedureBuilder) -> Self {
+        self.procedure = procedure;
+        self
+    }
+
+    pub fn anesthesia_case(mut self, anesthesia_case: AnesthesiaCaseBuilder) -> Self {
+        self.anesthesia_case = anesthesia_case;
+        self
+    }
+
+    pub fn pre_op(mut self, pre_op: PreOpBuilder) -> Self {
+        self.pre_op = pre_op;
+        self
+    }
+
+    pub fn intra_op(mut self, intra_op: IntraOpBuilder) -> Self {
+        self.intra_op = intra_op;
+        self
+    }
+
+    pub fn post_op(mut self, post_op: PostOpBuilder) -> Self {
+        self.post_op = post_op;
+        self
+    }
+
+    pub fn add_timing_milestone(mut self, timing_milestone: TimingMilestoneType) -> Self {
+        self.timing_milestones.push(timing_milestone);
+        self
+    }
+
+    pub fn outcomes_events(mut self, outcomes_events: OutcomesEventsBuilder) -> Self {
+        self.outcomes_events = Some(outcomes_events);
+        self
+    }
+
+    pub fn anesthesia_details(mut self, anesthesia_details: AnesthesiaDetailsBuilder) -> Self {
+        self.anesthesia_details = Some(anesthesia_details);
+        self
+    }
+
+    /// Builds every section, listing the missing elements of all of them
+    pub fn build(self) -> Result<AnesthesiaRecordType, MissingElements> {
+        let mut missing = Vec::new();
+
+        let demographic = section(&mut missing, "Demographic", self.demographic.build());
+        let procedure = section(&mut missing, "Procedure", self.procedure.build());
+        let anesthesia_case = section(&mut missing, "AnesthesiaCase", self.anesthesia_case.build());
+        let pre_op = section(&mut missing, "PreOp", self.pre_op.build());
+        let intra_op = self.intra_op.build();
+        let post_op = section(&mut missing, "PostOp", self.post_op.build());
+        let outcomes_events = self.outcomes_events.and_then(OutcomesEventsBuilder::build);
+        let anesthesia_details = self
+            .anesthesia_details
+            .and_then(AnesthesiaDetailsBuilder::build);
+
+        match (demographic, procedure, anesthesia_case, pre_op, post_op) {
+            (
+                Some(demographic),
+                Some(procedure),
+                Some(anesthesia_case),
+                Some(pre_op),
+                Some(post_op),
+            ) if missing.is_empty() => Ok(AnesthesiaRecordType {
+                demographic,
+                procedure,
+                anesthesia_case,
+                pre_op,
+                intra_op,
+                post_op,
+                timing_milestones: non_empty(self.timing_milestones)
+                    .map(|timing_milestone| TimingMilestonesSetType { timing_milestone }),
+                outcomes_events,
+                anesthesia_details,
+            }),
+            _ => Err(MissingElements { paths: missing }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DemographicBuilder {
+    patient_id: Option<String>,
+    dob: Option<NaiveDate>,
+    home_zip: Option<ZipCodeType>,
+    home_state: Option<USStateCodeType>,
+    home_city: Option<String>,
+    race: Option<RaceCodeType>,
+    patient_sex: Option<PatientSexCodeType>,
+}
+
+impl DemographicBuilder {
+    pub fn new() -> DemographicBuilder {
+        Default::default()
+    }
+
+    string_setters! {
+        patient_id,
+        home_city,
+    }
+
+    setters! {
+        dob: NaiveDate,
+        home_zip: ZipCodeType,
+        home_state: USStateCodeType,
+        race: RaceCodeType,
+        patient_sex: PatientSexCodeType,
+    }
+
+    pub fn build(self) -> Result<DemographicType, MissingElements> {
+        let mut missing = Vec::new();
+        let patient_sex = require(&mut missing, "PatientSex", self.patient_sex);
+
+        match patient_sex {
+            Some(patient_sex) => Ok(DemographicType {
+                patient_id: self.patient_id,
+                dob: self.dob,
+                home_zip: self.home_zip,
+                home_state: self.home_state,
+                home_city: self.home_city,
+                race: self.race,
+                patient_sex,
+            }),
+            None => Err(MissingElements { paths: missing }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ProcedureBuilder {
+    procedure_id: Option<String>,
+    facility_id: Option<String>,
+    procedure_location: Option<ProcedureLocationType>,
+    proc_start_time: Option<NaiveDateTime>,
+    proc_end_time: Option<NaiveDateTime>,
+    admission_status: Option<AdmissionStatusCodeType>,
+    proc_status: Option<ProcStatusCodeType>,
+    transfer_status: Option<TransferStatusCodeType>,
+    admission_date: Option<NaiveDateTime>,
+    procedure_notes: Option<String>,
+    cpts: Vec<CPTType>,
+}
+
+impl ProcedureBuilder {
+    pub fn new() -> ProcedureBuilder {
+        Default::default()
+    }
+
+    string_setters! {
+        procedure_id,
+        facility_id,
+        procedure_notes,
+    }
+
+    setters! {
+        procedure_location: ProcedureLocationType,
+        proc_start_time: NaiveDateTime,
+        proc_end_time: NaiveDateTime,
+        admission_status: AdmissionStatusCodeType,
+        proc_status: ProcStatusCodeType,
+        transfer_status: TransferStatusCodeType,
+        admission_date: NaiveDateTime,
+    }
+
+    pub fn add_cpt(mut self, cpt: CPTType) -> Self {
+        self.cpts.push(cpt);
+        self
+    }
+
+    pub fn build(self) -> Result<ProcedureType, MissingElements> {
+        let mut missing = Vec::new();
+        let facility_id = require(&mut missing, "FacilityID", self.facility_id);
+        let proc_status = require(&mut missing, "ProcStatus", self.proc_status);
+
+        match (facility_id, proc_status) {
+            (Some(facility_id), Some(proc_status)) => Ok(ProcedureType {
+                procedure_id: self.procedure_id,
+                facility_id,
+                procedure_location: self.procedure_location,
+                proc_start_time: self.proc_start_time,
+                proc_end_time: self.proc_end_time,
+                admission_status: self.admission_status,
+                proc_status,
+                transfer_status: self.transfer_status,
+                admission_date: self.admission_date,
+                procedure_notes: self.procedure_notes,
+                cpt_set: non_empty(self.cpts).map(|cpt| CPTSetType { cpt }),
+            }),
+            _ => Err(MissingElements { paths: missing }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AnesthesiaCaseBuilder {
+    anesthesia_record_id: Option<String>,
+    anesthesia_coverage: Option<CoverageCodeType>,
+    anesthesia_staff: Vec<AnesthesiaStaffType>,
+    monitors: Vec<MonitorCodeType>,
+    anesthesia_methods: Vec<AnesthesiaMethodType>,
+    airway_managements: Vec<AirwayManagementType>,
+    cpt_anes: Vec<CPTAnesType>,
+}
+
+impl AnesthesiaCaseBuilder {
+    pub fn new() -> AnesthesiaCaseBuilder {
+        Default::default()
+    }
+
+    string_setters! {
+        anesthesia_record_id,
+    }
+
+    setters! {
+        anesthesia_coverage: CoverageCodeType,
+    }
+
+    pub fn add_anesthesia_staff(mut self, anesthesia_staff: AnesthesiaStaffType) -> Self {
+        self.anesthesia_staff.push(anesthesia_staff);
+        self
+    }
+
+    pub fn add_monitor(mut self, monitor: MonitorCodeType) -> Self {
+        self.monitors.push(monitor);
+        self
+    }
+
+    pub fn add_anesthesia_method(mut self, anesthesia_method: AnesthesiaMethodType) -> Self {
+        self.anesthesia_methods.push(anesthesia_method);
+        self
+    }
+
+    pub fn add_airway_management(mut self, airway_management: AirwayManagementType) -> Self {
+        self.airway_managements.push(airway_management);
+        self
+    }
+
+    pub fn add_cpt_anes(mut self, cpt_anes: CPTAnesType) -> Self {
+        self.cpt_anes.push(cpt_anes);
+        self
+    }
+
+    pub fn build(self) -> Result<AnesthesiaCaseType, MissingElements> {
+        let mut missing = Vec::new();
+        let anesthesia_record_id = require(
+            &mut missing,
+            "AnesthesiaRecordID",
+            self.anesthesia_record_id,
+        );
+        require_some(
+            &mut missing,
+            "AnesthesiaStaffSet/AnesthesiaStaff",
+            &self.anesthesia_staff,
+        );
+        require_some(
+            &mut missing,
+            "AnesthesiaMethodSet/AnesthesiaMethod",
+            &self.anesthesia_methods,
+        );
+
+        match anesthesia_record_id {
+            Some(anesthesia_record_id) if missing.is_empty() => Ok(AnesthesiaCaseType {
+                anesthesia_record_id,
+                anesthesia_coverage: self.anesthesia_coverage,
+                anesthesia_staff_set: AnesthesiaStaffSetType {
+                    anesthesia_staff: self.anesthesia_staff,
+                },
+                monitoring_set: non_empty(self.monitors)
+                    .map(|monitor| MonitoringSetType { monitor }),
+                anesthesia_method_set: AnesthesiaMethodSetType {
+                    anesthesia_method: self.anesthesia_methods,
+                },
+                airway_management_set: non_empty(self.airway_managements)
+                    .map(|airway_management| AirwayManagementSetType { airway_management }),
+                cpt_anes_set: non_empty(self.cpt_anes).map(|cpt_anes| CPTAnesSetType { cpt_anes }),
+            }),
+            _ => Err(MissingElements { paths: missing }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PreOpBuilder {
+    age: Option<u64>,
+    weight: Option<DecimalType>,
+    weight_in_kg: Option<DecimalType>,
+    height: Option<DecimalType>,
+    height_in_cm: Option<DecimalType>,
+    asa_class: Option<ASAClassCodeType>,
+    pre_anesth_status: Option<PreAnesthStatusCodeType>,
+    icds: Vec<ICDType>,
+    pre_lab_data: Vec<LabDataType>,
+}
+
+impl PreOpBuilder {
+    pub fn new() -> PreOpBuilder {
+        Default::default()
+    }
+
+    setters! {
+        age: u64,
+        weight: DecimalType,
+        weight_in_kg: DecimalType,
+        height: DecimalType,
+        height_in_cm: DecimalType,
+        asa_class: ASAClassCodeType,
+        pre_anesth_status: PreAnesthStatusCodeType,
+    }
+
+    pub fn add_icd(mut self, icd: ICDType) -> Self {
+        self.icds.push(icd);
+        self
+    }
+
+    pub fn add_pre_lab_data(mut self, pre_lab_data: LabDataType) -> Self {
+        self.pre_lab_data.push(pre_lab_data);
+        self
+    }
+
+    pub fn build(self) -> Result<PreOpType, MissingElements> {
+        let mut missing = Vec::new();
+        let age = require(&mut missing, "Age", self.age);
+        let asa_class = require(&mut missing, "ASAClass", self.asa_class);
+
+        match (age, asa_class) {
+            (Some(age), Some(asa_class)) => Ok(PreOpType {
+                age,
+                weight: self.weight,
+                weight_in_kg: self.weight_in_kg,
+                height: self.height,
+                height_in_cm: self.height_in_cm,
+                asa_class,
+                pre_anesth_status: self.pre_anesth_status,
+                icd_set: non_empty(self.icds).map(|icd| ICDSetType { icd }),
+                pre_lab_set: non_empty(self.pre_lab_data)
+                    .map(|pre_lab_data| PreLabDataSetType { pre_lab_data }),
+            }),
+            _ => Err(MissingElements { paths: missing }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct IntraOpBuilder {
+    medications: Vec<MedicationType>,
+    monitorings: Vec<MonitoringPhysiologicType>,
+    output_events: Vec<OutputEventType>,
+}
+
+impl IntraOpBuilder {
+    pub fn new() -> IntraOpBuilder {
+        Default::default()
+    }
+
+    pub fn add_medication(mut self, medication: MedicationType) -> Self {
+        self.medications.push(medication);
+        self
+    }
+
+    pub fn add_monitoring(mut self, monitoring: MonitoringPhysiologicType) -> Self {
+        self.monitorings.push(monitoring);
+        self
+    }
+
+    pub fn add_output_event(mut self, output_event: OutputEventType) -> Self {
+        self.output_events.push(output_event);
+        self
+    }
+
+    /// Every `IntraOp` element is optional, so an empty builder gives an
+    /// empty `IntraOp`, which the schema allows
+    pub fn build(self) -> IntraOpType {
+        IntraOpType {
+            medications_set: non_empty(self.medications)
+                .map(|medication| MedicationsSetType { medication }),
+            monitoring_physiologic_set: non_empty(self.monitorings)
+                .map(|monitoring| MonitoringPhysiologicSetType { monitoring }),
+            outputs_set: non_empty(self.output_events)
+                .map(|output_event| OutputsSetType { output_event }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PostOpBuilder {
+    post_op_disposition: Option<PostOpDispositionCodeType>,
+    post_op_disp_date_time: Option<NaiveDateTime>,
+    post_op_discharge: Option<PostOpDischargeCodeType>,
+    post_op_discharge_date_time: Option<NaiveDateTime>,
+    length_of_hospital_stay: Option<u64>,
+    payment_methods: Vec<PaymentMethodType>,
+    post_lab_data: Vec<LabDataType>,
+    icds: Vec<ICDType>,
+}
+
+impl PostOpBuilder {
+    pub fn new() -> PostOpBuilder {
+        Default::default()
+    }
+
+    setters! {
+        post_op_disposition: PostOpDispositionCodeType,
+        post_op_disp_date_time: NaiveDateTime,
+        post_op_discharge: PostOpDischargeCodeType,
+        post_op_discharge_date_time: NaiveDateTime,
+        length_of_hospital_stay: u64,
+    }
+
+    pub fn add_payment_method(mut self, payment_method: PaymentMethodType) -> Self {
+        self.payment_methods.push(payment_method);
+        self
+    }
+
+    pub fn add_post_lab_data(mut self, post_lab_data: LabDataType) -> Self {
+        self.post_lab_data.push(post_lab_data);
+        self
+    }
+
+    pub fn add_icd(mut self, icd: ICDType) -> Self {
+        self.icds.push(icd);
+        self
+    }
+
+    pub fn build(self) -> Result<PostOpType, MissingElements> {
+        let mut missing = Vec::new();
+        require_some(&mut missing, "PaymentMethod", &self.payment_methods);
+
+        if !missing.is_empty() {
+            return Err(MissingElements { paths: missing });
+        }
+
+        Ok(PostOpType {
+            post_op_disposition: self.post_op_disposition,
+            post_op_disp_date_time: self.post_op_disp_date_time,
+            post_op_discharge: self.post_op_discharge,
+            post_op_discharge_date_time: self.post_op_discharge_date_time,
+            length_of_hospital_stay: self.length_of_hospital_stay,
+            payment_method: self.payment_methods,
+            post_op_lab_set: non_empty(self.post_lab_data)
+                .map(|post_lab_data| PostOpLabSetType { post_lab_data }),
+            icd_set: non_empty(self.icds).map(|icd| ICDSetType { icd }),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct OutcomesEventsBuilder {
+    ic_events: Vec<ICEventType>,
+    outcomes: Vec<OutcomeCodeType>,
+    qcdrs: Vec<QCDRSetType>,
+}
+
+impl OutcomesEventsBuilder {
+    pub fn new() -> OutcomesEventsBuilder {
+        Default::default()
+    }
+
+    pub fn add_ic_event(mut self, ic_event: ICEventType) -> Self {
+        self.ic_events.push(ic_event);
+        self
+    }
+
+    pub fn add_outcome(mut self, outcome: OutcomeCodeType) -> Self {
+        self.outcomes.push(outcome);
+        self
+    }
+
+    pub fn add_qcdr(mut self, qcdr: QCDRSetType) -> Self {
+        self.qcdrs.push(qcdr);
+        self
+    }
+
+    /// Every `OutcomesEvents` element is optional, so this returns `None`
+    /// rather than an empty wrapper when nothing was added
+    pub fn build(self) -> Option<OutcomesEventsType> {
+        if self.ic_events.is_empty() && self.outcomes.is_empty() && self.qcdrs.is_empty() {
+            return None;
+        }
+
+        Some(OutcomesEventsType {
+            ic_event_set: non_empty(self.ic_events).map(|ic_event| ICEventSetType { ic_event }),
+            outcome_set: non_empty(self.outcomes).map(|outcome| OutcomeSetSetType { outcome }),
+            qcdr_set: non_empty(self.qcdrs).map(|qcdr| QCDRSetTypeSet { qcdr }),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AnesthesiaDetailsBuilder {
+    intake_output_totals: Vec<IntakeOutputTotalType>,
+    intubation_details: Option<IntubationDetailsType>,
+    anesthesia_details_data: Vec<AnesthesiaDetailsDataType>,
+    medications_totals: Vec<MedicationType>,
+}
+
+impl AnesthesiaDetailsBuilder {
+    pub fn new() -> AnesthesiaDetailsBuilder {
+        Default::default()
+    }
+
+    setters! {
+        intubation_details: IntubationDetailsType,
+    }
+
+    pub fn add_intake_output_total(mut self, intake_output_total: IntakeOutputTotalType) -> Self {
+        self.intake_output_totals.push(intake_output_total);
+        self
+    }
+
+    pub fn add_anesthesia_details_data(
+        mut self,
+        anesthesia_details_data: AnesthesiaDetailsDataType,
+    ) -> Self {
+        self.anesthesia_details_data.push(anesthesia_details_data);
+        self
+    }
+
+    pub fn add_medications_total(mut self, medications_total: MedicationType) -> Self {
+        self.medications_totals.push(medications_total);
+        self
+    }
+
+    /// Every `AnesthesiaDetails` element is optional, so this returns `None`
+    /// rather than an empty wrapper when nothing was set
+    pub fn build(self) -> Option<AnesthesiaDetailsType> {
+        if self.intake_output_totals.is_empty()
+            && self.intubation_details.is_none()
+            && self.anesthesia_details_data.is_empty()
+            && self.medications_totals.is_empty()
+        {
+            return None;
+        }
+
+        Some(AnesthesiaDetailsType {
+            intake_output_set: non_empty(self.intake_output_totals).map(|intake_output_total| {
+                IntakeOutputSetType {
+                    intake_output_total,
+                }
+            }),
+            intubation_details: self.intubation_details,
+            anesthesia_details_set: non_empty(self.anesthesia_details_data).map(
+                |anesthesia_details_data| AnesthesiaDetailsSetType {
+                    anesthesia_details_data,
+                },
+            ),
+            medications_total_set: non_empty(self.medications_totals)
+                .map(|medications_totals| MedicationsTotalSetType { medications_totals }),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AnesthesiaStaffBuilder {
+    tax_id: Option<TaxIdType>,
+    npi: Option<NPIType>,
+    staff_responsibility: Option<StaffResponsibilityCodeType>,
+    provider_credentials: Option<ProviderCredentialsCodeType>,
+    staff_sign_in: Option<NaiveDateTime>,
+    staff_sign_out: Option<NaiveDateTime>,
+    staff_notes: Option<String>,
+}
+
+impl AnesthesiaStaffBuilder {
+    pub fn new() -> AnesthesiaStaffBuilder {
+        Default::default()
+    }
+
+    string_setters! {
+        staff_notes,
+    }
+
+    setters! {
+        tax_id: TaxIdType,
+        npi: NPIType,
+        staff_responsibility: StaffResponsibilityCodeType,
+        provider_credentials: ProviderCredentialsCodeType,
+        staff_sign_in: NaiveDateTime,
+        staff_sign_out: NaiveDateTime,
+    }
+
+    pub fn build(self) -> Result<AnesthesiaStaffType, MissingElements> {
+        let mut missing = Vec::new();
+        let tax_id = require(&mut missing, "TaxID", self.tax_id);
+        let npi = require(&mut missing, "NPI", self.npi);
+        let provider_credentials = require(
+            &mut missing,
+            "ProviderCredentials",
+            self.provider_credentials,
+        );
+
+        match (tax_id, npi, provider_credentials) {
+            (Some(tax_id), Some(npi), Some(provider_credentials)) => Ok(AnesthesiaStaffType {
+                tax_id,
+                npi,
+                staff_responsibility: self.staff_responsibility,
+                provider_credentials,
+                staff_sign_in: self.staff_sign_in,
+                staff_sign_out: self.staff_sign_out,
+                staff_notes: self.staff_notes,
+            }),
+            _ => Err(MissingElements { paths: missing }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MedicationBuilder {
+    medication_name: Option<String>,
+    medication_types: Vec<MedicationTypeCodeType>,
+    med_dose: Option<DecimalType>,
+    dose_units: Option<CommonUnit>,
+    dose_start: Option<NaiveDateTime>,
+    dose_end: Option<NaiveDateTime>,
+    med_concentration: Option<DecimalType>,
+    med_concentration_unit: Option<CommonUnit>,
+    medication_routes: Vec<RouteCodeType>,
+    mixture_medications: Vec<MixtureMedicationType>,
+}
+
+impl MedicationBuilder {
+    pub fn new() -> MedicationBuilder {
+        Default::default()
+    }
+
+    string_setters! {
+        medication_name,
+    }
+
+    setters! {
+        med_dose: DecimalType,
+        dose_units: CommonUnit,
+        dose_start: NaiveDateTime,
+        dose_end: NaiveDateTime,
+        med_concentration: DecimalType,
+        med_concentration_unit: CommonUnit,
+    }
+
+    pub fn add_medication_type(mut self, medication_type: MedicationTypeCodeType) -> Self {
+        self.medication_types.push(medication_type);
+        self
+    }
+
+    pub fn add_medication_route(mut self, medication_route: RouteCodeType) -> Self {
+        self.medication_routes.push(medication_route);
+        self
+    }
+
+    pub fn add_mixture_medication(mut self, mixture_medication: MixtureMedicationType) -> Self {
+        self.mixture_medications.push(mixture_medication);
+        self
+    }
+
+    pub fn build(self) -> Result<MedicationType, MissingElements> {
+        let mut missing = Vec::new();
+        let medication_name = require(&mut missing, "MedicationName", self.medication_name);
+
+        match medication_name {
+            Some(medication_name) => Ok(MedicationType {
+                medication_name,
+                medication_type: non_empty(self.medication_types),
+                med_dose: self.med_dose,
+                dose_units: self.dose_units,
+                dose_start: self.dose_start,
+                dose_end: self.dose_end,
+                med_concentration: self.med_concentration,
+                med_concentration_unit: self.med_concentration_unit,
+                medication_route: non_empty(self.medication_routes),
+                mixture_medications: non_empty(self.mixture_medications),
+            }),
+            None => Err(MissingElements { paths: missing }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LabDataBuilder {
+    lab_name: Option<LabDataNameCodeType>,
+    lab_category_name: Option<LabDataCategoryCodeType>,
+    lab_unit: Option<CommonUnit>,
+    lab_value: Option<String>,
+    lab_value_text: Option<String>,
+    lab_range_high: Option<String>,
+    lab_range_low: Option<String>,
+    lab_date_time: Option<NaiveDateTime>,
+    lab_comments: Option<String>,
+}
+
+impl LabDataBuilder {
+    pub fn new() -> LabDataBuilder {
+        Default::default()
+    }
+
+    string_setters! {
+        lab_value,
+        lab_value_text,
+        lab_range_high,
+        lab_range_low,
+        lab_comments,
+    }
+
+    setters! {
+        lab_name: LabDataNameCodeType,
+        lab_category_name: LabDataCategoryCodeType,
+        lab_unit: CommonUnit,
+        lab_date_time: NaiveDateTime,
+    }
+
+    pub fn build(self) -> Result<LabDataType, MissingElements> {
+        let mut missing = Vec::new();
+        let lab_name = require(&mut missing, "LabName", self.lab_name);
+        let lab_unit = require(&mut missing, "LabUnit", self.lab_unit);
+        let lab_value = require(&mut missing, "LabValue", self.lab_value);
+        let lab_date_time = require(&mut missing, "LabDateTime", self.lab_date_time);
+
+        match (lab_name, lab_unit, lab_value, lab_date_time) {
+            (Some(lab_name), Some(lab_unit), Some(lab_value), Some(lab_date_time)) => {
+                Ok(LabDataType {
+                    lab_name,
+                    lab_category_name: self.lab_category_name,
+                    lab_unit,
+                    lab_value,
+                    lab_value_text: self.lab_value_text,
+                    lab_range_high: self.lab_range_high,
+                    lab_range_low: self.lab_range_low,
+                    lab_date_time,
+                    lab_comments: self.lab_comments,
+                })
+            }
+            _ => Err(MissingElements { paths: missing }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2020, 3, 4)
+            .and_then(|date| date.and_hms_opt(hour, 0, 0))
+            .unwrap()
+    }
+
+    #[test]
+    fn lists_every_missing_element() {
+        let err = AnesthesiaRecordBuilder::new()
+            .procedure(ProcedureBuilder::new().facility_id("F1"))
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            err.paths,
+            vec![
+                "Demographic/PatientSex",
+                "Procedure/ProcStatus",
+                "AnesthesiaCase/AnesthesiaRecordID",
+                "AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff",
+                "AnesthesiaCase/AnesthesiaMethodSet/AnesthesiaMethod",
+                "PreOp/Age",
+                "PreOp/ASAClass",
+                "PostOp/PaymentMethod",
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_a_record_matching_the_sample() {
+        let sample = &submission().anesthesia_records[1];
+
+        let staff = AnesthesiaStaffBuilder::new()
+            .tax_id(
+                sample.anesthesia_case.anesthesia_staff_set.anesthesia_staff[0]
+                    .tax_id
+                    .clone(),
+            )
+            .npi(
+                sample.anesthesia_case.anesthesia_staff_set.anesthesia_staff[0]
+                    .npi
+                    .clone(),
+            )
+            .provider_credentials(ProviderCredentialsCodeType::CRNA)
+            .build()
+            .unwrap();
+        let record = AnesthesiaRecordBuilder::new()
+            .demographic(
+                DemographicBuilder::new()
+                    .patient_id("MRN1")
+                    .dob(NaiveDate::from_ymd_opt(1930, 5, 6).unwrap())
+                    .patient_sex(PatientSexCodeType::Male),
+            )
+            .procedure(
+                ProcedureBuilder::new()
+                    .procedure_id("P2")
+                    .facility_id("F1")
+                    .proc_start_time(sample.procedure.proc_start_time.unwrap())
+                    .proc_end_time(sample.procedure.proc_end_time.unwrap())
+                    .proc_status(ProcStatusCodeType::Urgent),
+            )
+            .anesthesia_case(
+                AnesthesiaCaseBuilder::new()
+                    .anesthesia_record_id("R2")
+                    .add_anesthesia_staff(staff)
+                    .add_anesthesia_method(
+                        sample
+                            .anesthesia_case
+                            .anesthesia_method_set
+                            .anesthesia_method[0]
+                            .clone(),
+                    ),
+            )
+            .pre_op(PreOpBuilder::new().age(90).asa_class(ASAClassCodeType::III))
+            .intra_op(
+                IntraOpBuilder::new().add_monitoring(
+                    sample
+                        .intra_op
+                        .monitoring_physiologic_set
+                        .as_ref()
+                        .unwrap()
+                        .monitoring[0]
+                        .clone(),
+                ),
+            )
+            .post_op(
+                PostOpBuilder::new().add_payment_method(sample.post_op.payment_method[0].clone()),
+            )
+            .outcomes_events(OutcomesEventsBuilder::new())
+            .anesthesia_details(AnesthesiaDetailsBuilder::new())
+            .build()
+            .unwrap();
+
+        let mut records = submission();
+        let expected = records.to_xml_string(true).unwrap();
+        records.anesthesia_records[1] = record;
+        assert_eq!(records.to_xml_string(true).unwrap(), expected);
+    }
+
+    #[test]
+    fn leaves_out_empty_optional_sections() {
+        assert!(OutcomesEventsBuilder::new().build().is_none());
+        assert!(AnesthesiaDetailsBuilder::new().build().is_none());
+    }
+
+    #[test]
+    fn builds_an_empty_intra_op() {
+        let intra_op = IntraOpBuilder::new().build();
+
+        assert!(intra_op.medications_set.is_none());
+        assert!(intra_op.monitoring_physiologic_set.is_none());
+        assert!(intra_op.outputs_set.is_none());
+    }
+
+    #[test]
+    fn builds_medications_and_lab_data() {
+        let medication = MedicationBuilder::new()
+            .medication_name("Propofol")
+            .dose_start(time(8))
+            .add_medication_route(RouteCodeType::from_value("IV"))
+            .build()
+            .unwrap();
+        assert_eq!(medication.medication_name, "Propofol");
+        assert_eq!(
+            medication.medication_route.map(|routes| routes.len()),
+            Some(1)
+        );
+        assert!(medication.medication_type.is_none());
+
+        let err = LabDataBuilder::new()
+            .lab_value("13.1")
+            .lab_date_time(time(8))
+            .build()
+            .unwrap_err();
+        assert_eq!(err.paths, vec!["LabName", "LabUnit"]);
+
+        let err = AnesthesiaStaffBuilder::new().build().unwrap_err();
+        assert_eq!(err.paths, vec!["TaxID", "NPI", "ProviderCredentials"]);
+    }
+}
@@ -0,0 +1,968 @@
+//! # De-identification
+//! Removes or generalizes the HIPAA Safe Harbor identifiers in records
+//!
+//! `safe_harbor` removes patient and procedure IDs, home cities and
+//! free-text notes, renumbers the records, truncates ZIP codes to their
+//! first three digits, reduces every date and time to its year and
+//! aggregates ages over 89. Every change is listed in the returned reports,
+//! which name the affected elements but never include the original values,
+//! so they can be kept alongside the extract.
+//!
+//! Reducing dates to the year loses every interval in the record. For
+//! limited datasets, `shift_dates` instead moves every date in a patient's
+//! records by the same secret offset, which keeps intervals and ordering
+//! intact, and `remove_free_text` scrubs the notes.
+//!
+//! `pseudonymize` replaces the patient, procedure and record IDs with keyed
+//! pseudonyms, so cases can still be linked across submissions. The
+//! `Crosswalk` it returns maps them back and should only be saved where
+//! authorized staff can get at it.
+
+use super::validate::child_path;
+use super::*;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+
+use chrono::{Datelike, Duration};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// ZIP3s covering 20,000 or fewer people, per the 2000 Census, which have to
+/// be replaced with 000
+pub const RESTRICTED_ZIP3S: &[&str] = &[
+    "036", "059", "063", "102", "203", "556", "692", "790", "821", "823", "830", "831", "878",
+    "879", "884", "890", "893",
+];
+
+/// Ages above this are reported as `AGGREGATED_AGE`
+pub const MAX_AGE: u64 = 89;
+
+/// The age reported for everyone older than `MAX_AGE`
+pub const AGGREGATED_AGE: u64 = 90;
+
+/// What happened to a single element
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    Removed,
+    /// Replaced with a less specific value, which is included
+    Generalized(String),
+    /// Replaced with an unrelated value, which is included
+    Replaced(String),
+}
+
+/// A single change made by `safe_harbor`
+#[derive(Clone, Debug)]
+pub struct Change {
+    /// Path relative to the record
+    pub path: String,
+    pub action: Action,
+}
+
+/// Everything `safe_harbor` changed in a single record
+#[derive(Debug)]
+pub struct RecordReport {
+    /// Position of the record in `anesthesia_records`
+    pub index: usize,
+    pub changes: Vec<Change>,
+}
+
+/// De-identified records, along with a report for each record
+#[derive(Debug)]
+pub struct Deidentified {
+    pub records: AnesthesiaRecordsType,
+    pub reports: Vec<RecordReport>,
+}
+
+/// Applies the Safe Harbor rules to every record
+pub fn safe_harbor(mut records: AnesthesiaRecordsType) -> Deidentified {
+    let reports = records
+        .anesthesia_records
+        .iter_mut()
+        .enumerate()
+        .map(|(index, record)| RecordReport {
+            index,
+            changes: safe_harbor_record(record, index),
+        })
+        .collect();
+
+    Deidentified { records, reports }
+}
+
+/// Applies the Safe Harbor rules to a single record, returning what changed
+///
+/// `index` is the record's position in its submission, its
+/// `AnesthesiaRecordID` is replaced with `index + 1`.
+pub fn safe_harbor_record(record: &mut AnesthesiaRecordType, index: usize) -> Vec<Change> {
+    let mut changes = Changes(Vec::new());
+
+    let record_id = (index + 1).to_string();
+    if record.anesthesia_case.anesthesia_record_id != record_id {
+        changes.replace("AnesthesiaCase/AnesthesiaRecordID", &record_id);
+        record.anesthesia_case.anesthesia_record_id = record_id;
+    }
+    changes.remove("Procedure/ProcedureID", &mut record.procedure.procedure_id);
+
+    let age = record.pre_op.age;
+    let demographic = &mut record.demographic;
+    changes.remove("Demographic/PatientID", &mut demographic.patient_id);
+    changes.remove("Demographic/HomeCity", &mut demographic.home_city);
+
+    if let Some(ref mut home_zip) = demographic.home_zip {
+        let zip = truncate_zip(home_zip.value());
+        if zip != home_zip.value() {
+            changes.generalize("Demographic/HomeZip", &zip);
+            *home_zip = ZipCodeType::from_str(&zip).expect("truncated ZIP codes are valid");
+        }
+    }
+
+    if let Some(ref mut dob) = demographic.dob {
+        let mut year = dob.year();
+        if age > MAX_AGE {
+            year += (age - AGGREGATED_AGE) as i32;
+        }
+
+        let generalized = NaiveDate::from_ymd_opt(year, 1, 1).expect("January 1 is a valid date");
+        if generalized != *dob {
+            changes.generalize("Demographic/DOB", &generalized.format("%F"));
+            *dob = generalized;
+        }
+    }
+
+    if age > MAX_AGE {
+        changes.generalize("PreOp/Age", &AGGREGATED_AGE);
+        record.pre_op.age = AGGREGATED_AGE;
+    }
+
+    visit_date_times(record, |path, time| {
+        let generalized = NaiveDate::from_ymd_opt(time.year(), 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .expect("January 1 is a valid date");
+        if generalized != *time {
+            changes.generalize(path, &generalized.format("%FT%T"));
+            *time = generalized;
+        }
+    });
+
+    changes.0.extend(remove_free_text(record));
+
+    changes.0
+}
+
+/// Removes every free-text note from `record`, returning what changed
+pub fn remove_free_text(record: &mut AnesthesiaRecordType) -> Vec<Change> {
+    let mut changes = Changes(Vec::new());
+
+    changes.remove(
+        "Procedure/ProcedureNotes",
+        &mut record.procedure.procedure_notes,
+    );
+
+    let case = &mut record.anesthesia_case;
+    for (i, staff) in case
+        .anesthesia_staff_set
+        .anesthesia_staff
+        .iter_mut()
+        .enumerate()
+    {
+        changes.remove(
+            &format!(
+                "AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff[{}]/StaffNotes",
+                i
+            ),
+            &mut staff.staff_notes,
+        );
+    }
+
+    for (i, method) in case
+        .anesthesia_method_set
+        .anesthesia_method
+        .iter_mut()
+        .enumerate()
+    {
+        changes.remove(
+            &format!(
+                "AnesthesiaCase/AnesthesiaMethodSet/AnesthesiaMethod[{}]/AnesthesiaNotes",
+                i
+            ),
+            &mut method.anesthesia_notes,
+        );
+    }
+
+    if let Some(ref mut lab_set) = record.pre_op.pre_lab_set {
+        changes.remove_lab_comments("PreOp/PreLabSet/PreLabData", &mut lab_set.pre_lab_data);
+    }
+
+    if let Some(ref mut lab_set) = record.post_op.post_op_lab_set {
+        changes.remove_lab_comments(
+            "PostOp/PostOpLabSet/PostLabData",
+            &mut lab_set.post_lab_data,
+        );
+    }
+
+    if let Some(ref mut event_set) = record
+        .outcomes_events
+        .as_mut()
+        .and_then(|outcomes| outcomes.ic_event_set.as_mut())
+    {
+        for (i, event) in event_set.ic_event.iter_mut().enumerate() {
+            changes.remove(
+                &format!("OutcomesEvents/ICEventSet/ICEvent[{}]/ICNotes", i),
+                &mut event.ic_notes,
+            );
+        }
+    }
+
+    changes.0
+}
+
+/// The first three digits of `zip` padded back to five, or `00000` for a
+/// restricted ZIP3
+fn truncate_zip(zip: &str) -> String {
+    let zip3 = &zip[..3];
+    if RESTRICTED_ZIP3S.contains(&zip3) {
+        "00000".to_string()
+    } else {
+        format!("{}00", zip3)
+    }
+}
+
+struct Changes(Vec<Change>);
+
+impl Changes {
+    fn remove<T>(&mut self, path: &str, value: &mut Option<T>) {
+        if value.take().is_some() {
+            self.0.push(Change {
+                path: path.to_string(),
+                action: Action::Removed,
+            });
+        }
+    }
+
+    fn generalize<T: fmt::Display>(&mut self, path: &str, value: &T) {
+        self.0.push(Change {
+            path: path.to_string(),
+            action: Action::Generalized(value.to_string()),
+        });
+    }
+
+    fn replace(&mut self, path: &str, value: &str) {
+        self.0.push(Change {
+            path: path.to_string(),
+            action: Action::Replaced(value.to_string()),
+        });
+    }
+
+    fn remove_lab_comments(&mut self, path: &str, labs: &mut [LabDataType]) {
+        for (i, lab) in labs.iter_mut().enumerate() {
+            self.remove(
+                &format!("{}[{}]/LabComments", path, i),
+                &mut lab.lab_comments,
+            );
+        }
+    }
+}
+
+/// How far `shift_dates` may move dates
+#[derive(Clone, Copy, Debug)]
+pub struct DateShiftOptions {
+    /// Offsets are between 1 and this many days, earlier or later
+    pub max_days: u32,
+}
+
+impl Default for DateShiftOptions {
+    fn default() -> DateShiftOptions {
+        DateShiftOptions { max_days: 365 }
+    }
+}
+
+/// Moves every date and time in every record by its patient's offset
+///
+/// Records without a `PatientID` are keyed by their `AnesthesiaRecordID`
+/// instead, so their dates are still hidden but won't line up with the
+/// patient's other records. The header's `CreateDate` isn't patient data
+/// and is left alone.
+pub fn shift_dates(records: &mut AnesthesiaRecordsType, key: &[u8], options: &DateShiftOptions) {
+    for record in &mut records.anesthesia_records {
+        let patient = match record.demographic.patient_id {
+            Some(ref patient_id) => patient_id.clone(),
+            None => record.anesthesia_case.anesthesia_record_id.clone(),
+        };
+
+        shift_record_dates(record, patient_offset(key, &patient, options));
+    }
+}
+
+/// The offset `shift_dates` uses for `patient_id`
+///
+/// It's derived from an HMAC-SHA256 of `patient_id` keyed by `key`, so the
+/// same patient and key always get the same offset, and it's never zero.
+pub fn patient_offset(key: &[u8], patient_id: &str, options: &DateShiftOptions) -> Duration {
+    let digest = keyed_digest(key, b"date-shift", patient_id.as_bytes());
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    let n = u64::from_be_bytes(bytes);
+
+    let days = 1 + (n >> 1) % u64::from(options.max_days.max(1));
+    if n & 1 == 0 {
+        Duration::days(days as i64)
+    } else {
+        Duration::days(-(days as i64))
+    }
+}
+
+/// Moves every date and time in `record` by `offset`
+pub fn shift_record_dates(record: &mut AnesthesiaRecordType, offset: Duration) {
+    if let Some(ref mut dob) = record.demographic.dob {
+        *dob += offset;
+    }
+
+    visit_date_times(record, |_, time| *time += offset);
+}
+
+/// Calls `visit` with the path and value of every date and time in `record`
+/// other than the birth date, which is the only `NaiveDate`
+fn visit_date_times<F: FnMut(&str, &mut NaiveDateTime)>(
+    record: &mut AnesthesiaRecordType,
+    mut visit: F,
+) {
+    let procedure = &mut record.procedure;
+    visit_opt(
+        &mut visit,
+        "Procedure/ProcStartTime",
+        &mut procedure.proc_start_time,
+    );
+    visit_opt(
+        &mut visit,
+        "Procedure/ProcEndTime",
+        &mut procedure.proc_end_time,
+    );
+    visit_opt(
+        &mut visit,
+        "Procedure/AdmissionDate",
+        &mut procedure.admission_date,
+    );
+
+    let case = &mut record.anesthesia_case;
+    for (i, staff) in case
+        .anesthesia_staff_set
+        .anesthesia_staff
+        .iter_mut()
+        .enumerate()
+    {
+        let path = format!("AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff[{}]", i);
+        visit_opt(
+            &mut visit,
+            &child_path(&path, "StaffSignIn"),
+            &mut staff.staff_sign_in,
+        );
+        visit_opt(
+            &mut visit,
+            &child_path(&path, "StaffSignOut"),
+            &mut staff.staff_sign_out,
+        );
+    }
+    for (i, method) in case
+        .anesthesia_method_set
+        .anesthesia_method
+        .iter_mut()
+        .enumerate()
+    {
+        let path = format!("AnesthesiaCase/AnesthesiaMethodSet/AnesthesiaMethod[{}]", i);
+        visit(
+            &child_path(&path, "AnesthesiaStartTime"),
+            &mut method.anesthesia_start_time,
+        );
+        visit(
+            &child_path(&path, "AnesthesiaEndTime"),
+            &mut method.anesthesia_end_time,
+        );
+        visit_opt(
+            &mut visit,
+            &child_path(&path, "AnesthesiaInductionStartTime"),
+            &mut method.anesthesia_induction_start_time,
+        );
+    }
+
+    if let Some(ref mut lab_set) = record.pre_op.pre_lab_set {
+        visit_labs(
+            &mut visit,
+            "PreOp/PreLabSet/PreLabData",
+            &mut lab_set.pre_lab_data,
+        );
+    }
+
+    let intra_op = &mut record.intra_op;
+    if let Some(ref mut medications) = intra_op.medications_set {
+        visit_medications(
+            &mut visit,
+            "IntraOp/MedicationsSet/Medication",
+            &mut medications.medication,
+        );
+    }
+    if let Some(ref mut monitoring) = intra_op.monitoring_physiologic_set {
+        for (i, monitoring) in monitoring.monitoring.iter_mut().enumerate() {
+            visit_opt(
+                &mut visit,
+                &format!(
+                    "IntraOp/MonitoringPhysiologicSet/Monitoring[{}]/MonitoringTime",
+                    i
+                ),
+                &mut monitoring.monitoring_time,
+            );
+        }
+    }
+    if let Some(ref mut outputs) = intra_op.outputs_set {
+        for (i, output) in outputs.output_event.iter_mut().enumerate() {
+            let path = format!("IntraOp/OutputsSet/OutputEvent[{}]", i);
+            visit_opt(
+                &mut visit,
+                &child_path(&path, "OutputStartDateTime"),
+                &mut output.output_start_date_time,
+            );
+            visit_opt(
+                &mut visit,
+                &child_path(&path, "OutputEndDateTime"),
+                &mut output.output_end_date_time,
+            );
+        }
+    }
+
+    let post_op = &mut record.post_op;
+    visit_opt(
+        &mut visit,
+        "PostOp/PostOpDispDateTime",
+        &mut post_op.post_op_disp_date_time,
+    );
+    visit_opt(
+        &mut visit,
+        "PostOp/PostOpDischargeDateTime",
+        &mut post_op.post_op_discharge_date_time,
+    );
+    if let Some(ref mut lab_set) = post_op.post_op_lab_set {
+        visit_labs(
+            &mut visit,
+            "PostOp/PostOpLabSet/PostLabData",
+            &mut lab_set.post_lab_data,
+        );
+    }
+
+    if let Some(ref mut milestones) = record.timing_milestones {
+        for (i, milestone) in milestones.timing_milestone.iter_mut().enumerate() {
+            let path = format!("TimingMilestones/TimingMilestone[{}]", i);
+            visit(
+                &child_path(&path, "TMStartTime"),
+                &mut milestone.tm_start_time,
+            );
+            visit_opt(
+                &mut visit,
+                &child_path(&path, "TMEndTime"),
+                &mut milestone.tm_end_time,
+            );
+        }
+    }
+
+    if let Some(ref mut outcomes) = record.outcomes_events {
+        if let Some(ref mut events) = outcomes.ic_event_set {
+            for (i, event) in events.ic_event.iter_mut().enumerate() {
+                visit_opt(
+                    &mut visit,
+                    &format!("OutcomesEvents/ICEventSet/ICEvent[{}]/ICEventTimeDate", i),
+                    &mut event.ic_event_time_date,
+                );
+            }
+        }
+        if let Some(ref mut outcome_set) = outcomes.outcome_set {
+            for (i, outcome) in outcome_set.outcome.iter_mut().enumerate() {
+                visit_opt(
+                    &mut visit,
+                    &format!("OutcomesEvents/OutcomeSet/Outcome[{}]/OutcomeTimeStamp", i),
+                    &mut outcome.outcome_time_stamp,
+                );
+            }
+        }
+    }
+
+    if let Some(ref mut totals) = record
+        .anesthesia_details
+        .as_mut()
+        .and_then(|details| details.medications_total_set.as_mut())
+    {
+        visit_medications(
+            &mut visit,
+            "AnesthesiaDetails/MedicationsTotalSet/MedicationsTotal",
+            &mut totals.medications_totals,
+        );
+    }
+}
+
+fn visit_opt<F: FnMut(&str, &mut NaiveDateTime)>(
+    visit: &mut F,
+    path: &str,
+    time: &mut Option<NaiveDateTime>,
+) {
+    if let Some(ref mut time) = *time {
+        visit(path, time);
+    }
+}
+
+fn visit_labs<F: FnMut(&str, &mut NaiveDateTime)>(
+    visit: &mut F,
+    path: &str,
+    labs: &mut [LabDataType],
+) {
+    for (i, lab) in labs.iter_mut().enumerate() {
+        visit(
+            &format!("{}[{}]/LabDateTime", path, i),
+            &mut lab.lab_date_time,
+        );
+    }
+}
+
+fn visit_medications<F: FnMut(&str, &mut NaiveDateTime)>(
+    visit: &mut F,
+    path: &str,
+    medications: &mut [MedicationType],
+) {
+    for (i, medication) in medications.iter_mut().enumerate() {
+        let path = format!("{}[{}]", path, i);
+        visit_opt(
+            visit,
+            &child_path(&path, "DoseStart"),
+            &mut medication.dose_start,
+        );
+        visit_opt(
+            visit,
+            &child_path(&path, "DoseEnd"),
+            &mut medication.dose_end,
+        );
+    }
+}
+
+/// An identifier `pseudonymize` replaces
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Identifier {
+    PatientID,
+    ProcedureID,
+    AnesthesiaRecordID,
+}
+
+impl Identifier {
+    /// The element the identifier is written as
+    pub fn element_name(&self) -> &'static str {
+        match *self {
+            Identifier::PatientID => "PatientID",
+            Identifier::ProcedureID => "ProcedureID",
+            Identifier::AnesthesiaRecordID => "AnesthesiaRecordID",
+        }
+    }
+}
+
+/// Length of the hex pseudonyms, half of an HMAC-SHA256
+pub const PSEUDONYM_LENGTH: usize = 32;
+
+/// The pseudonym `pseudonymize` uses for `value`
+///
+/// It's a truncated HMAC-SHA256 of `value` keyed by `key`, so the same value
+/// and key always give the same pseudonym. Each kind of identifier is hashed
+/// separately, so a patient and a procedure that happen to share an ID don't
+/// share a pseudonym.
+pub fn pseudonym(key: &[u8], identifier: Identifier, value: &str) -> String {
+    keyed_digest(key, identifier.element_name().as_bytes(), value.as_bytes())
+        .iter()
+        .take(PSEUDONYM_LENGTH / 2)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Replaces every `PatientID`, `ProcedureID` and `AnesthesiaRecordID` with its
+/// pseudonym, returning the crosswalk back to the originals
+pub fn pseudonymize(records: &mut AnesthesiaRecordsType, key: &[u8]) -> Crosswalk {
+    let mut crosswalk = Crosswalk::default();
+
+    for record in &mut records.anesthesia_records {
+        if let Some(ref mut patient_id) = record.demographic.patient_id {
+            crosswalk.replace(key, Identifier::PatientID, patient_id);
+        }
+        if let Some(ref mut procedure_id) = record.procedure.procedure_id {
+            crosswalk.replace(key, Identifier::ProcedureID, procedure_id);
+        }
+        crosswalk.replace(
+            key,
+            Identifier::AnesthesiaRecordID,
+            &mut record.anesthesia_case.anesthesia_record_id,
+        );
+    }
+
+    crosswalk
+}
+
+/// Pseudonyms mapped back to the identifiers they replaced
+///
+/// This re-identifies the records it came from, so treat it like the
+/// originals.
+#[derive(Clone, Debug, Default)]
+pub struct Crosswalk {
+    entries: BTreeMap<(Identifier, String), String>,
+}
+
+impl Crosswalk {
+    /// The identifier `pseudonym` replaced, if it's in this crosswalk
+    pub fn original(&self, identifier: Identifier, pseudonym: &str) -> Option<&str> {
+        self.entries
+            .get(&(identifier, pseudonym.to_string()))
+            .map(String::as_str)
+    }
+
+    /// Every `(identifier, pseudonym, original)`, ordered by identifier and pseudonym
+    pub fn iter(&self) -> impl Iterator<Item = (Identifier, &str, &str)> {
+        self.entries
+            .iter()
+            .map(|((identifier, pseudonym), original)| {
+                (*identifier, pseudonym.as_str(), original.as_str())
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds another crosswalk's entries, e.g. from an earlier submission
+    /// pseudonymized with the same key
+    pub fn extend(&mut self, other: Crosswalk) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Writes the crosswalk as CSV, with an `Identifier,Pseudonym,Original` header
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), AQIError> {
+        writeln!(writer, "Identifier,Pseudonym,Original")?;
+        for (identifier, pseudonym, original) in self.iter() {
+            writeln!(
+                writer,
+                "{},{},{}",
+                identifier.element_name(),
+                pseudonym,
+                csv_field(original)
+            )?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes the crosswalk as CSV to `path`, readable and writable only by
+    /// its owner on Unix
+    ///
+    /// The crosswalk is written to a new file next to `path` and renamed over
+    /// it, so an existing file or symlink at `path` is replaced rather than
+    /// written through, and a failed save leaves it as it was.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), AQIError> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| AQIError::parse(format!("{} isn't a file", path.display())))?;
+        let temporary = path.with_file_name(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            process::id()
+        ));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            options.mode(0o600);
+        }
+
+        let file = options.open(&temporary)?;
+        let result = self
+            .write(io::BufWriter::new(&file))
+            .and_then(|_| file.sync_all().map_err(AQIError::from))
+            .and_then(|_| fs::rename(&temporary, path).map_err(AQIError::from));
+        if result.is_err() {
+            let _ = fs::remove_file(&temporary);
+        }
+
+        result
+    }
+
+    fn replace(&mut self, key: &[u8], identifier: Identifier, value: &mut String) {
+        let pseudonym = pseudonym(key, identifier, value);
+        let original = std::mem::replace(value, pseudonym.clone());
+        self.entries.insert((identifier, pseudonym), original);
+    }
+}
+
+/// Quotes `value` if it would otherwise break the CSV row
+fn csv_field(value: &str) -> String {
+    if value.contains(&[',', '"', '\r', '\n'][..]) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// HMAC-SHA256 of `input` keyed by `key`, with `purpose` mixed in so the same
+/// key can be used for different transforms without their outputs matching
+fn keyed_digest(key: &[u8], purpose: &[u8], input: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(purpose);
+    mac.update(&[0]);
+    mac.update(input);
+
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::validate::Validate;
+    use super::*;
+
+    #[test]
+    fn safe_harbor_removes_identifiers() {
+        let deidentified = safe_harbor(submission());
+        let xml = deidentified.records.to_xml_string(true).unwrap();
+
+        for identifier in &[
+            "MRN1",
+            "<ProcedureID>",
+            "Milwaukee",
+            "53226",
+            "1930-05-06",
+            "Left knee",
+            "Called in",
+            "Uneventful",
+            "2020-03-04",
+            "2020-06-10",
+        ] {
+            assert!(!xml.contains(identifier), "{} wasn't removed", identifier);
+        }
+
+        assert!(xml.contains("<HomeZip>53200</HomeZip>"));
+        assert!(xml.contains("<DOB>1930-01-01</DOB>"));
+        assert!(xml.contains("<ProcStartTime>2020-01-01T00:00:00.000</ProcStartTime>"));
+        assert!(xml.contains("<AnesthesiaRecordID>1</AnesthesiaRecordID>"));
+        assert!(xml.contains("<AnesthesiaRecordID>2</AnesthesiaRecordID>"));
+        assert_eq!(deidentified.records.violations(), vec![]);
+    }
+
+    #[test]
+    fn safe_harbor_reports_never_include_original_values() {
+        let deidentified = safe_harbor(submission());
+        let changes = &deidentified.reports[0].changes;
+
+        assert!(changes.iter().any(|change| {
+            change.path == "Demographic/PatientID" && change.action == Action::Removed
+        }));
+        assert!(changes.iter().any(|change| {
+            change.path == "Demographic/HomeZip"
+                && change.action == Action::Generalized("53200".to_string())
+        }));
+        assert!(changes.iter().any(|change| {
+            change.path == "AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff[0]/StaffSignIn"
+                && change.action == Action::Generalized("2020-01-01T00:00:00".to_string())
+        }));
+
+        for change in deidentified
+            .reports
+            .iter()
+            .flat_map(|report| &report.changes)
+        {
+            let value = match change.action {
+                Action::Removed => continue,
+                Action::Generalized(ref value) | Action::Replaced(ref value) => value,
+            };
+
+            for original in &["MRN1", "53226", "R1", "R2", "1930-05-06", "2020-03-04"] {
+                assert!(!value.contains(original), "{:?} leaks {}", change, original);
+            }
+        }
+    }
+
+    #[test]
+    fn safe_harbor_aggregates_ages_over_89() {
+        let mut records = submission();
+        let record = &mut records.anesthesia_records[0];
+        record.pre_op.age = 95;
+        record.demographic.dob = NaiveDate::from_ymd_opt(1925, 2, 1);
+
+        let record = &safe_harbor(records).records.anesthesia_records[0];
+        assert_eq!(record.pre_op.age, AGGREGATED_AGE);
+        assert_eq!(record.demographic.dob, NaiveDate::from_ymd_opt(1930, 1, 1));
+    }
+
+    #[test]
+    fn truncates_restricted_zip3s_to_zeros() {
+        assert_eq!(truncate_zip("53226-1234"), "53200");
+        assert_eq!(truncate_zip("03601"), "00000");
+    }
+
+    const KEY: &[u8] = b"test key";
+
+    #[test]
+    fn patient_offsets_are_deterministic() {
+        let options = DateShiftOptions { max_days: 30 };
+        let offset = patient_offset(KEY, "MRN1", &options);
+
+        assert_eq!(patient_offset(KEY, "MRN1", &options), offset);
+        assert_ne!(offset, Duration::zero());
+        assert!(offset.num_days().abs() <= 30);
+
+        let offsets = (0..100)
+            .map(|i| patient_offset(KEY, &format!("MRN{}", i), &options))
+            .collect::<Vec<_>>();
+        assert!(offsets.iter().any(|offset| *offset != offsets[0]));
+        assert!(offsets
+            .iter()
+            .all(|offset| *offset != Duration::zero() && offset.num_days().abs() <= 30));
+    }
+
+    #[test]
+    fn shift_dates_moves_a_patients_records_together() {
+        let original = submission();
+        let mut shifted = submission();
+        shift_dates(&mut shifted, KEY, &Default::default());
+
+        let offset = patient_offset(KEY, "MRN1", &Default::default());
+        for (before, after) in original
+            .anesthesia_records
+            .iter()
+            .zip(&shifted.anesthesia_records)
+        {
+            assert_eq!(
+                after.procedure.proc_start_time,
+                before.procedure.proc_start_time.map(|time| time + offset)
+            );
+            assert_eq!(
+                after.demographic.dob,
+                before.demographic.dob.map(|dob| dob + offset)
+            );
+        }
+
+        let staff = &shifted.anesthesia_records[0]
+            .anesthesia_case
+            .anesthesia_staff_set
+            .anesthesia_staff[0];
+        assert_eq!(
+            staff.staff_sign_in,
+            original.anesthesia_records[0]
+                .anesthesia_case
+                .anesthesia_staff_set
+                .anesthesia_staff[0]
+                .staff_sign_in
+                .map(|time| time + offset)
+        );
+        assert_eq!(
+            shifted.record_header.create_date,
+            original.record_header.create_date
+        );
+
+        let mut again = submission();
+        shift_dates(&mut again, KEY, &Default::default());
+        assert_eq!(
+            again.to_xml_string(true).unwrap(),
+            shifted.to_xml_string(true).unwrap()
+        );
+    }
+
+    #[test]
+    fn pseudonymize_links_records_and_maps_back() {
+        let mut records = submission();
+        let crosswalk = pseudonymize(&mut records, KEY);
+
+        let patient_ids = records
+            .anesthesia_records
+            .iter()
+            .map(|record| record.demographic.patient_id.clone().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(patient_ids[0], patient_ids[1]);
+        assert_eq!(
+            patient_ids[0],
+            pseudonym(KEY, Identifier::PatientID, "MRN1")
+        );
+        assert_eq!(patient_ids[0].len(), PSEUDONYM_LENGTH);
+        assert_ne!(
+            pseudonym(b"other key", Identifier::PatientID, "MRN1"),
+            patient_ids[0]
+        );
+
+        // MRN1 once, plus two procedures and two records
+        assert_eq!(crosswalk.len(), 5);
+        assert_eq!(
+            crosswalk.original(Identifier::PatientID, &patient_ids[0]),
+            Some("MRN1")
+        );
+        let record_id = &records.anesthesia_records[1]
+            .anesthesia_case
+            .anesthesia_record_id;
+        assert_eq!(
+            crosswalk.original(Identifier::AnesthesiaRecordID, record_id),
+            Some("R2")
+        );
+    }
+
+    #[test]
+    fn pseudonyms_differ_by_identifier() {
+        assert_ne!(
+            pseudonym(KEY, Identifier::PatientID, "1"),
+            pseudonym(KEY, Identifier::ProcedureID, "1")
+        );
+    }
+
+    #[test]
+    fn crosswalk_quotes_csv_fields() {
+        let mut records = submission();
+        records.anesthesia_records[0].demographic.patient_id = Some("Doe, \"J\"".to_string());
+        let crosswalk = pseudonymize(&mut records, KEY);
+
+        let mut csv = Vec::new();
+        crosswalk.write(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        assert!(csv.starts_with("Identifier,Pseudonym,Original\n"));
+        assert!(csv.contains(&format!(
+            "PatientID,{},\"Doe, \"\"J\"\"\"\n",
+            pseudonym(KEY, Identifier::PatientID, "Doe, \"J\"")
+        )));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn saves_the_crosswalk_owner_only_without_following_symlinks() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let mut records = submission();
+        let crosswalk = pseudonymize(&mut records, b"key");
+        let mut expected = Vec::new();
+        crosswalk.write(&mut expected).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("nacor-crosswalk-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("target.csv");
+        let link = dir.join("crosswalk.csv");
+        fs::write(&target, "unrelated").unwrap();
+        symlink(&target, &link).unwrap();
+
+        crosswalk.save(&link).unwrap();
+
+        assert_eq!(fs::read(&link).unwrap(), expected);
+        assert_eq!(fs::read_to_string(&target).unwrap(), "unrelated");
+        let metadata = fs::symlink_metadata(&link).unwrap();
+        assert!(metadata.is_file());
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
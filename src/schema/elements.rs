@@ -0,0 +1,39 @@
+//! # Schema elements
+//! The elements of every complex type, for walking the schema at runtime
+//!
+//! The table is generated from `schemas/AQISchema.xsd` by
+//! `aqi-nacor-codegen`, along with the types it describes.
+
+mod generated;
+
+/// What an element holds
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Kind {
+    /// A nested element, of the named complex type
+    Complex(&'static str),
+    Text,
+    Date,
+    DateTime,
+}
+
+pub(crate) struct SchemaType {
+    pub name: &'static str,
+    pub elements: &'static [SchemaElement],
+}
+
+pub(crate) struct SchemaElement {
+    pub name: &'static str,
+    /// The struct field it's read into
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub field: &'static str,
+    pub kind: Kind,
+    pub repeated: bool,
+}
+
+/// The complex type named `name`, which has to be in the schema
+pub(crate) fn complex_type(name: &str) -> &'static SchemaType {
+    generated::COMPLEX_TYPES
+        .iter()
+        .find(|complex| complex.name == name)
+        .unwrap_or_else(|| panic!("{} isn't in the schema", name))
+}
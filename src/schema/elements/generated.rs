@@ -0,0 +1,1440 @@
+// Generated by aqi-nacor-codegen from schemas/AQISchema.xsd, don't edit by hand.
+// Change the schema or codegen/src/names.rs and run `cargo run -p aqi-nacor-codegen`.
+
+use super::*;
+
+pub(super) const COMPLEX_TYPES: &[SchemaType] = &[
+    SchemaType {
+        name: "AnesthesiaRecordsType",
+        elements: &[
+            SchemaElement {
+                name: "RecordHeader",
+                field: "record_header",
+                kind: Kind::Complex("RecordHeaderType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaRecord",
+                field: "anesthesia_records",
+                kind: Kind::Complex("AnesthesiaRecordType"),
+                repeated: true,
+            },
+        ],
+    },
+    SchemaType {
+        name: "RecordHeaderType",
+        elements: &[
+            SchemaElement {
+                name: "PracticeID",
+                field: "practice_id",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "CreatedBy",
+                field: "created_by",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "CreateDate",
+                field: "create_date",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "EmailSet",
+                field: "email_set",
+                kind: Kind::Complex("EmailSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AQIXMLVersion",
+                field: "aqi_xml_version",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "VendorSet",
+                field: "vendor_set",
+                kind: Kind::Complex("VendorSetType"),
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "EmailSetType",
+        elements: &[SchemaElement {
+            name: "EmailNotificationSet",
+            field: "email_notification_set",
+            kind: Kind::Complex("EmailNotificationSetType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "EmailNotificationSetType",
+        elements: &[
+            SchemaElement {
+                name: "EmailNotificationFirstName",
+                field: "email_notification_first_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "EmailNotificationLastName",
+                field: "email_notification_last_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "EmailNotificationAddress",
+                field: "email_notification_address",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "VendorSetType",
+        elements: &[SchemaElement {
+            name: "Vendor",
+            field: "vendor",
+            kind: Kind::Complex("Vendors"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "Vendors",
+        elements: &[
+            SchemaElement {
+                name: "VendorID",
+                field: "vendor_id",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "VendorSetType",
+                field: "vendor_set_type",
+                kind: Kind::Complex("SetVendorSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "VendorName",
+                field: "vendor_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "SetVendorSetType",
+        elements: &[SchemaElement {
+            name: "VendorType",
+            field: "vendor_type",
+            kind: Kind::Text,
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "AnesthesiaRecordType",
+        elements: &[
+            SchemaElement {
+                name: "Demographic",
+                field: "demographic",
+                kind: Kind::Complex("DemographicType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "Procedure",
+                field: "procedure",
+                kind: Kind::Complex("ProcedureType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaCase",
+                field: "anesthesia_case",
+                kind: Kind::Complex("AnesthesiaCaseType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PreOp",
+                field: "pre_op",
+                kind: Kind::Complex("PreOpType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "IntraOp",
+                field: "intra_op",
+                kind: Kind::Complex("IntraOpType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PostOp",
+                field: "post_op",
+                kind: Kind::Complex("PostOpType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "TimingMilestones",
+                field: "timing_milestones",
+                kind: Kind::Complex("TimingMilestonesSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutcomesEvents",
+                field: "outcomes_events",
+                kind: Kind::Complex("OutcomesEventsType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaDetails",
+                field: "anesthesia_details",
+                kind: Kind::Complex("AnesthesiaDetailsType"),
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "DemographicType",
+        elements: &[
+            SchemaElement {
+                name: "PatientID",
+                field: "patient_id",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "DOB",
+                field: "dob",
+                kind: Kind::Date,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "HomeZip",
+                field: "home_zip",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "HomeState",
+                field: "home_state",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "HomeCity",
+                field: "home_city",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "Race",
+                field: "race",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PatientSex",
+                field: "patient_sex",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "ProcedureType",
+        elements: &[
+            SchemaElement {
+                name: "ProcedureID",
+                field: "procedure_id",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "FacilityID",
+                field: "facility_id",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ProcedureLocation",
+                field: "procedure_location",
+                kind: Kind::Complex("ProcedureLocationType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ProcStartTime",
+                field: "proc_start_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ProcEndTime",
+                field: "proc_end_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AdmissionStatus",
+                field: "admission_status",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ProcStatus",
+                field: "proc_status",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "TransferStatus",
+                field: "transfer_status",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AdmissionDate",
+                field: "admission_date",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ProcedureNotes",
+                field: "procedure_notes",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "CPTSet",
+                field: "cpt_set",
+                kind: Kind::Complex("CPTSetType"),
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "ProcedureLocationType",
+        elements: &[
+            SchemaElement {
+                name: "LocationType",
+                field: "location_type",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "LocationDetails",
+                field: "location_details",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "CPTSetType",
+        elements: &[SchemaElement {
+            name: "CPT",
+            field: "cpt",
+            kind: Kind::Complex("CPTType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "CPTType",
+        elements: &[
+            SchemaElement {
+                name: "CPTRank",
+                field: "cpt_rank",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "CPTValue",
+                field: "cpt_value",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "CPTModifier",
+                field: "cpt_modifier",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "AnesthesiaCaseType",
+        elements: &[
+            SchemaElement {
+                name: "AnesthesiaRecordID",
+                field: "anesthesia_record_id",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaCoverage",
+                field: "anesthesia_coverage",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaStaffSet",
+                field: "anesthesia_staff_set",
+                kind: Kind::Complex("AnesthesiaStaffSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MonitoringSet",
+                field: "monitoring_set",
+                kind: Kind::Complex("MonitoringSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaMethodSet",
+                field: "anesthesia_method_set",
+                kind: Kind::Complex("AnesthesiaMethodSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AirwayManagementSet",
+                field: "airway_management_set",
+                kind: Kind::Complex("AirwayManagementSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "CPTAnesSet",
+                field: "cpt_anes_set",
+                kind: Kind::Complex("CPTAnesSetType"),
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "AnesthesiaStaffSetType",
+        elements: &[SchemaElement {
+            name: "AnesthesiaStaff",
+            field: "anesthesia_staff",
+            kind: Kind::Complex("AnesthesiaStaffType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "AnesthesiaStaffType",
+        elements: &[
+            SchemaElement {
+                name: "TaxID",
+                field: "tax_id",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "NPI",
+                field: "npi",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "StaffResponsibility",
+                field: "staff_responsibility",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ProviderCredentials",
+                field: "provider_credentials",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "StaffSignIn",
+                field: "staff_sign_in",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "StaffSignOut",
+                field: "staff_sign_out",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "StaffNotes",
+                field: "staff_notes",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "MonitoringSetType",
+        elements: &[SchemaElement {
+            name: "Monitor",
+            field: "monitor",
+            kind: Kind::Text,
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "AnesthesiaMethodSetType",
+        elements: &[SchemaElement {
+            name: "AnesthesiaMethod",
+            field: "anesthesia_method",
+            kind: Kind::Complex("AnesthesiaMethodType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "AnesthesiaMethodType",
+        elements: &[
+            SchemaElement {
+                name: "AnesthesiaCategory",
+                field: "anesthesia_category",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaSubCategory",
+                field: "anesthesia_subcategory",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaStartTime",
+                field: "anesthesia_start_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaEndTime",
+                field: "anesthesia_end_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaInduction",
+                field: "anesthesia_induction",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaInductionStartTime",
+                field: "anesthesia_induction_start_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaMaintenance",
+                field: "anesthesia_maintenance",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaNotes",
+                field: "anesthesia_notes",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "AirwayManagementSetType",
+        elements: &[SchemaElement {
+            name: "AirwayManagement",
+            field: "airway_management",
+            kind: Kind::Complex("AirwayManagementType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "AirwayManagementType",
+        elements: &[
+            SchemaElement {
+                name: "AirwayManagementMethod",
+                field: "airway_management_method",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AirwaySubManagementMethod",
+                field: "airway_sub_management_method",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "CPTAnesSetType",
+        elements: &[SchemaElement {
+            name: "CPTAnes",
+            field: "cpt_anes",
+            kind: Kind::Complex("CPTAnesType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "CPTAnesType",
+        elements: &[
+            SchemaElement {
+                name: "CPTAnesValue",
+                field: "cpt_anes_value",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "CPTAnesModifier",
+                field: "cpt_anes_modifier",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "CPTAnesDescription",
+                field: "cpt_anes_description",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "PreOpType",
+        elements: &[
+            SchemaElement {
+                name: "Age",
+                field: "age",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "Weight",
+                field: "weight",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "WeightInKg",
+                field: "weight_in_kg",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "Height",
+                field: "height",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "HeightInCm",
+                field: "height_in_cm",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ASAClass",
+                field: "asa_class",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PreAnesthStatus",
+                field: "pre_anesth_status",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ICDSet",
+                field: "icd_set",
+                kind: Kind::Complex("ICDSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PreLabSet",
+                field: "pre_lab_set",
+                kind: Kind::Complex("PreLabDataSetType"),
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "ICDSetType",
+        elements: &[SchemaElement {
+            name: "ICD",
+            field: "icd",
+            kind: Kind::Complex("ICDType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "ICDType",
+        elements: &[
+            SchemaElement {
+                name: "ICDRank",
+                field: "icd_rank",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ICDValue",
+                field: "icd_value",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ICDVersion",
+                field: "icd_version",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "PreLabDataSetType",
+        elements: &[SchemaElement {
+            name: "PreLabData",
+            field: "pre_lab_data",
+            kind: Kind::Complex("LabDataType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "LabDataType",
+        elements: &[
+            SchemaElement {
+                name: "LabName",
+                field: "lab_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "LabCategoryName",
+                field: "lab_category_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "LabUnit",
+                field: "lab_unit",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "LabValue",
+                field: "lab_value",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "LabValueText",
+                field: "lab_value_text",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "LabRangeHigh",
+                field: "lab_range_high",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "LabRangeLow",
+                field: "lab_range_low",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "LabDateTime",
+                field: "lab_date_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "LabComments",
+                field: "lab_comments",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "IntraOpType",
+        elements: &[
+            SchemaElement {
+                name: "MedicationsSet",
+                field: "medications_set",
+                kind: Kind::Complex("MedicationsSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MonitoringPhysiologicSet",
+                field: "monitoring_physiologic_set",
+                kind: Kind::Complex("MonitoringPhysiologicSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutputsSet",
+                field: "outputs_set",
+                kind: Kind::Complex("OutputsSetType"),
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "MedicationsSetType",
+        elements: &[SchemaElement {
+            name: "Medication",
+            field: "medication",
+            kind: Kind::Complex("MedicationType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "MedicationType",
+        elements: &[
+            SchemaElement {
+                name: "MedicationName",
+                field: "medication_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MedicationType",
+                field: "medication_type",
+                kind: Kind::Text,
+                repeated: true,
+            },
+            SchemaElement {
+                name: "MedDose",
+                field: "med_dose",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "DoseUnits",
+                field: "dose_units",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "DoseStart",
+                field: "dose_start",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "DoseEnd",
+                field: "dose_end",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MedConcentration",
+                field: "med_concentration",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MedConcentrationUnit",
+                field: "med_concentration_unit",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MedicationRoute",
+                field: "medication_route",
+                kind: Kind::Text,
+                repeated: true,
+            },
+            SchemaElement {
+                name: "MixtureMedication",
+                field: "mixture_medications",
+                kind: Kind::Complex("MixtureMedicationType"),
+                repeated: true,
+            },
+        ],
+    },
+    SchemaType {
+        name: "MixtureMedicationType",
+        elements: &[
+            SchemaElement {
+                name: "MixtureMedicationName",
+                field: "mixture_medication_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MixtureMedicationType",
+                field: "mixture_medication_type",
+                kind: Kind::Text,
+                repeated: true,
+            },
+            SchemaElement {
+                name: "MixtureMedDose",
+                field: "mixture_med_dose",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MixtureDoseUnits",
+                field: "mixture_dose_units",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MixtureMedConcentration",
+                field: "mixture_med_concentration",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MixtureMedConcentrationUnit",
+                field: "mixture_med_concentration_unit",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "MonitoringPhysiologicSetType",
+        elements: &[SchemaElement {
+            name: "Monitoring",
+            field: "monitoring",
+            kind: Kind::Complex("MonitoringPhysiologicType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "MonitoringPhysiologicType",
+        elements: &[
+            SchemaElement {
+                name: "MonitoringName",
+                field: "monitoring_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MonitoringTime",
+                field: "monitoring_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MonitoringUnits",
+                field: "monitoring_units",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MonitoringValueNumeric",
+                field: "monitoring_value_numeric",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MonitoringValueText",
+                field: "monitoring_value_text",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MonitoringSource",
+                field: "monitoring_source",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "OutputsSetType",
+        elements: &[SchemaElement {
+            name: "OutputEvent",
+            field: "output_event",
+            kind: Kind::Complex("OutputEventType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "OutputEventType",
+        elements: &[
+            SchemaElement {
+                name: "OutputEventName",
+                field: "output_event_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutputUnits",
+                field: "output_units",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutputValueNumeric",
+                field: "output_value_numeric",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutputValueText",
+                field: "output_value_text",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutputStartDateTime",
+                field: "output_start_date_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutputEndDateTime",
+                field: "output_end_date_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "PostOpType",
+        elements: &[
+            SchemaElement {
+                name: "PostOpDisposition",
+                field: "post_op_disposition",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PostOpDispDateTime",
+                field: "post_op_disp_date_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PostOpDischarge",
+                field: "post_op_discharge",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PostOpDischargeDateTime",
+                field: "post_op_discharge_date_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "LengthOfHospitalStay",
+                field: "length_of_hospital_stay",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PaymentMethod",
+                field: "payment_method",
+                kind: Kind::Complex("PaymentMethodType"),
+                repeated: true,
+            },
+            SchemaElement {
+                name: "PostOpLabSet",
+                field: "post_op_lab_set",
+                kind: Kind::Complex("PostOpLabSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ICDSet",
+                field: "icd_set",
+                kind: Kind::Complex("ICDSetType"),
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "PaymentMethodType",
+        elements: &[
+            SchemaElement {
+                name: "PaymentCode",
+                field: "payment_code",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PaymentDescription",
+                field: "payment_description",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PaymentDescriptionSec",
+                field: "payment_description_sec",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "PaymentDescriptionTh",
+                field: "payment_description_th",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "PostOpLabSetType",
+        elements: &[SchemaElement {
+            name: "PostLabData",
+            field: "post_lab_data",
+            kind: Kind::Complex("LabDataType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "TimingMilestonesSetType",
+        elements: &[SchemaElement {
+            name: "TimingMilestone",
+            field: "timing_milestone",
+            kind: Kind::Complex("TimingMilestoneType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "TimingMilestoneType",
+        elements: &[
+            SchemaElement {
+                name: "TMType",
+                field: "tm_type",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "TMStartTime",
+                field: "tm_start_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "TMEndTime",
+                field: "tm_end_time",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "OutcomesEventsType",
+        elements: &[
+            SchemaElement {
+                name: "ICEventSet",
+                field: "ic_event_set",
+                kind: Kind::Complex("ICEventSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutcomeSet",
+                field: "outcome_set",
+                kind: Kind::Complex("OutcomeSetSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "QCDRSet",
+                field: "qcdr_set",
+                kind: Kind::Complex("QCDRSetTypeSet"),
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "ICEventSetType",
+        elements: &[SchemaElement {
+            name: "ICEvent",
+            field: "ic_event",
+            kind: Kind::Complex("ICEventType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "ICEventType",
+        elements: &[
+            SchemaElement {
+                name: "ICEventTimeDate",
+                field: "ic_event_time_date",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ICCategory",
+                field: "ic_category",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ICSeverity",
+                field: "ic_severity",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ICName",
+                field: "ic_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ICDescription",
+                field: "ic_description",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ICValue",
+                field: "ic_value",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "ICNotes",
+                field: "ic_notes",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "OutcomeSetSetType",
+        elements: &[SchemaElement {
+            name: "Outcome",
+            field: "outcome",
+            kind: Kind::Complex("OutcomeCodeType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "OutcomeCodeType",
+        elements: &[
+            SchemaElement {
+                name: "OutcomeID",
+                field: "outcome_id",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutcomeOccurred",
+                field: "outcome_occurred",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutcomeTimeStamp",
+                field: "outcome_time_stamp",
+                kind: Kind::DateTime,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutcomeSeverity",
+                field: "outcome_severity",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutcomeTimeFrame",
+                field: "outcome_time_frame",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "QCDRSetTypeSet",
+        elements: &[SchemaElement {
+            name: "QCDR",
+            field: "qcdr",
+            kind: Kind::Complex("QCDRSetType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "QCDRSetType",
+        elements: &[
+            SchemaElement {
+                name: "QCDRMeasure",
+                field: "qcdr_measure",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "QCDRCodeValue",
+                field: "qcdr_code_value",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "QCDRModifier",
+                field: "qcdr_modifier",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "AnesthesiaDetailsType",
+        elements: &[
+            SchemaElement {
+                name: "IntakeOutputSet",
+                field: "intake_output_set",
+                kind: Kind::Complex("IntakeOutputSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "IntubationDetails",
+                field: "intubation_details",
+                kind: Kind::Complex("IntubationDetailsType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaDetailsSet",
+                field: "anesthesia_details_set",
+                kind: Kind::Complex("AnesthesiaDetailsSetType"),
+                repeated: false,
+            },
+            SchemaElement {
+                name: "MedicationsTotalSet",
+                field: "medications_total_set",
+                kind: Kind::Complex("MedicationsTotalSetType"),
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "IntakeOutputSetType",
+        elements: &[SchemaElement {
+            name: "IntakeOutputTotal",
+            field: "intake_output_total",
+            kind: Kind::Complex("IntakeOutputTotalType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "IntakeOutputTotalType",
+        elements: &[
+            SchemaElement {
+                name: "IntakeOutputDirection",
+                field: "intake_output_direction",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "InputOutputName",
+                field: "input_output_name",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "OutputUnits",
+                field: "output_units",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "InputOutputTotal",
+                field: "input_output_total",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "InputOutputRoute",
+                field: "input_output_route",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "IntubationDetailsType",
+        elements: &[
+            SchemaElement {
+                name: "IntubationApproach",
+                field: "intubation_approach",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "IntubationAttempts",
+                field: "intubation_attempts",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "TubeSize",
+                field: "tube_size",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "TubeType",
+                field: "tube_type",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "IntubationDetailsPropertiesSet",
+                field: "intubation_details_properties_set",
+                kind: Kind::Complex("IntubationDetailsPropertiesType"),
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "IntubationDetailsPropertiesType",
+        elements: &[SchemaElement {
+            name: "IntubationDetailsProperties",
+            field: "intubation_details_properties",
+            kind: Kind::Complex("IntubationDetailsPropertyType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "IntubationDetailsPropertyType",
+        elements: &[
+            SchemaElement {
+                name: "IntubationProperty",
+                field: "intubation_property",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "IntubationDetailsPropertyValue",
+                field: "intubation_details_property_value",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "AnesthesiaDetailsSetType",
+        elements: &[SchemaElement {
+            name: "AnesthesiaDetailsData",
+            field: "anesthesia_details_data",
+            kind: Kind::Complex("AnesthesiaDetailsDataType"),
+            repeated: true,
+        }],
+    },
+    SchemaType {
+        name: "AnesthesiaDetailsDataType",
+        elements: &[
+            SchemaElement {
+                name: "AnesthesiaDetailsCategory",
+                field: "anesthesia_details_category",
+                kind: Kind::Text,
+                repeated: false,
+            },
+            SchemaElement {
+                name: "AnesthesiaDetailsValue",
+                field: "anesthesia_details_value",
+                kind: Kind::Text,
+                repeated: false,
+            },
+        ],
+    },
+    SchemaType {
+        name: "MedicationsTotalSetType",
+        elements: &[SchemaElement {
+            name: "MedicationsTotal",
+            field: "medications_totals",
+            kind: Kind::Complex("MedicationType"),
+            repeated: true,
+        }],
+    },
+];
@@ -0,0 +1,968 @@
+//! # Tabular export
+//! Flattens records into normalized tables for analysis
+//!
+//! `tables` returns one table per kind of element, each row keyed by the
+//! `anesthesia_record_id` of its record. Repeated elements get an `index`,
+//! their zero-based position in their set, and tables that combine several
+//! sets, such as pre- and post-op labs, say which one each row came from in
+//! `set`. Values are kept as text: codes as their schema values, numbers,
+//! dates and times as in XML, and lists of codes joined with `;`, while each
+//! column's `ColumnType` says what its text holds.
+//!
+//! The tables can be written as CSV with the `csv` feature and as Parquet,
+//! with a nullable column of the matching type for each, with the `parquet`
+//! feature.
+
+use self::ColumnType::{Boolean, Date, DateTime, Decimal, Integer, Text};
+use super::writer::DATE_TIME_FORMAT;
+use super::*;
+
+#[cfg(feature = "parquet")]
+use std::convert::TryFrom;
+#[cfg(any(feature = "csv", feature = "parquet"))]
+use std::fs::{self, File};
+#[cfg(any(feature = "csv", feature = "parquet"))]
+use std::path::Path;
+
+/// What the text in a column holds
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnType {
+    Text,
+    Integer,
+    /// An `xs:decimal`, kept exact
+    Decimal,
+    Boolean,
+    Date,
+    DateTime,
+}
+
+/// A single table, with a value or null for every column of every row
+#[derive(Clone, Debug)]
+pub struct Table {
+    pub name: &'static str,
+    pub columns: &'static [(&'static str, ColumnType)],
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+impl Table {
+    fn new(name: &'static str, columns: &'static [(&'static str, ColumnType)]) -> Table {
+        Table {
+            name,
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, row: Vec<Option<String>>) {
+        debug_assert_eq!(row.len(), self.columns.len(), "{} row", self.name);
+        self.rows.push(row);
+    }
+}
+
+const CASES: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("patient_id", Text),
+    ("dob", Date),
+    ("home_zip", Text),
+    ("home_state", Text),
+    ("home_city", Text),
+    ("race", Text),
+    ("patient_sex", Text),
+    ("procedure_id", Text),
+    ("facility_id", Text),
+    ("location_type", Integer),
+    ("location_details", Text),
+    ("proc_start_time", DateTime),
+    ("proc_end_time", DateTime),
+    ("admission_status", Text),
+    ("proc_status", Text),
+    ("transfer_status", Text),
+    ("admission_date", DateTime),
+    ("procedure_notes", Text),
+    ("anesthesia_coverage", Text),
+    ("monitors", Text),
+    ("airway_management_methods", Text),
+    ("age", Integer),
+    ("weight", Decimal),
+    ("weight_in_kg", Decimal),
+    ("height", Decimal),
+    ("height_in_cm", Decimal),
+    ("asa_class", Text),
+    ("pre_anesth_status", Text),
+    ("post_op_disposition", Text),
+    ("post_op_disp_date_time", DateTime),
+    ("post_op_discharge", Text),
+    ("post_op_discharge_date_time", DateTime),
+    ("length_of_hospital_stay", Integer),
+];
+
+const STAFF: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("index", Integer),
+    ("tax_id", Text),
+    ("npi", Text),
+    ("staff_responsibility", Text),
+    ("provider_credentials", Text),
+    ("staff_sign_in", DateTime),
+    ("staff_sign_out", DateTime),
+    ("staff_notes", Text),
+];
+
+const METHODS: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("index", Integer),
+    ("anesthesia_category", Text),
+    ("anesthesia_subcategory", Text),
+    ("anesthesia_start_time", DateTime),
+    ("anesthesia_end_time", DateTime),
+    ("anesthesia_induction", Text),
+    ("anesthesia_induction_start_time", DateTime),
+    ("anesthesia_maintenance", Text),
+    ("anesthesia_notes", Text),
+];
+
+const CPT: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("set", Text),
+    ("index", Integer),
+    ("rank", Text),
+    ("value", Text),
+    ("modifier", Text),
+    ("description", Text),
+];
+
+const ICD: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("set", Text),
+    ("index", Integer),
+    ("rank", Text),
+    ("value", Text),
+    ("version", Text),
+];
+
+const PAYMENT_METHODS: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("index", Integer),
+    ("payment_code", Text),
+    ("payment_description", Text),
+    ("payment_description_sec", Text),
+    ("payment_description_th", Text),
+];
+
+const MEDICATIONS: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("set", Text),
+    ("index", Integer),
+    ("medication_name", Text),
+    ("medication_type", Text),
+    ("med_dose", Decimal),
+    ("dose_units", Text),
+    ("dose_start", DateTime),
+    ("dose_end", DateTime),
+    ("med_concentration", Decimal),
+    ("med_concentration_unit", Text),
+    ("medication_route", Text),
+];
+
+const MONITORING: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("index", Integer),
+    ("monitoring_name", Text),
+    ("monitoring_time", DateTime),
+    ("monitoring_units", Text),
+    ("monitoring_value_numeric", Decimal),
+    ("monitoring_value_text", Text),
+    ("monitoring_source", Text),
+];
+
+const OUTPUTS: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("index", Integer),
+    ("output_event_name", Text),
+    ("output_units", Text),
+    ("output_value_numeric", Decimal),
+    ("output_value_text", Text),
+    ("output_start_date_time", DateTime),
+    ("output_end_date_time", DateTime),
+];
+
+const LABS: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("set", Text),
+    ("index", Integer),
+    ("lab_name", Text),
+    ("lab_category_name", Text),
+    ("lab_unit", Text),
+    ("lab_value", Text),
+    ("lab_value_text", Text),
+    ("lab_range_high", Text),
+    ("lab_range_low", Text),
+    ("lab_date_time", DateTime),
+    ("lab_comments", Text),
+];
+
+const MILESTONES: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("index", Integer),
+    ("tm_type", Text),
+    ("tm_start_time", DateTime),
+    ("tm_end_time", DateTime),
+];
+
+const IC_EVENTS: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("index", Integer),
+    ("ic_event_time_date", DateTime),
+    ("ic_category", Text),
+    ("ic_severity", Text),
+    ("ic_name", Text),
+    ("ic_description", Text),
+    ("ic_value", Text),
+    ("ic_notes", Text),
+];
+
+const OUTCOMES: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("index", Integer),
+    ("outcome_id", Integer),
+    ("outcome_occurred", Boolean),
+    ("outcome_time_stamp", DateTime),
+    ("outcome_severity", Text),
+    ("outcome_time_frame", Text),
+];
+
+const QCDR: &[(&str, ColumnType)] = &[
+    ("anesthesia_record_id", Text),
+    ("index", Integer),
+    ("qcdr_measure", Text),
+    ("qcdr_code_value", Text),
+    ("qcdr_modifier", Text),
+];
+
+fn text<T: SchemaStringType>(value: &T) -> String {
+    value.value().to_string()
+}
+
+fn code<T: SchemaIntType>(value: &T) -> String {
+    value.value().to_string()
+}
+
+fn date(value: &NaiveDate) -> String {
+    value.format("%F").to_string()
+}
+
+fn date_time(value: &NaiveDateTime) -> String {
+    value.format(DATE_TIME_FORMAT).to_string()
+}
+
+fn texts<T: SchemaStringType>(values: &[T]) -> Option<String> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().map(text).collect::<Vec<_>>().join(";"))
+    }
+}
+
+/// Every table, in a fixed order, including empty ones
+pub fn tables(records: &AnesthesiaRecordsType) -> Vec<Table> {
+    record_tables(&records.anesthesia_records)
+}
+
+/// Every table for `records`, in the same order as `tables`
+pub fn record_tables(records: &[AnesthesiaRecordType]) -> Vec<Table> {
+    let mut cases = Table::new("cases", CASES);
+    let mut staff = Table::new("staff", STAFF);
+    let mut methods = Table::new("methods", METHODS);
+    let mut cpt = Table::new("cpt", CPT);
+    let mut icd = Table::new("icd", ICD);
+    let mut payment_methods = Table::new("payment_methods", PAYMENT_METHODS);
+    let mut medications = Table::new("medications", MEDICATIONS);
+    let mut monitoring = Table::new("monitoring", MONITORING);
+    let mut outputs = Table::new("outputs", OUTPUTS);
+    let mut labs = Table::new("labs", LABS);
+    let mut milestones = Table::new("milestones", MILESTONES);
+    let mut ic_events = Table::new("ic_events", IC_EVENTS);
+    let mut outcomes = Table::new("outcomes", OUTCOMES);
+    let mut qcdr = Table::new("qcdr", QCDR);
+
+    for record in records {
+        let id = Some(record.anesthesia_case.anesthesia_record_id.clone());
+        let demographic = &record.demographic;
+        let procedure = &record.procedure;
+        let case = &record.anesthesia_case;
+        let pre_op = &record.pre_op;
+        let post_op = &record.post_op;
+
+        cases.push(vec![
+            id.clone(),
+            demographic.patient_id.clone(),
+            demographic.dob.as_ref().map(date),
+            demographic.home_zip.as_ref().map(text),
+            demographic.home_state.as_ref().map(text),
+            demographic.home_city.clone(),
+            demographic.race.as_ref().map(text),
+            Some(text(&demographic.patient_sex)),
+            procedure.procedure_id.clone(),
+            Some(procedure.facility_id.clone()),
+            procedure
+                .procedure_location
+                .as_ref()
+                .map(|location| code(&location.location_type)),
+            procedure
+                .procedure_location
+                .as_ref()
+                .map(|location| location.location_details.clone()),
+            procedure.proc_start_time.as_ref().map(date_time),
+            procedure.proc_end_time.as_ref().map(date_time),
+            procedure.admission_status.as_ref().map(text),
+            Some(text(&procedure.proc_status)),
+            procedure.transfer_status.as_ref().map(text),
+            procedure.admission_date.as_ref().map(date_time),
+            procedure.procedure_notes.clone(),
+            case.anesthesia_coverage.as_ref().map(text),
+            case.monitoring_set
+                .as_ref()
+                .and_then(|set| texts(&set.monitor)),
+            case.airway_management_set.as_ref().and_then(|set| {
+                let methods: Vec<_> = set
+                    .airway_management
+                    .iter()
+                    .map(|airway| airway.airway_management_method.clone())
+                    .collect();
+                texts(&methods)
+            }),
+            Some(pre_op.age.to_string()),
+            pre_op.weight.as_ref().map(text),
+            pre_op.weight_in_kg.as_ref().map(text),
+            pre_op.height.as_ref().map(text),
+            pre_op.height_in_cm.as_ref().map(text),
+            Some(text(&pre_op.asa_class)),
+            pre_op.pre_anesth_status.as_ref().map(text),
+            post_op.post_op_disposition.as_ref().map(text),
+            post_op.post_op_disp_date_time.as_ref().map(date_time),
+            post_op.post_op_discharge.as_ref().map(text),
+            post_op.post_op_discharge_date_time.as_ref().map(date_time),
+            post_op
+                .length_of_hospital_stay
+                .map(|length| length.to_string()),
+        ]);
+
+        for (i, member) in case
+            .anesthesia_staff_set
+            .anesthesia_staff
+            .iter()
+            .enumerate()
+        {
+            staff.push(vec![
+                id.clone(),
+                Some(i.to_string()),
+                Some(text(&member.tax_id)),
+                Some(text(&member.npi)),
+                member.staff_responsibility.as_ref().map(text),
+                Some(text(&member.provider_credentials)),
+                member.staff_sign_in.as_ref().map(date_time),
+                member.staff_sign_out.as_ref().map(date_time),
+                member.staff_notes.clone(),
+            ]);
+        }
+
+        for (i, method) in case
+            .anesthesia_method_set
+            .anesthesia_method
+            .iter()
+            .enumerate()
+        {
+            methods.push(vec![
+                id.clone(),
+                Some(i.to_string()),
+                Some(text(&method.anesthesia_category)),
+                method.anesthesia_subcategory.as_ref().map(text),
+                Some(date_time(&method.anesthesia_start_time)),
+                Some(date_time(&method.anesthesia_end_time)),
+                method.anesthesia_induction.as_ref().map(text),
+                method
+                    .anesthesia_induction_start_time
+                    .as_ref()
+                    .map(date_time),
+                method.anesthesia_maintenance.as_ref().map(text),
+                method.anesthesia_notes.clone(),
+            ]);
+        }
+
+        if let Some(ref set) = procedure.cpt_set {
+            for (i, value) in set.cpt.iter().enumerate() {
+                cpt.push(vec![
+                    id.clone(),
+                    Some("Procedure".to_string()),
+                    Some(i.to_string()),
+                    value.cpt_rank.clone(),
+                    Some(text(&value.cpt_value)),
+                    value.cpt_modifier.as_ref().map(text),
+                    None,
+                ]);
+            }
+        }
+        if let Some(ref set) = case.cpt_anes_set {
+            for (i, value) in set.cpt_anes.iter().enumerate() {
+                cpt.push(vec![
+                    id.clone(),
+                    Some("AnesthesiaCase".to_string()),
+                    Some(i.to_string()),
+                    None,
+                    Some(text(&value.cpt_anes_value)),
+                    value.cpt_anes_modifier.as_ref().map(text),
+                    value.cpt_anes_description.clone(),
+                ]);
+            }
+        }
+
+        for (set_name, set) in [("PreOp", &pre_op.icd_set), ("PostOp", &post_op.icd_set)].iter() {
+            if let Some(ref set) = **set {
+                for (i, value) in set.icd.iter().enumerate() {
+                    icd.push(vec![
+                        id.clone(),
+                        Some(set_name.to_string()),
+                        Some(i.to_string()),
+                        value.icd_rank.map(|rank| rank.to_string()),
+                        Some(text(&value.icd_value)),
+                        Some(text(&value.icd_version)),
+                    ]);
+                }
+            }
+        }
+
+        for (i, payment) in post_op.payment_method.iter().enumerate() {
+            payment_methods.push(vec![
+                id.clone(),
+                Some(i.to_string()),
+                Some(text(&payment.payment_code)),
+                payment.payment_description.as_ref().map(text),
+                payment.payment_description_sec.as_ref().map(text),
+                payment.payment_description_th.as_ref().map(text),
+            ]);
+        }
+
+        let intra_op = &record.intra_op;
+        let totals = record
+            .anesthesia_details
+            .as_ref()
+            .and_then(|details| details.medications_total_set.as_ref())
+            .map(|set| &set.medications_totals);
+        let intra_op_medications = intra_op.medications_set.as_ref().map(|set| &set.medication);
+        for (set_name, set) in [
+            ("IntraOp", intra_op_medications),
+            ("AnesthesiaDetails", totals),
+        ]
+        .iter()
+        {
+            for (i, medication) in set.iter().flat_map(|set| set.iter()).enumerate() {
+                medications.push(vec![
+                    id.clone(),
+                    Some(set_name.to_string()),
+                    Some(i.to_string()),
+                    Some(medication.medication_name.clone()),
+                    medication
+                        .medication_type
+                        .as_ref()
+                        .and_then(|types| texts(types)),
+                    medication.med_dose.as_ref().map(text),
+                    medication.dose_units.as_ref().map(text),
+                    medication.dose_start.as_ref().map(date_time),
+                    medication.dose_end.as_ref().map(date_time),
+                    medication.med_concentration.as_ref().map(text),
+                    medication.med_concentration_unit.as_ref().map(text),
+                    medication
+                        .medication_route
+                        .as_ref()
+                        .and_then(|routes| texts(routes)),
+                ]);
+            }
+        }
+
+        if let Some(ref set) = intra_op.monitoring_physiologic_set {
+            for (i, value) in set.monitoring.iter().enumerate() {
+                monitoring.push(vec![
+                    id.clone(),
+                    Some(i.to_string()),
+                    Some(text(&value.monitoring_name)),
+                    value.monitoring_time.as_ref().map(date_time),
+                    value.monitoring_units.as_ref().map(text),
+                    value.monitoring_value_numeric.as_ref().map(text),
+                    value.monitoring_value_text.clone(),
+                    value.monitoring_source.as_ref().map(text),
+                ]);
+            }
+        }
+
+        if let Some(ref set) = intra_op.outputs_set {
+            for (i, output) in set.output_event.iter().enumerate() {
+                outputs.push(vec![
+                    id.clone(),
+                    Some(i.to_string()),
+                    Some(text(&output.output_event_name)),
+                    output.output_units.as_ref().map(text),
+                    output.output_value_numeric.as_ref().map(text),
+                    output.output_value_text.clone(),
+                    output.output_start_date_time.as_ref().map(date_time),
+                    output.output_end_date_time.as_ref().map(date_time),
+                ]);
+            }
+        }
+
+        let pre_op_labs = pre_op.pre_lab_set.as_ref().map(|set| &set.pre_lab_data);
+        let post_op_labs = post_op
+            .post_op_lab_set
+            .as_ref()
+            .map(|set| &set.post_lab_data);
+        for (set_name, set) in [("PreOp", pre_op_labs), ("PostOp", post_op_labs)].iter() {
+            for (i, lab) in set.iter().flat_map(|set| set.iter()).enumerate() {
+                labs.push(vec![
+                    id.clone(),
+                    Some(set_name.to_string()),
+                    Some(i.to_string()),
+                    Some(text(&lab.lab_name)),
+                    lab.lab_category_name.as_ref().map(text),
+                    Some(text(&lab.lab_unit)),
+                    Some(lab.lab_value.clone()),
+                    lab.lab_value_text.clone(),
+                    lab.lab_range_high.clone(),
+                    lab.lab_range_low.clone(),
+                    Some(date_time(&lab.lab_date_time)),
+                    lab.lab_comments.clone(),
+                ]);
+            }
+        }
+
+        if let Some(ref set) = record.timing_milestones {
+            for (i, milestone) in set.timing_milestone.iter().enumerate() {
+                milestones.push(vec![
+                    id.clone(),
+                    Some(i.to_string()),
+                    Some(text(&milestone.tm_type)),
+                    Some(date_time(&milestone.tm_start_time)),
+                    milestone.tm_end_time.as_ref().map(date_time),
+                ]);
+            }
+        }
+
+        if let Some(ref outcomes_events) = record.outcomes_events {
+            if let Some(ref set) = outcomes_events.ic_event_set {
+                for (i, event) in set.ic_event.iter().enumerate() {
+                    ic_events.push(vec![
+                        id.clone(),
+                        Some(i.to_string()),
+                        event.ic_event_time_date.as_ref().map(date_time),
+                        event.ic_category.as_ref().map(text),
+                        event.ic_severity.clone(),
+                        event.ic_name.clone(),
+                        event.ic_description.clone(),
+                        event.ic_value.clone(),
+                        event.ic_notes.clone(),
+                    ]);
+                }
+            }
+
+            if let Some(ref set) = outcomes_events.outcome_set {
+                for (i, outcome) in set.outcome.iter().enumerate() {
+                    outcomes.push(vec![
+                        id.clone(),
+                        Some(i.to_string()),
+                        Some(code(&outcome.outcome_id)),
+                        Some(outcome.outcome_occurred.to_string()),
+                        outcome.outcome_time_stamp.as_ref().map(date_time),
+                        outcome.outcome_severity.as_ref().map(text),
+                        outcome.outcome_time_frame.as_ref().map(text),
+                    ]);
+                }
+            }
+
+            if let Some(ref set) = outcomes_events.qcdr_set {
+                for (i, value) in set.qcdr.iter().enumerate() {
+                    qcdr.push(vec![
+                        id.clone(),
+                        Some(i.to_string()),
+                        Some(text(&value.qcdr_measure)),
+                        Some(text(&value.qcdr_code_value)),
+                        value.qcdr_modifier.as_ref().map(text),
+                    ]);
+                }
+            }
+        }
+    }
+
+    vec![
+        cases,
+        staff,
+        methods,
+        cpt,
+        icd,
+        payment_methods,
+        medications,
+        monitoring,
+        outputs,
+        labs,
+        milestones,
+        ic_events,
+        outcomes,
+        qcdr,
+    ]
+}
+
+#[cfg(feature = "csv")]
+impl Table {
+    /// Writes the table as CSV with a header row, nulls as empty fields
+    pub fn write_csv<W: std::io::Write>(&self, writer: W) -> Result<(), AQIError> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer
+            .write_record(self.columns.iter().map(|&(name, _)| name))
+            .map_err(csv_error)?;
+        for row in &self.rows {
+            writer
+                .write_record(row.iter().map(|value| value.as_deref().unwrap_or("")))
+                .map_err(csv_error)?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "csv")]
+fn csv_error(err: csv::Error) -> AQIError {
+    match err.into_kind() {
+        csv::ErrorKind::Io(err) => AQIError::from(err),
+        kind => AQIError::parse(format!("CSV error: {:?}", kind)),
+    }
+}
+
+/// Writes each table to `<name>.csv` in `directory`, creating it if needed
+#[cfg(feature = "csv")]
+pub fn write_csv<P: AsRef<Path>>(tables: &[Table], directory: P) -> Result<(), AQIError> {
+    let directory = directory.as_ref();
+    fs::create_dir_all(directory)?;
+
+    for table in tables {
+        let path = directory.join(format!("{}.csv", table.name));
+        table
+            .write_csv(File::create(path)?)
+            .map_err(|err| err.in_element(table.name))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+impl Table {
+    /// Writes the table as Parquet, with a nullable column for each of
+    /// `columns`: `Utf8`, `Int64`, `Decimal128` with the largest scale in the
+    /// column, `Boolean`, `Date32`, or a `Timestamp` in milliseconds without
+    /// a time zone
+    pub fn write_parquet<W: std::io::Write + Send>(&self, writer: W) -> Result<(), AQIError> {
+        use std::sync::Arc;
+
+        use arrow_array::{
+            ArrayRef, BooleanArray, Date32Array, Decimal128Array, Int64Array, RecordBatch,
+            StringArray, TimestampMillisecondArray,
+        };
+        use arrow_schema::{Field, Schema};
+        use parquet::arrow::ArrowWriter;
+
+        let parquet_error =
+            |err: parquet::errors::ParquetError| AQIError::parse(format!("Parquet error: {}", err));
+        let arrow_error =
+            |err: arrow_schema::ArrowError| AQIError::parse(format!("Arrow error: {}", err));
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+
+        let mut fields = Vec::new();
+        let mut arrays = Vec::new();
+        for (i, &(name, column_type)) in self.columns.iter().enumerate() {
+            let column = self.rows.iter().map(|row| row[i].as_deref());
+            let array: ArrayRef = match column_type {
+                Text => Arc::new(column.collect::<StringArray>()),
+                Integer => Arc::new(Int64Array::from(parse_column(name, column, |value| {
+                    value.parse().ok()
+                })?)),
+                Decimal => {
+                    let values = parse_column(name, column, decimal_parts)?;
+                    let scale = values.iter().flatten().map(|&(_, scale)| scale).max();
+                    let scale = scale.unwrap_or(0);
+                    let too_long = || {
+                        AQIError::parse(format!(
+                            "{} has more than {} digits",
+                            name, DECIMAL_PRECISION
+                        ))
+                    };
+                    if scale > DECIMAL_PRECISION {
+                        return Err(too_long());
+                    }
+                    let values = values
+                        .into_iter()
+                        .map(|value| match value {
+                            Some((unscaled, from)) => rescale(unscaled, from, scale)
+                                .map(Some)
+                                .ok_or_else(too_long),
+                            None => Ok(None),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Arc::new(
+                        Decimal128Array::from(values)
+                            .with_precision_and_scale(DECIMAL_PRECISION as u8, scale as i8)
+                            .map_err(arrow_error)?,
+                    )
+                }
+                Boolean => Arc::new(BooleanArray::from(parse_column(name, column, |value| {
+                    value.parse().ok()
+                })?)),
+                Date => Arc::new(Date32Array::from(parse_column(name, column, |value| {
+                    let date = NaiveDate::parse_from_str(value, "%F").ok()?;
+                    i32::try_from((date - epoch).num_days()).ok()
+                })?)),
+                DateTime => Arc::new(TimestampMillisecondArray::from(parse_column(
+                    name,
+                    column,
+                    |value| {
+                        NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT)
+                            .ok()
+                            .map(|time| time.and_utc().timestamp_millis())
+                    },
+                )?)),
+            };
+            fields.push(Field::new(name, array.data_type().clone(), true));
+            arrays.push(array);
+        }
+
+        let schema = Arc::new(Schema::new(fields));
+        let batch = RecordBatch::try_new(schema.clone(), arrays).map_err(arrow_error)?;
+
+        let mut writer = ArrowWriter::try_new(writer, schema, None).map_err(parquet_error)?;
+        writer.write(&batch).map_err(parquet_error)?;
+        writer.close().map_err(parquet_error)?;
+
+        Ok(())
+    }
+}
+
+/// The most digits a `Decimal128` holds
+#[cfg(feature = "parquet")]
+const DECIMAL_PRECISION: u32 = 38;
+
+/// Parses every value in a column, failing on the first that `parse` rejects
+#[cfg(feature = "parquet")]
+fn parse_column<'a, T>(
+    name: &str,
+    column: impl Iterator<Item = Option<&'a str>>,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Vec<Option<T>>, AQIError> {
+    column
+        .map(|value| {
+            value
+                .map(|value| {
+                    parse(value).ok_or_else(|| {
+                        AQIError::parse(format!("Invalid {} value: {}", name, value))
+                    })
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+/// The digits of a decimal as an integer, and how many are after the point
+#[cfg(feature = "parquet")]
+fn decimal_parts(value: &str) -> Option<(i128, u32)> {
+    let (negative, digits) = match value.as_bytes().first() {
+        Some(b'-') => (true, &value[1..]),
+        Some(b'+') => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let (integer, fraction) = match digits.find('.') {
+        Some(point) => (&digits[..point], &digits[point + 1..]),
+        None => (digits, ""),
+    };
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+
+    let mut unscaled: i128 = 0;
+    for digit in integer.bytes().chain(fraction.bytes()) {
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+        unscaled = unscaled
+            .checked_mul(10)?
+            .checked_add(i128::from(digit - b'0'))?;
+    }
+
+    Some((
+        if negative { -unscaled } else { unscaled },
+        u32::try_from(fraction.len()).ok()?,
+    ))
+}
+
+/// `unscaled` with `from` digits after the point, as one with `to` digits,
+/// if it fits in `DECIMAL_PRECISION` digits
+#[cfg(feature = "parquet")]
+fn rescale(unscaled: i128, from: u32, to: u32) -> Option<i128> {
+    let rescaled = unscaled.checked_mul(10i128.checked_pow(to - from)?)?;
+
+    if rescaled.unsigned_abs() < 10u128.pow(DECIMAL_PRECISION) {
+        Some(rescaled)
+    } else {
+        None
+    }
+}
+
+/// Writes each table to `<name>.parquet` in `directory`, creating it if needed
+#[cfg(feature = "parquet")]
+pub fn write_parquet<P: AsRef<Path>>(tables: &[Table], directory: P) -> Result<(), AQIError> {
+    let directory = directory.as_ref();
+    fs::create_dir_all(directory)?;
+
+    for table in tables {
+        let path = directory.join(format!("{}.parquet", table.name));
+        table
+            .write_parquet(File::create(path)?)
+            .map_err(|err| err.in_element(table.name))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table<'a>(tables: &'a [Table], name: &str) -> &'a Table {
+        tables.iter().find(|table| table.name == name).unwrap()
+    }
+
+    fn column(table: &Table, name: &str) -> Vec<Option<String>> {
+        let i = table
+            .columns
+            .iter()
+            .position(|&(column, _)| column == name)
+            .unwrap();
+        table.rows.iter().map(|row| row[i].clone()).collect()
+    }
+
+    fn some(values: &[&str]) -> Vec<Option<String>> {
+        values.iter().map(|value| Some(value.to_string())).collect()
+    }
+
+    #[test]
+    fn flattens_records_into_tables() {
+        let tables = tables(&submission());
+
+        let cases = table(&tables, "cases");
+        assert_eq!(column(cases, "anesthesia_record_id"), some(&["R1", "R2"]));
+        assert_eq!(column(cases, "dob"), some(&["1930-05-06", "1930-05-06"]));
+        assert_eq!(
+            column(cases, "proc_start_time"),
+            some(&["2020-03-04T08:00:00.000", "2020-06-10T13:00:00.000"])
+        );
+        assert_eq!(
+            column(cases, "home_city"),
+            vec![Some("Milwaukee".to_string()), None]
+        );
+
+        let payment_methods = table(&tables, "payment_methods");
+        assert_eq!(
+            column(payment_methods, "anesthesia_record_id"),
+            some(&["R1", "R1", "R2"])
+        );
+        assert_eq!(column(payment_methods, "index"), some(&["0", "1", "0"]));
+        assert_eq!(
+            column(payment_methods, "payment_code"),
+            some(&["Commercial", "Government: Medicaid", "Government: Medicaid"])
+        );
+
+        let labs = table(&tables, "labs");
+        assert_eq!(column(labs, "set"), some(&["PreOp"]));
+        assert_eq!(column(labs, "lab_value"), some(&["13.1"]));
+
+        assert!(table(&tables, "qcdr").rows.is_empty());
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn writes_csv_with_a_header() {
+        let tables = tables(&submission());
+        let mut csv = Vec::new();
+        table(&tables, "payment_methods")
+            .write_csv(&mut csv)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "anesthesia_record_id,index,payment_code,payment_description,\
+             payment_description_sec,payment_description_th\n\
+             R1,0,Commercial,,,\n\
+             R1,1,Government: Medicaid,,,\n\
+             R2,0,Government: Medicaid,,,\n"
+        );
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn writes_parquet_with_typed_columns() {
+        use arrow_array::cast::AsArray;
+        use arrow_array::types::{Date32Type, Decimal128Type, Int64Type, TimestampMillisecondType};
+        use arrow_schema::{DataType, TimeUnit};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let mut records = submission();
+        records.anesthesia_records[1].pre_op.weight =
+            Some(DecimalType::from_str("-0.125").unwrap());
+        let tables = tables(&records);
+        let mut parquet = Vec::new();
+        table(&tables, "cases").write_parquet(&mut parquet).unwrap();
+
+        let batch = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(parquet))
+            .unwrap()
+            .build()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let column = |name: &str| batch.column(batch.schema().index_of(name).unwrap()).clone();
+
+        assert_eq!(column("anesthesia_record_id").data_type(), &DataType::Utf8);
+        assert_eq!(column("age").as_primitive::<Int64Type>().value(0), 89);
+        assert_eq!(
+            column("dob").as_primitive::<Date32Type>().value(0),
+            (NaiveDate::from_ymd_opt(1930, 5, 6).unwrap()
+                - NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())
+            .num_days() as i32
+        );
+        assert_eq!(
+            column("proc_start_time").data_type(),
+            &DataType::Timestamp(TimeUnit::Millisecond, None)
+        );
+        assert_eq!(
+            column("proc_start_time")
+                .as_primitive::<TimestampMillisecondType>()
+                .value(0),
+            NaiveDate::from_ymd_opt(2020, 3, 4)
+                .unwrap()
+                .and_hms_opt(8, 0, 0)
+                .unwrap()
+                .and_utc()
+                .timestamp_millis()
+        );
+
+        let weight = column("weight");
+        assert_eq!(weight.data_type(), &DataType::Decimal128(38, 3));
+        let weight = weight.as_primitive::<Decimal128Type>();
+        assert_eq!(weight.value_as_string(1), "-0.125");
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn rescales_decimals_exactly() {
+        assert_eq!(decimal_parts("+12.50"), Some((1250, 2)));
+        assert_eq!(decimal_parts(".5"), Some((5, 1)));
+        assert_eq!(decimal_parts("7."), Some((7, 0)));
+        assert_eq!(decimal_parts("."), None);
+        assert_eq!(rescale(5, 1, 3), Some(500));
+        assert_eq!(rescale(1, 0, 38), None);
+    }
+}
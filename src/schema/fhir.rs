@@ -0,0 +1,2064 @@
+//! # FHIR
+//! Converts records to and from FHIR R4 Bundles
+//!
+//! Each record becomes a `collection` Bundle identified by its
+//! AnesthesiaRecordID, containing
+//!
+//! - a Patient for the Demographic
+//! - a Procedure for the Procedure and its CPT codes
+//! - a MedicationAdministration for each intra-op Medication
+//! - an Observation for each intra-op Monitoring and each pre- and post-op lab
+//! - a Practitioner and a PractitionerRole for each AnesthesiaStaff
+//! - an AdverseEvent for each Outcome, `actual` if it occurred and
+//!   `potential` if not
+//!
+//! Schema codes are written as codings in `urn:aqi-nacor:<Element>` systems,
+//! apart from CPT codes and NPIs which have their own, and elements with no
+//! place in their resource, such as ProcStatus, become extensions with
+//! `urn:aqi-nacor:<Element>` URLs. Everything else in the record, like the
+//! ASA class or the anesthesia methods, is reported as `Unmapped` rather than
+//! converted. Reading a Bundle reports any part of it that wasn't understood
+//! the same way, and fills in builders for the sections it has, as a Bundle
+//! can't hold everything a record requires.
+//!
+//! The Procedure is `completed` unless its ProcStatus is `Unknown`, which
+//! makes it `unknown`, and quantities keep their decimals' digits as written,
+//! as the `fhir` feature turns on serde_json's `arbitrary_precision`.
+//!
+//! Record times don't have a time zone, so both directions take the offset
+//! they're in.
+
+use super::builder::*;
+use super::reader::{ReadableValue, DATE_FORMAT};
+use super::validate::child_path;
+use super::writer::DATE_TIME_FORMAT;
+use super::*;
+
+use chrono::{DateTime, FixedOffset};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+
+use std::collections::HashMap;
+
+/// Prefix of the code systems, identifier systems, and extension URLs for
+/// schema elements
+pub const NACOR_URN: &str = "urn:aqi-nacor:";
+
+const CPT_SYSTEM: &str = "http://www.ama-assn.org/go/cpt";
+const NPI_SYSTEM: &str = "http://hl7.org/fhir/sid/us-npi";
+const OBSERVATION_CATEGORY_SYSTEM: &str =
+    "http://terminology.hl7.org/CodeSystem/observation-category";
+
+const NO_MAPPING: &str = "no FHIR mapping";
+
+/// Part of a record or Bundle that wasn't converted, and why
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unmapped {
+    /// An element path for records, e.g. `PreOp/ASAClass`, or a JSON path
+    /// for Bundles, e.g. `Bundle.entry[2].resource.status`
+    pub path: String,
+    pub reason: String,
+}
+
+fn unmapped<S: Into<String>>(report: &mut Vec<Unmapped>, path: String, reason: S) {
+    report.push(Unmapped {
+        path,
+        reason: reason.into(),
+    });
+}
+
+/// A record converted to a Bundle
+#[derive(Clone, Debug)]
+pub struct Exported {
+    pub bundle: Value,
+    pub unmapped: Vec<Unmapped>,
+}
+
+/// The sections of a record read from a Bundle
+///
+/// Sections the Bundle has nothing for are left empty, to be filled in
+/// before building.
+#[derive(Clone, Debug, Default)]
+pub struct Imported {
+    pub demographic: DemographicBuilder,
+    pub procedure: ProcedureBuilder,
+    pub anesthesia_case: AnesthesiaCaseBuilder,
+    pub pre_op: PreOpBuilder,
+    pub intra_op: IntraOpBuilder,
+    pub post_op: PostOpBuilder,
+    pub outcomes_events: Option<OutcomesEventsBuilder>,
+    pub unmapped: Vec<Unmapped>,
+}
+
+impl Imported {
+    /// A builder for the whole record, with every section read from the Bundle
+    pub fn into_builder(self) -> AnesthesiaRecordBuilder {
+        let builder = AnesthesiaRecordBuilder::new()
+            .demographic(self.demographic)
+            .procedure(self.procedure)
+            .anesthesia_case(self.anesthesia_case)
+            .pre_op(self.pre_op)
+            .intra_op(self.intra_op)
+            .post_op(self.post_op);
+
+        match self.outcomes_events {
+            Some(outcomes_events) => builder.outcomes_events(outcomes_events),
+            None => builder,
+        }
+    }
+}
+
+fn urn(element: &str) -> String {
+    format!("{}{}", NACOR_URN, element)
+}
+
+fn coding(system: &str, code: &str) -> Value {
+    json!({ "system": system, "code": code })
+}
+
+fn concept<'a, I: IntoIterator<Item = &'a str>>(element: &str, codes: I) -> Value {
+    let system = urn(element);
+    let codings: Vec<Value> = codes
+        .into_iter()
+        .map(|code| coding(&system, code))
+        .collect();
+
+    json!({ "coding": codings })
+}
+
+fn observation_category(code: &str) -> Value {
+    json!({ "coding": [coding(OBSERVATION_CATEGORY_SYSTEM, code)] })
+}
+
+fn extension(element: &str, key: &str, value: Value) -> Value {
+    let mut extension = Map::new();
+    extension.insert("url".to_string(), Value::from(urn(element)));
+    extension.insert(key.to_string(), value);
+
+    Value::Object(extension)
+}
+
+fn set_extensions(resource: &mut Value, extensions: Vec<Value>) {
+    if !extensions.is_empty() {
+        resource["extension"] = Value::Array(extensions);
+    }
+}
+
+/// A JSON number with a decimal's digits as written, kept by serde_json's
+/// `arbitrary_precision`, or nothing if `value` isn't a decimal
+fn number(value: &str) -> Option<Value> {
+    let value = value.trim();
+    DecimalType::from_str(value).ok()?;
+
+    // JSON has no `+`, leading zeros, or points without digits on both sides
+    let (sign, digits) = match value.as_bytes()[0] {
+        b'-' => ("-", &value[1..]),
+        b'+' => ("", &value[1..]),
+        _ => ("", value),
+    };
+    let (integer, fraction) = match digits.find('.') {
+        Some(point) => (&digits[..point], &digits[point + 1..]),
+        None => (digits, ""),
+    };
+    let integer = integer.trim_start_matches('0');
+    let mut json = format!("{}{}", sign, if integer.is_empty() { "0" } else { integer });
+    if !fraction.is_empty() {
+        json.push('.');
+        json.push_str(fraction);
+    }
+
+    serde_json::from_str(&json).ok().map(Value::Number)
+}
+
+/// The event status of a Procedure, as every record is for a case that took
+/// place, unless its ProcStatus is unknown
+fn procedure_status(proc_status: &ProcStatusCodeType) -> &'static str {
+    match proc_status {
+        ProcStatusCodeType::Unknown => "unknown",
+        ProcStatusCodeType::Elective
+        | ProcStatusCodeType::Emergency
+        | ProcStatusCodeType::Urgent => "completed",
+    }
+}
+
+fn gender(patient_sex: PatientSexCodeType) -> Option<&'static str> {
+    match patient_sex {
+        PatientSexCodeType::Male => Some("male"),
+        PatientSexCodeType::Female => Some("female"),
+        PatientSexCodeType::Unknown => Some("unknown"),
+        PatientSexCodeType::Missing => None,
+    }
+}
+
+/// A stable UUID for a resource, so converting a record again gives the same Bundle
+fn resource_id(record_id: &str, resource_type: &str, path: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [record_id, resource_type, path].iter() {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    let mut bytes = hasher.finalize();
+
+    // Version 8, for custom UUIDs, and the RFC 4122 variant
+    bytes[6] = (bytes[6] & 0x0f) | 0x80;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes[..16]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Converts one record to a Bundle
+pub fn to_bundle(record: &AnesthesiaRecordType, offset: FixedOffset) -> Exported {
+    let record_id = &record.anesthesia_case.anesthesia_record_id;
+    let mut export = Export {
+        record_id,
+        offset,
+        entries: Vec::new(),
+        unmapped: Vec::new(),
+    };
+
+    let patient = export.patient(&record.demographic);
+    let procedure = export.procedure(&record.procedure, &patient);
+
+    for (i, staff) in record
+        .anesthesia_case
+        .anesthesia_staff_set
+        .anesthesia_staff
+        .iter()
+        .enumerate()
+    {
+        let path = format!("AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff[{}]", i);
+        export.staff(&path, staff);
+    }
+
+    let intra_op = &record.intra_op;
+    if let Some(ref set) = intra_op.medications_set {
+        for (i, medication) in set.medication.iter().enumerate() {
+            let path = format!("IntraOp/MedicationsSet/Medication[{}]", i);
+            export.medication(&path, medication, &patient, &procedure);
+        }
+    }
+    if let Some(ref set) = intra_op.monitoring_physiologic_set {
+        for (i, monitoring) in set.monitoring.iter().enumerate() {
+            let path = format!("IntraOp/MonitoringPhysiologicSet/Monitoring[{}]", i);
+            export.monitoring(&path, monitoring, &patient, &procedure);
+        }
+    }
+
+    if let Some(ref set) = record.pre_op.pre_lab_set {
+        for (i, lab) in set.pre_lab_data.iter().enumerate() {
+            let path = format!("PreOp/PreLabSet/PreLabData[{}]", i);
+            export.lab(&path, "PreOp", lab, &patient);
+        }
+    }
+    if let Some(ref set) = record.post_op.post_op_lab_set {
+        for (i, lab) in set.post_lab_data.iter().enumerate() {
+            let path = format!("PostOp/PostOpLabSet/PostLabData[{}]", i);
+            export.lab(&path, "PostOp", lab, &patient);
+        }
+    }
+
+    if let Some(set) = record
+        .outcomes_events
+        .as_ref()
+        .and_then(|outcomes_events| outcomes_events.outcome_set.as_ref())
+    {
+        for (i, outcome) in set.outcome.iter().enumerate() {
+            let path = format!("OutcomesEvents/OutcomeSet/Outcome[{}]", i);
+            export.outcome(&path, outcome, &patient);
+        }
+    }
+
+    export.unmapped_sections(record);
+
+    Exported {
+        bundle: json!({
+            "resourceType": "Bundle",
+            "type": "collection",
+            "identifier": { "system": urn("AnesthesiaRecordID"), "value": record_id },
+            "entry": export.entries,
+        }),
+        unmapped: export.unmapped,
+    }
+}
+
+/// The Bundle being written for one record
+struct Export<'a> {
+    record_id: &'a str,
+    offset: FixedOffset,
+    entries: Vec<Value>,
+    unmapped: Vec<Unmapped>,
+}
+
+impl<'a> Export<'a> {
+    /// Adds the resource for the element at `path`, returning a reference to it
+    fn add(&mut self, path: &str, mut resource: Value) -> Value {
+        let id = resource_id(
+            self.record_id,
+            resource["resourceType"].as_str().unwrap_or(""),
+            path,
+        );
+        let url = format!("urn:uuid:{}", id);
+        resource["id"] = Value::from(id);
+
+        self.entries
+            .push(json!({ "fullUrl": url, "resource": resource }));
+
+        json!({ "reference": url })
+    }
+
+    fn time(&self, value: &NaiveDateTime) -> Value {
+        Value::from(format!("{}{}", value.format(DATE_TIME_FORMAT), self.offset))
+    }
+
+    fn period(&self, start: Option<&NaiveDateTime>, end: Option<&NaiveDateTime>) -> Option<Value> {
+        let mut period = Map::new();
+        if let Some(start) = start {
+            period.insert("start".to_string(), self.time(start));
+        }
+        if let Some(end) = end {
+            period.insert("end".to_string(), self.time(end));
+        }
+
+        if period.is_empty() {
+            None
+        } else {
+            Some(Value::Object(period))
+        }
+    }
+
+    /// A Quantity, or nothing if `value` isn't a number
+    fn quantity(&mut self, path: &str, value: &str, unit: Option<&CommonUnit>) -> Option<Value> {
+        match number(value) {
+            Some(value) => {
+                let mut quantity = json!({ "value": value });
+                if let Some(unit) = unit {
+                    quantity["unit"] = Value::from(unit.value());
+                }
+                Some(quantity)
+            }
+            None => {
+                unmapped(&mut self.unmapped, path.to_string(), "not a number");
+                None
+            }
+        }
+    }
+
+    fn patient(&mut self, demographic: &DemographicType) -> Value {
+        let mut patient = json!({ "resourceType": "Patient" });
+
+        if let Some(ref patient_id) = demographic.patient_id {
+            patient["identifier"] = json!([{ "system": urn("PatientID"), "value": patient_id }]);
+        }
+        if let Some(gender) = gender(demographic.patient_sex) {
+            patient["gender"] = Value::from(gender);
+        }
+        if let Some(ref dob) = demographic.dob {
+            patient["birthDate"] = Value::from(dob.format(DATE_FORMAT).to_string());
+        }
+
+        let mut address = Map::new();
+        if let Some(ref home_city) = demographic.home_city {
+            address.insert("city".to_string(), Value::from(home_city.as_str()));
+        }
+        if let Some(ref home_state) = demographic.home_state {
+            address.insert("state".to_string(), Value::from(home_state.value()));
+        }
+        if let Some(ref home_zip) = demographic.home_zip {
+            address.insert("postalCode".to_string(), Value::from(home_zip.value()));
+        }
+        if !address.is_empty() {
+            patient["address"] = json!([address]);
+        }
+
+        let mut extensions = Vec::new();
+        if let Some(ref race) = demographic.race {
+            extensions.push(extension(
+                "Race",
+                "valueCoding",
+                coding(&urn("Race"), race.value()),
+            ));
+        }
+        set_extensions(&mut patient, extensions);
+
+        self.add("Demographic", patient)
+    }
+
+    fn procedure(&mut self, procedure: &ProcedureType, patient: &Value) -> Value {
+        let mut resource = json!({
+            "resourceType": "Procedure",
+            "status": procedure_status(&procedure.proc_status),
+            "subject": patient,
+            "location": {
+                "identifier": { "system": urn("FacilityID"), "value": procedure.facility_id },
+            },
+        });
+
+        if let Some(ref procedure_id) = procedure.procedure_id {
+            resource["identifier"] =
+                json!([{ "system": urn("ProcedureID"), "value": procedure_id }]);
+        }
+        if let Some(ref set) = procedure.cpt_set {
+            let codings: Vec<Value> = set
+                .cpt
+                .iter()
+                .map(|cpt| {
+                    let mut coding = coding(CPT_SYSTEM, cpt.cpt_value.value());
+                    let mut extensions = Vec::new();
+                    if let Some(ref cpt_rank) = cpt.cpt_rank {
+                        extensions.push(extension(
+                            "CPTRank",
+                            "valueString",
+                            Value::from(cpt_rank.as_str()),
+                        ));
+                    }
+                    if let Some(ref cpt_modifier) = cpt.cpt_modifier {
+                        extensions.push(extension(
+                            "CPTModifier",
+                            "valueCode",
+                            Value::from(cpt_modifier.value()),
+                        ));
+                    }
+                    set_extensions(&mut coding, extensions);
+                    coding
+                })
+                .collect();
+            resource["code"] = json!({ "coding": codings });
+        }
+        if let Some(period) = self.period(
+            procedure.proc_start_time.as_ref(),
+            procedure.proc_end_time.as_ref(),
+        ) {
+            resource["performedPeriod"] = period;
+        }
+        if let Some(ref procedure_notes) = procedure.procedure_notes {
+            resource["note"] = json!([{ "text": procedure_notes }]);
+        }
+
+        let mut extensions = vec![extension(
+            "ProcStatus",
+            "valueCode",
+            Value::from(procedure.proc_status.value()),
+        )];
+        if let Some(ref admission_status) = procedure.admission_status {
+            extensions.push(extension(
+                "AdmissionStatus",
+                "valueCode",
+                Value::from(admission_status.value()),
+            ));
+        }
+        if let Some(ref transfer_status) = procedure.transfer_status {
+            extensions.push(extension(
+                "TransferStatus",
+                "valueCode",
+                Value::from(transfer_status.value()),
+            ));
+        }
+        if let Some(ref admission_date) = procedure.admission_date {
+            extensions.push(extension(
+                "AdmissionDate",
+                "valueDateTime",
+                self.time(admission_date),
+            ));
+        }
+        if let Some(ref location) = procedure.procedure_location {
+            extensions.push(extension(
+                "LocationType",
+                "valueCode",
+                Value::from(location.location_type.value().to_string()),
+            ));
+            extensions.push(extension(
+                "LocationDetails",
+                "valueString",
+                Value::from(location.location_details.as_str()),
+            ));
+        }
+        set_extensions(&mut resource, extensions);
+
+        self.add("Procedure", resource)
+    }
+
+    fn staff(&mut self, path: &str, staff: &AnesthesiaStaffType) {
+        let practitioner = self.add(
+            path,
+            json!({
+                "resourceType": "Practitioner",
+                "identifier": [
+                    { "system": NPI_SYSTEM, "value": staff.npi.value() },
+                    { "system": urn("TaxID"), "value": staff.tax_id.value() },
+                ],
+                "qualification": [
+                    { "code": concept("ProviderCredentials", Some(staff.provider_credentials.value())) },
+                ],
+            }),
+        );
+
+        let mut role = json!({
+            "resourceType": "PractitionerRole",
+            "practitioner": practitioner,
+        });
+        if let Some(ref staff_responsibility) = staff.staff_responsibility {
+            role["code"] = json!([concept(
+                "StaffResponsibility",
+                Some(staff_responsibility.value())
+            )]);
+        }
+        if let Some(period) =
+            self.period(staff.staff_sign_in.as_ref(), staff.staff_sign_out.as_ref())
+        {
+            role["period"] = period;
+        }
+        if let Some(ref staff_notes) = staff.staff_notes {
+            set_extensions(
+                &mut role,
+                vec![extension(
+                    "StaffNotes",
+                    "valueString",
+                    Value::from(staff_notes.as_str()),
+                )],
+            );
+        }
+
+        self.add(path, role);
+    }
+
+    fn medication(
+        &mut self,
+        path: &str,
+        medication: &MedicationType,
+        patient: &Value,
+        procedure: &Value,
+    ) {
+        let mut resource = json!({
+            "resourceType": "MedicationAdministration",
+            "status": "completed",
+            "subject": patient,
+            "partOf": [procedure],
+            "medicationCodeableConcept": { "text": medication.medication_name },
+        });
+        let mut extensions = Vec::new();
+
+        if let Some(ref medication_type) = medication.medication_type {
+            resource["category"] = concept(
+                "MedicationType",
+                medication_type.iter().map(SchemaStringType::value),
+            );
+        }
+        if let Some(period) =
+            self.period(medication.dose_start.as_ref(), medication.dose_end.as_ref())
+        {
+            resource["effectivePeriod"] = period;
+        }
+
+        let mut dosage = Map::new();
+        match (&medication.med_dose, &medication.dose_units) {
+            (Some(med_dose), dose_units) => {
+                let path = child_path(path, "MedDose");
+                if let Some(dose) = self.quantity(&path, med_dose.value(), dose_units.as_ref()) {
+                    dosage.insert("dose".to_string(), dose);
+                }
+            }
+            (None, Some(dose_units)) => extensions.push(extension(
+                "DoseUnits",
+                "valueCode",
+                Value::from(dose_units.value()),
+            )),
+            (None, None) => {}
+        }
+        if let Some(ref medication_route) = medication.medication_route {
+            dosage.insert(
+                "route".to_string(),
+                concept(
+                    "MedicationRoute",
+                    medication_route.iter().map(SchemaStringType::value),
+                ),
+            );
+        }
+        if !dosage.is_empty() {
+            resource["dosage"] = Value::Object(dosage);
+        }
+
+        match (
+            &medication.med_concentration,
+            &medication.med_concentration_unit,
+        ) {
+            (Some(med_concentration), unit) => {
+                let path = child_path(path, "MedConcentration");
+                if let Some(quantity) =
+                    self.quantity(&path, med_concentration.value(), unit.as_ref())
+                {
+                    extensions.push(extension("MedConcentration", "valueQuantity", quantity));
+                }
+            }
+            (None, Some(unit)) => extensions.push(extension(
+                "MedConcentrationUnit",
+                "valueCode",
+                Value::from(unit.value()),
+            )),
+            (None, None) => {}
+        }
+        set_extensions(&mut resource, extensions);
+
+        if medication.mixture_medications.is_some() {
+            unmapped(
+                &mut self.unmapped,
+                child_path(path, "MixtureMedication"),
+                NO_MAPPING,
+            );
+        }
+
+        self.add(path, resource);
+    }
+
+    fn monitoring(
+        &mut self,
+        path: &str,
+        monitoring: &MonitoringPhysiologicType,
+        patient: &Value,
+        procedure: &Value,
+    ) {
+        let mut resource = json!({
+            "resourceType": "Observation",
+            "status": "final",
+            "subject": patient,
+            "partOf": [procedure],
+            "category": [observation_category("procedure")],
+            "code": concept("MonitoringName", Some(monitoring.monitoring_name.value())),
+        });
+        let mut extensions = Vec::new();
+
+        if let Some(ref monitoring_time) = monitoring.monitoring_time {
+            resource["effectiveDateTime"] = self.time(monitoring_time);
+        }
+
+        let mut has_quantity = false;
+        match (
+            &monitoring.monitoring_value_numeric,
+            &monitoring.monitoring_units,
+        ) {
+            (Some(numeric), units) => {
+                let path = child_path(path, "MonitoringValueNumeric");
+                if let Some(quantity) = self.quantity(&path, numeric.value(), units.as_ref()) {
+                    resource["valueQuantity"] = quantity;
+                    has_quantity = true;
+                }
+            }
+            (None, Some(units)) => extensions.push(extension(
+                "MonitoringUnits",
+                "valueCode",
+                Value::from(units.value()),
+            )),
+            (None, None) => {}
+        }
+        if let Some(ref text) = monitoring.monitoring_value_text {
+            if has_quantity {
+                extensions.push(extension(
+                    "MonitoringValueText",
+                    "valueString",
+                    Value::from(text.as_str()),
+                ));
+            } else {
+                resource["valueString"] = Value::from(text.as_str());
+            }
+        }
+        if let Some(ref monitoring_source) = monitoring.monitoring_source {
+            resource["method"] = concept("MonitoringSource", Some(monitoring_source.value()));
+        }
+        set_extensions(&mut resource, extensions);
+
+        self.add(path, resource);
+    }
+
+    fn lab(&mut self, path: &str, set: &str, lab: &LabDataType, patient: &Value) {
+        let mut categories = vec![observation_category("laboratory")];
+        if let Some(ref lab_category_name) = lab.lab_category_name {
+            categories.push(concept("LabCategoryName", Some(lab_category_name.value())));
+        }
+
+        let mut resource = json!({
+            "resourceType": "Observation",
+            "status": "final",
+            "subject": patient,
+            "category": categories,
+            "code": concept("LabName", Some(lab.lab_name.value())),
+            "effectiveDateTime": self.time(&lab.lab_date_time),
+        });
+        let mut extensions = vec![extension("LabSet", "valueCode", Value::from(set))];
+
+        match number(&lab.lab_value) {
+            Some(value) => {
+                resource["valueQuantity"] = json!({ "value": value, "unit": lab.lab_unit.value() });
+            }
+            None => {
+                resource["valueString"] = Value::from(lab.lab_value.as_str());
+                extensions.push(extension(
+                    "LabUnit",
+                    "valueCode",
+                    Value::from(lab.lab_unit.value()),
+                ));
+            }
+        }
+        if let Some(ref lab_value_text) = lab.lab_value_text {
+            extensions.push(extension(
+                "LabValueText",
+                "valueString",
+                Value::from(lab_value_text.as_str()),
+            ));
+        }
+
+        let mut range = Map::new();
+        for (key, element, value) in [
+            ("low", "LabRangeLow", &lab.lab_range_low),
+            ("high", "LabRangeHigh", &lab.lab_range_high),
+        ]
+        .iter()
+        {
+            if let Some(ref value) = **value {
+                let path = child_path(path, element);
+                if let Some(quantity) = self.quantity(&path, value, Some(&lab.lab_unit)) {
+                    range.insert(key.to_string(), quantity);
+                }
+            }
+        }
+        if !range.is_empty() {
+            resource["referenceRange"] = json!([range]);
+        }
+
+        if let Some(ref lab_comments) = lab.lab_comments {
+            resource["note"] = json!([{ "text": lab_comments }]);
+        }
+        set_extensions(&mut resource, extensions);
+
+        self.add(path, resource);
+    }
+
+    fn outcome(&mut self, path: &str, outcome: &OutcomeCodeType, patient: &Value) {
+        let outcome_id = outcome.outcome_id.value().to_string();
+        let mut resource = json!({
+            "resourceType": "AdverseEvent",
+            "actuality": if outcome.outcome_occurred { "actual" } else { "potential" },
+            "subject": patient,
+            "event": concept("OutcomeID", Some(outcome_id.as_str())),
+        });
+
+        if let Some(ref outcome_time_stamp) = outcome.outcome_time_stamp {
+            resource["date"] = self.time(outcome_time_stamp);
+        }
+        if let Some(ref outcome_severity) = outcome.outcome_severity {
+            resource["severity"] = concept("OutcomeSeverity", Some(outcome_severity.value()));
+        }
+        if let Some(ref outcome_time_frame) = outcome.outcome_time_frame {
+            set_extensions(
+                &mut resource,
+                vec![extension(
+                    "OutcomeTimeFrame",
+                    "valueCode",
+                    Value::from(outcome_time_frame.value()),
+                )],
+            );
+        }
+
+        self.add(path, resource);
+    }
+
+    /// Reports every element outside of the mapped resources
+    fn unmapped_sections(&mut self, record: &AnesthesiaRecordType) {
+        let case = &record.anesthesia_case;
+        let pre_op = &record.pre_op;
+        let post_op = &record.post_op;
+        let outcomes_events = record.outcomes_events.as_ref();
+
+        let sections = [
+            (
+                case.anesthesia_coverage.is_some(),
+                "AnesthesiaCase/AnesthesiaCoverage",
+            ),
+            (
+                case.monitoring_set.is_some(),
+                "AnesthesiaCase/MonitoringSet",
+            ),
+            (true, "AnesthesiaCase/AnesthesiaMethodSet"),
+            (
+                case.airway_management_set.is_some(),
+                "AnesthesiaCase/AirwayManagementSet",
+            ),
+            (case.cpt_anes_set.is_some(), "AnesthesiaCase/CPTAnesSet"),
+            (true, "PreOp/Age"),
+            (pre_op.weight.is_some(), "PreOp/Weight"),
+            (pre_op.weight_in_kg.is_some(), "PreOp/WeightInKg"),
+            (pre_op.height.is_some(), "PreOp/Height"),
+            (pre_op.height_in_cm.is_some(), "PreOp/HeightInCm"),
+            (true, "PreOp/ASAClass"),
+            (pre_op.pre_anesth_status.is_some(), "PreOp/PreAnesthStatus"),
+            (pre_op.icd_set.is_some(), "PreOp/ICDSet"),
+            (record.intra_op.outputs_set.is_some(), "IntraOp/OutputsSet"),
+            (
+                post_op.post_op_disposition.is_some(),
+                "PostOp/PostOpDisposition",
+            ),
+            (
+                post_op.post_op_disp_date_time.is_some(),
+                "PostOp/PostOpDispDateTime",
+            ),
+            (
+                post_op.post_op_discharge.is_some(),
+                "PostOp/PostOpDischarge",
+            ),
+            (
+                post_op.post_op_discharge_date_time.is_some(),
+                "PostOp/PostOpDischargeDateTime",
+            ),
+            (
+                post_op.length_of_hospital_stay.is_some(),
+                "PostOp/LengthOfHospitalStay",
+            ),
+            (!post_op.payment_method.is_empty(), "PostOp/PaymentMethod"),
+            (post_op.icd_set.is_some(), "PostOp/ICDSet"),
+            (record.timing_milestones.is_some(), "TimingMilestones"),
+            (
+                outcomes_events.is_some_and(|events| events.ic_event_set.is_some()),
+                "OutcomesEvents/ICEventSet",
+            ),
+            (
+                outcomes_events.is_some_and(|events| events.qcdr_set.is_some()),
+                "OutcomesEvents/QCDRSet",
+            ),
+            (record.anesthesia_details.is_some(), "AnesthesiaDetails"),
+        ];
+
+        for &(present, path) in sections.iter() {
+            if present {
+                unmapped(&mut self.unmapped, path.to_string(), NO_MAPPING);
+            }
+        }
+    }
+}
+
+/// Fields of any resource or element that carry nothing to convert
+const IGNORED: &[&str] = &["resourceType", "id", "meta", "text", "display"];
+
+/// A JSON object being read, which remembers the fields that were used
+/// so the rest can be reported
+struct Object<'a> {
+    path: String,
+    fields: &'a Map<String, Value>,
+    used: Vec<&'static str>,
+}
+
+impl<'a> Object<'a> {
+    fn new(path: String, value: &'a Value, report: &mut Vec<Unmapped>) -> Option<Object<'a>> {
+        match value.as_object() {
+            Some(fields) => Some(Object {
+                path,
+                fields,
+                used: Vec::new(),
+            }),
+            None => {
+                unmapped(report, path, "expected an object");
+                None
+            }
+        }
+    }
+
+    fn child_path(&self, key: &str) -> String {
+        format!("{}.{}", self.path, key)
+    }
+
+    fn get(&mut self, key: &'static str) -> Option<&'a Value> {
+        let value = self.fields.get(key)?;
+        self.used.push(key);
+
+        Some(value)
+    }
+
+    fn str(&mut self, key: &'static str, report: &mut Vec<Unmapped>) -> Option<&'a str> {
+        match self.get(key)? {
+            Value::String(value) => Some(value),
+            _ => {
+                unmapped(report, self.child_path(key), "expected a string");
+                None
+            }
+        }
+    }
+
+    fn object(&mut self, key: &'static str, report: &mut Vec<Unmapped>) -> Option<Object<'a>> {
+        let value = self.get(key)?;
+
+        Object::new(self.child_path(key), value, report)
+    }
+
+    fn array(&mut self, key: &'static str, report: &mut Vec<Unmapped>) -> Vec<Object<'a>> {
+        let values = match self.get(key) {
+            Some(Value::Array(values)) => values,
+            Some(_) => {
+                unmapped(report, self.child_path(key), "expected an array");
+                return Vec::new();
+            }
+            None => return Vec::new(),
+        };
+
+        values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, value)| {
+                Object::new(format!("{}.{}[{}]", self.path, key, i), value, report)
+            })
+            .collect()
+    }
+
+    /// Reads a string, number, or boolean the same way as XML
+    fn read<T: ReadableValue>(
+        &mut self,
+        key: &'static str,
+        report: &mut Vec<Unmapped>,
+    ) -> Option<T> {
+        let text = match self.get(key)? {
+            Value::String(value) => value.clone(),
+            Value::Number(value) => value.to_string(),
+            Value::Bool(value) => value.to_string(),
+            _ => {
+                unmapped(report, self.child_path(key), "expected a value");
+                return None;
+            }
+        };
+
+        parse(report, self.child_path(key), &text)
+    }
+
+    fn date_time(
+        &mut self,
+        key: &'static str,
+        offset: FixedOffset,
+        report: &mut Vec<Unmapped>,
+    ) -> Option<NaiveDateTime> {
+        let value = self.str(key, report)?;
+
+        match DateTime::parse_from_rfc3339(value) {
+            Ok(time) => Some(time.with_timezone(&offset).naive_local()),
+            Err(_) => {
+                unmapped(
+                    report,
+                    self.child_path(key),
+                    "expected a date and time with a time zone",
+                );
+                None
+            }
+        }
+    }
+
+    /// Reads a Period's start and end
+    fn period(
+        &mut self,
+        key: &'static str,
+        offset: FixedOffset,
+        report: &mut Vec<Unmapped>,
+    ) -> (Option<NaiveDateTime>, Option<NaiveDateTime>) {
+        match self.object(key, report) {
+            Some(mut period) => {
+                let start = period.date_time("start", offset, report);
+                let end = period.date_time("end", offset, report);
+                period.finish(report);
+                (start, end)
+            }
+            None => (None, None),
+        }
+    }
+
+    /// Reads a Quantity's value and unit
+    fn quantity(&mut self, key: &'static str, report: &mut Vec<Unmapped>) -> Option<Quantity> {
+        let mut quantity = self.object(key, report)?;
+        let value = quantity.read("value", report);
+        let unit = quantity.str("unit", report);
+        quantity.finish(report);
+
+        Some(Quantity {
+            value,
+            unit: unit.map(str::to_string),
+        })
+    }
+
+    /// The codes from `element`'s system in this CodeableConcept
+    fn codes(&mut self, element: &str, report: &mut Vec<Unmapped>) -> Vec<&'a str> {
+        let system = urn(element);
+
+        self.array("coding", report)
+            .into_iter()
+            .filter_map(|mut coding| {
+                let code = match coding.str("system", report) {
+                    Some(found) if found == system => coding.str("code", report),
+                    _ => {
+                        unmapped(report, coding.path.clone(), format!("expected {}", system));
+                        coding.get("code");
+                        None
+                    }
+                };
+                coding.finish(report);
+                code
+            })
+            .collect()
+    }
+
+    /// Reads every code in the CodeableConcept at `key`
+    fn concept<T: ReadableValue>(
+        &mut self,
+        key: &'static str,
+        element: &str,
+        report: &mut Vec<Unmapped>,
+    ) -> Vec<T> {
+        let mut concept = match self.object(key, report) {
+            Some(concept) => concept,
+            None => return Vec::new(),
+        };
+        let path = concept.child_path("coding");
+        let codes = concept.codes(element, report);
+        concept.finish(report);
+
+        codes
+            .into_iter()
+            .filter_map(|code| parse(report, path.clone(), code))
+            .collect()
+    }
+
+    /// Reads the single code in the CodeableConcept at `key`
+    fn code<T: ReadableValue>(
+        &mut self,
+        key: &'static str,
+        element: &str,
+        report: &mut Vec<Unmapped>,
+    ) -> Option<T> {
+        let path = self.child_path(key);
+        let mut codes = self.concept(key, element, report).into_iter();
+        let code = codes.next();
+        if codes.next().is_some() {
+            unmapped(report, path, "only the first code is used");
+        }
+
+        code
+    }
+
+    /// The value of the `identifier` with `system`, reporting any others
+    fn identifier(&mut self, system: &str, report: &mut Vec<Unmapped>) -> Option<&'a str> {
+        match self.str("system", report) {
+            Some(found) if found == system => self.str("value", report),
+            _ => {
+                unmapped(report, self.path.clone(), format!("expected {}", system));
+                self.get("value");
+                None
+            }
+        }
+    }
+
+    fn extensions(&mut self, report: &mut Vec<Unmapped>) -> Extensions<'a> {
+        let mut found = Vec::new();
+        for mut extension in self.array("extension", report) {
+            match extension.str("url", report) {
+                Some(url) if url.starts_with(NACOR_URN) => {
+                    found.push((&url[NACOR_URN.len()..], extension));
+                }
+                _ => unmapped(report, extension.path, "unknown extension"),
+            }
+        }
+
+        Extensions { found }
+    }
+
+    /// Reads the note's text, reporting any notes after the first
+    fn note(&mut self, report: &mut Vec<Unmapped>) -> Option<String> {
+        let mut notes = self.array("note", report).into_iter();
+        let text = notes.next().and_then(|mut note| {
+            let text = note.read("text", report);
+            note.finish(report);
+            text
+        });
+        for note in notes {
+            unmapped(report, note.path, "only the first note is used");
+        }
+
+        text
+    }
+
+    /// Reports every field that wasn't used
+    fn finish(self, report: &mut Vec<Unmapped>) {
+        for key in self.fields.keys() {
+            if !self.used.contains(&key.as_str()) && !IGNORED.contains(&key.as_str()) {
+                unmapped(report, self.child_path(key), "not mapped");
+            }
+        }
+    }
+}
+
+fn parse<T: ReadableValue>(report: &mut Vec<Unmapped>, path: String, text: &str) -> Option<T> {
+    match T::read_value(text) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            unmapped(report, path, err.to_string());
+            None
+        }
+    }
+}
+
+struct Quantity {
+    value: Option<String>,
+    unit: Option<String>,
+}
+
+/// The extensions of a resource or element with `urn:aqi-nacor:` URLs, by element
+struct Extensions<'a> {
+    found: Vec<(&'a str, Object<'a>)>,
+}
+
+impl<'a> Extensions<'a> {
+    fn take(&mut self, element: &str) -> Option<Object<'a>> {
+        let index = self.found.iter().position(|&(name, _)| name == element)?;
+
+        Some(self.found.remove(index).1)
+    }
+
+    /// Reads the `key` value of the `element` extension
+    fn read<T: ReadableValue>(
+        &mut self,
+        element: &str,
+        key: &'static str,
+        report: &mut Vec<Unmapped>,
+    ) -> Option<T> {
+        let mut extension = self.take(element)?;
+        let value = extension.read(key, report);
+        extension.finish(report);
+
+        value
+    }
+
+    fn date_time(
+        &mut self,
+        element: &str,
+        offset: FixedOffset,
+        report: &mut Vec<Unmapped>,
+    ) -> Option<NaiveDateTime> {
+        let mut extension = self.take(element)?;
+        let value = extension.date_time("valueDateTime", offset, report);
+        extension.finish(report);
+
+        value
+    }
+
+    fn quantity(&mut self, element: &str, report: &mut Vec<Unmapped>) -> Option<Quantity> {
+        let mut extension = self.take(element)?;
+        let value = extension.quantity("valueQuantity", report);
+        extension.finish(report);
+
+        value
+    }
+
+    /// Reports every extension that wasn't used
+    fn finish(self, report: &mut Vec<Unmapped>) {
+        for (_, extension) in self.found {
+            unmapped(report, extension.path, "unknown extension");
+        }
+    }
+}
+
+/// The parts of a staff member that come from their Practitioner
+#[derive(Clone, Default)]
+struct Practitioner {
+    tax_id: Option<TaxIdType>,
+    npi: Option<NPIType>,
+    provider_credentials: Option<ProviderCredentialsCodeType>,
+}
+
+/// Reads one record from a Bundle
+///
+/// Only fails if `bundle` isn't a Bundle at all; anything within it that
+/// can't be read is reported in `Imported::unmapped`.
+pub fn from_bundle(bundle: &Value, offset: FixedOffset) -> Result<Imported, AQIError> {
+    let mut report = Vec::new();
+    let mut root = match Object::new("Bundle".to_string(), bundle, &mut report) {
+        Some(root) if root.fields.get("resourceType") == Some(&json!("Bundle")) => root,
+        _ => return Err(AQIError::parse("Not a FHIR Bundle")),
+    };
+    root.get("type");
+
+    let mut read = Read {
+        offset,
+        report,
+        patient: None,
+        procedure: None,
+        demographic: DemographicBuilder::new(),
+        procedure_builder: ProcedureBuilder::new(),
+        practitioners: Vec::new(),
+        references: HashMap::new(),
+        staff: Vec::new(),
+        medications: Vec::new(),
+        monitoring: Vec::new(),
+        pre_op_labs: Vec::new(),
+        post_op_labs: Vec::new(),
+        outcomes: Vec::new(),
+    };
+
+    let mut anesthesia_case = AnesthesiaCaseBuilder::new();
+    if let Some(mut identifier) = root.object("identifier", &mut read.report) {
+        if let Some(record_id) = identifier.identifier(&urn("AnesthesiaRecordID"), &mut read.report)
+        {
+            anesthesia_case = anesthesia_case.anesthesia_record_id(record_id);
+        }
+        identifier.finish(&mut read.report);
+    }
+
+    let mut resources = Vec::new();
+    for mut entry in root.array("entry", &mut read.report) {
+        let full_url = entry.str("fullUrl", &mut read.report);
+        let resource = entry.object("resource", &mut read.report);
+        entry.finish(&mut read.report);
+
+        if let Some(resource) = resource {
+            let resource_type = resource
+                .fields
+                .get("resourceType")
+                .and_then(Value::as_str)
+                .unwrap_or("");
+            if let Some(full_url) = full_url {
+                read.references
+                    .insert(full_url.to_string(), resources.len());
+            }
+            if let Some(id) = resource.fields.get("id").and_then(Value::as_str) {
+                read.references
+                    .insert(format!("{}/{}", resource_type, id), resources.len());
+            }
+            resources.push((resource_type, Some(resource)));
+        }
+    }
+    root.finish(&mut read.report);
+
+    // Roles need their practitioners, wherever they are in the Bundle
+    for (i, entry) in resources.iter_mut().enumerate() {
+        if entry.0 == "Practitioner" {
+            if let Some(resource) = entry.1.take() {
+                let practitioner = read.practitioner(resource);
+                read.practitioners.push((i, practitioner, false));
+            }
+        }
+    }
+
+    for (resource_type, resource) in resources {
+        let resource = match resource {
+            Some(resource) => resource,
+            None => continue,
+        };
+
+        match resource_type {
+            "Patient" => read.patient(resource),
+            "Procedure" => read.procedure(resource),
+            "PractitionerRole" => read.role(resource),
+            "MedicationAdministration" => read.medication(resource),
+            "Observation" => read.observation(resource),
+            "AdverseEvent" => read.outcome(resource),
+            _ => unmapped(
+                &mut read.report,
+                resource.path,
+                format!("unsupported resource type {}", resource_type),
+            ),
+        }
+    }
+
+    for &(i, _, used) in &read.practitioners {
+        if !used {
+            unmapped(
+                &mut read.report,
+                format!("Bundle.entry[{}].resource", i),
+                "no PractitionerRole refers to it",
+            );
+        }
+    }
+
+    let outcomes_events = if read.outcomes.is_empty() {
+        None
+    } else {
+        Some(read.outcomes.into_iter().fold(
+            OutcomesEventsBuilder::new(),
+            OutcomesEventsBuilder::add_outcome,
+        ))
+    };
+
+    Ok(Imported {
+        demographic: read.demographic,
+        procedure: read.procedure_builder,
+        anesthesia_case: read
+            .staff
+            .into_iter()
+            .fold(anesthesia_case, AnesthesiaCaseBuilder::add_anesthesia_staff),
+        pre_op: read
+            .pre_op_labs
+            .into_iter()
+            .fold(PreOpBuilder::new(), PreOpBuilder::add_pre_lab_data),
+        intra_op: read.monitoring.into_iter().fold(
+            read.medications
+                .into_iter()
+                .fold(IntraOpBuilder::new(), IntraOpBuilder::add_medication),
+            IntraOpBuilder::add_monitoring,
+        ),
+        post_op: read
+            .post_op_labs
+            .into_iter()
+            .fold(PostOpBuilder::new(), PostOpBuilder::add_post_lab_data),
+        outcomes_events,
+        unmapped: read.report,
+    })
+}
+
+/// The state of one Bundle being read
+struct Read {
+    offset: FixedOffset,
+    report: Vec<Unmapped>,
+    /// Where the Patient and Procedure came from, as only one of each is read
+    patient: Option<String>,
+    procedure: Option<String>,
+    demographic: DemographicBuilder,
+    procedure_builder: ProcedureBuilder,
+    /// Each Practitioner's entry, and whether a role referred to it
+    practitioners: Vec<(usize, Practitioner, bool)>,
+    /// Entries by full URL and by `<resourceType>/<id>`
+    references: HashMap<String, usize>,
+    staff: Vec<AnesthesiaStaffType>,
+    medications: Vec<MedicationType>,
+    monitoring: Vec<MonitoringPhysiologicType>,
+    pre_op_labs: Vec<LabDataType>,
+    post_op_labs: Vec<LabDataType>,
+    outcomes: Vec<OutcomeCodeType>,
+}
+
+/// Whether `resource` is the first of its kind, reporting it if not
+fn first(report: &mut Vec<Unmapped>, first: &mut Option<String>, resource: &Object) -> bool {
+    match *first {
+        Some(ref first) => {
+            unmapped(
+                report,
+                resource.path.clone(),
+                format!("only the first, {}, is read", first),
+            );
+            false
+        }
+        None => {
+            *first = Some(resource.path.clone());
+            true
+        }
+    }
+}
+
+impl Read {
+    fn patient(&mut self, mut resource: Object) {
+        if !first(&mut self.report, &mut self.patient, &resource) {
+            return;
+        }
+
+        let report = &mut self.report;
+        let mut builder = DemographicBuilder::new();
+        let mut extensions = resource.extensions(report);
+
+        for mut identifier in resource.array("identifier", report) {
+            if let Some(patient_id) = identifier.identifier(&urn("PatientID"), report) {
+                builder = builder.patient_id(patient_id);
+            }
+            identifier.finish(report);
+        }
+
+        builder = builder.patient_sex(match resource.str("gender", report) {
+            None => PatientSexCodeType::Missing,
+            Some("male") => PatientSexCodeType::Male,
+            Some("female") => PatientSexCodeType::Female,
+            Some("unknown") => PatientSexCodeType::Unknown,
+            Some(gender) => {
+                unmapped(
+                    report,
+                    resource.child_path("gender"),
+                    format!("read {} as Unknown", gender),
+                );
+                PatientSexCodeType::Unknown
+            }
+        });
+
+        if let Some(dob) = resource.read("birthDate", report) {
+            builder = builder.dob(dob);
+        }
+
+        let mut addresses = resource.array("address", report).into_iter();
+        if let Some(mut address) = addresses.next() {
+            if let Some(home_city) = address.read::<String>("city", report) {
+                builder = builder.home_city(home_city);
+            }
+            if let Some(home_state) = address.read("state", report) {
+                builder = builder.home_state(home_state);
+            }
+            if let Some(home_zip) = address.read("postalCode", report) {
+                builder = builder.home_zip(home_zip);
+            }
+            address.finish(report);
+        }
+        for address in addresses {
+            unmapped(report, address.path, "only the first address is used");
+        }
+
+        if let Some(race) = extensions.take("Race") {
+            if let Some(race) = read_coding(race, "Race", report) {
+                builder = builder.race(race);
+            }
+        }
+
+        extensions.finish(report);
+        resource.finish(report);
+        self.demographic = builder;
+    }
+
+    fn procedure(&mut self, mut resource: Object) {
+        if !first(&mut self.report, &mut self.procedure, &resource) {
+            return;
+        }
+
+        let offset = self.offset;
+        let report = &mut self.report;
+        let mut builder = ProcedureBuilder::new();
+        let mut extensions = resource.extensions(report);
+        let status = resource.str("status", report);
+        resource.get("subject");
+
+        for mut identifier in resource.array("identifier", report) {
+            if let Some(procedure_id) = identifier.identifier(&urn("ProcedureID"), report) {
+                builder = builder.procedure_id(procedure_id);
+            }
+            identifier.finish(report);
+        }
+
+        if let Some(mut location) = resource.object("location", report) {
+            if let Some(mut identifier) = location.object("identifier", report) {
+                if let Some(facility_id) = identifier.identifier(&urn("FacilityID"), report) {
+                    builder = builder.facility_id(facility_id);
+                }
+                identifier.finish(report);
+            }
+            location.finish(report);
+        }
+
+        if let Some(mut code) = resource.object("code", report) {
+            for mut coding in code.array("coding", report) {
+                match coding.str("system", report) {
+                    Some(CPT_SYSTEM) => {
+                        if let Some(cpt) = read_cpt(&mut coding, report) {
+                            builder = builder.add_cpt(cpt);
+                        }
+                    }
+                    _ => {
+                        unmapped(report, coding.path.clone(), "not a CPT code");
+                        coding.get("code");
+                    }
+                }
+                coding.finish(report);
+            }
+            code.finish(report);
+        }
+
+        let (proc_start_time, proc_end_time) = resource.period("performedPeriod", offset, report);
+        if let Some(proc_start_time) = proc_start_time {
+            builder = builder.proc_start_time(proc_start_time);
+        }
+        if let Some(proc_end_time) = proc_end_time {
+            builder = builder.proc_end_time(proc_end_time);
+        }
+        if let Some(procedure_notes) = resource.note(report) {
+            builder = builder.procedure_notes(procedure_notes);
+        }
+
+        match extensions.read("ProcStatus", "valueCode", report) {
+            Some(proc_status) => builder = builder.proc_status(proc_status),
+            None if status == Some("unknown") => {
+                builder = builder.proc_status(ProcStatusCodeType::Unknown)
+            }
+            None => {}
+        }
+        if let Some(admission_status) = extensions.read("AdmissionStatus", "valueCode", report) {
+            builder = builder.admission_status(admission_status);
+        }
+        if let Some(transfer_status) = extensions.read("TransferStatus", "valueCode", report) {
+            builder = builder.transfer_status(transfer_status);
+        }
+        if let Some(admission_date) = extensions.date_time("AdmissionDate", offset, report) {
+            builder = builder.admission_date(admission_date);
+        }
+        let location_type = extensions.read("LocationType", "valueCode", report);
+        let location_details = extensions.read("LocationDetails", "valueString", report);
+        match (location_type, location_details) {
+            (Some(location_type), Some(location_details)) => {
+                builder = builder.procedure_location(ProcedureLocationType {
+                    location_type,
+                    location_details,
+                });
+            }
+            (None, None) => {}
+            _ => unmapped(
+                report,
+                resource.child_path("extension"),
+                "LocationType and LocationDetails need each other",
+            ),
+        }
+
+        extensions.finish(report);
+        resource.finish(report);
+        self.procedure_builder = builder;
+    }
+
+    fn practitioner(&mut self, mut resource: Object) -> Practitioner {
+        let report = &mut self.report;
+        let mut practitioner = Practitioner::default();
+
+        for mut identifier in resource.array("identifier", report) {
+            match identifier.str("system", report) {
+                Some(NPI_SYSTEM) => practitioner.npi = identifier.read("value", report),
+                Some(system) if system == urn("TaxID") => {
+                    practitioner.tax_id = identifier.read("value", report);
+                }
+                _ => {
+                    unmapped(report, identifier.path.clone(), "not an NPI or TaxID");
+                    identifier.get("value");
+                }
+            }
+            identifier.finish(report);
+        }
+
+        let mut qualifications = resource.array("qualification", report).into_iter();
+        if let Some(mut qualification) = qualifications.next() {
+            practitioner.provider_credentials =
+                qualification.code("code", "ProviderCredentials", report);
+            qualification.finish(report);
+        }
+        for qualification in qualifications {
+            unmapped(
+                report,
+                qualification.path,
+                "only the first qualification is used",
+            );
+        }
+
+        resource.finish(report);
+        practitioner
+    }
+
+    fn role(&mut self, mut resource: Object) {
+        let offset = self.offset;
+        let mut practitioner = None;
+
+        if let Some(mut reference) = resource.object("practitioner", &mut self.report) {
+            let target = reference
+                .str("reference", &mut self.report)
+                .and_then(|url| self.references.get(url).cloned());
+            practitioner = target.and_then(|target| {
+                self.practitioners
+                    .iter_mut()
+                    .find(|&&mut (i, _, _)| i == target)
+                    .map(|&mut (_, ref practitioner, ref mut used)| {
+                        *used = true;
+                        practitioner.clone()
+                    })
+            });
+            if practitioner.is_none() {
+                unmapped(
+                    &mut self.report,
+                    reference.path.clone(),
+                    "not a Practitioner in the Bundle",
+                );
+            }
+            reference.finish(&mut self.report);
+        }
+
+        let report = &mut self.report;
+        let mut extensions = resource.extensions(report);
+        let staff_responsibility = {
+            let mut codes = resource.array("code", report).into_iter();
+            let code = codes.next().and_then(|mut concept| {
+                let path = concept.child_path("coding");
+                let code = concept
+                    .codes("StaffResponsibility", report)
+                    .first()
+                    .and_then(|code| parse(report, path, code));
+                concept.finish(report);
+                code
+            });
+            for concept in codes {
+                unmapped(report, concept.path, "only the first code is used");
+            }
+            code
+        };
+        let (staff_sign_in, staff_sign_out) = resource.period("period", offset, report);
+        let staff_notes = extensions.read("StaffNotes", "valueString", report);
+        extensions.finish(report);
+
+        match practitioner {
+            Some(Practitioner {
+                tax_id: Some(tax_id),
+                npi: Some(npi),
+                provider_credentials: Some(provider_credentials),
+            }) => {
+                resource.finish(report);
+                self.staff.push(AnesthesiaStaffType {
+                    tax_id,
+                    npi,
+                    staff_responsibility,
+                    provider_credentials,
+                    staff_sign_in,
+                    staff_sign_out,
+                    staff_notes,
+                });
+            }
+            _ => {
+                unmapped(
+                    report,
+                    resource.path,
+                    "needs a Practitioner with an NPI, TaxID and ProviderCredentials",
+                );
+            }
+        }
+    }
+
+    fn medication(&mut self, mut resource: Object) {
+        let offset = self.offset;
+        let report = &mut self.report;
+        let mut extensions = resource.extensions(report);
+        resource.get("status");
+        resource.get("subject");
+        resource.get("partOf");
+
+        let medication_name = resource
+            .object("medicationCodeableConcept", report)
+            .and_then(|mut concept| {
+                let text = concept.read::<String>("text", report);
+                concept.finish(report);
+                text
+            });
+        let medication_type = non_empty(resource.concept("category", "MedicationType", report));
+        let (dose_start, dose_end) = resource.period("effectivePeriod", offset, report);
+
+        let mut med_dose = None;
+        let mut dose_units = None;
+        let mut medication_route = None;
+        if let Some(mut dosage) = resource.object("dosage", report) {
+            if let Some(dose) = dosage.quantity("dose", report) {
+                let path = dosage.child_path("dose");
+                med_dose = dose
+                    .value
+                    .and_then(|value| parse(report, path.clone(), &value));
+                dose_units = dose.unit.and_then(|unit| parse(report, path, &unit));
+            }
+            medication_route = non_empty(dosage.concept("route", "MedicationRoute", report));
+            dosage.finish(report);
+        }
+        if dose_units.is_none() {
+            dose_units = extensions.read("DoseUnits", "valueCode", report);
+        }
+
+        let mut med_concentration = None;
+        let mut med_concentration_unit = None;
+        if let Some(concentration) = extensions.quantity("MedConcentration", report) {
+            let path = resource.child_path("extension");
+            med_concentration = concentration
+                .value
+                .and_then(|value| parse(report, path.clone(), &value));
+            med_concentration_unit = concentration
+                .unit
+                .and_then(|unit| parse(report, path, &unit));
+        }
+        if med_concentration_unit.is_none() {
+            med_concentration_unit = extensions.read("MedConcentrationUnit", "valueCode", report);
+        }
+        extensions.finish(report);
+
+        match medication_name {
+            Some(medication_name) => {
+                resource.finish(report);
+                self.medications.push(MedicationType {
+                    medication_name,
+                    medication_type,
+                    med_dose,
+                    dose_units,
+                    dose_start,
+                    dose_end,
+                    med_concentration,
+                    med_concentration_unit,
+                    medication_route,
+                    mixture_medications: None,
+                });
+            }
+            None => unmapped(
+                report,
+                resource.path,
+                "needs a medicationCodeableConcept.text",
+            ),
+        }
+    }
+
+    fn observation(&mut self, mut resource: Object) {
+        let report = &mut self.report;
+        let mut kind = None;
+        let mut lab_category_name = None;
+
+        for mut concept in resource.array("category", report) {
+            for mut coding in concept.array("coding", report) {
+                let system = coding.str("system", report);
+                let code = coding.str("code", report);
+                match (system, code) {
+                    (Some(OBSERVATION_CATEGORY_SYSTEM), Some(code))
+                        if code == "laboratory" || code == "procedure" =>
+                    {
+                        kind = Some(code);
+                    }
+                    (Some(system), Some(code)) if system == urn("LabCategoryName") => {
+                        lab_category_name = parse(report, coding.child_path("code"), code);
+                    }
+                    _ => unmapped(report, coding.path.clone(), "unknown category"),
+                }
+                coding.finish(report);
+            }
+            concept.finish(report);
+        }
+
+        match kind {
+            Some("laboratory") => self.lab(resource, lab_category_name),
+            Some(_) => self.monitoring(resource),
+            None => unmapped(
+                report,
+                resource.path,
+                "needs a laboratory or procedure category",
+            ),
+        }
+    }
+
+    fn monitoring(&mut self, mut resource: Object) {
+        let offset = self.offset;
+        let report = &mut self.report;
+        let mut extensions = resource.extensions(report);
+        resource.get("status");
+        resource.get("subject");
+        resource.get("partOf");
+
+        let monitoring_name = resource.code("code", "MonitoringName", report);
+        let monitoring_time = resource.date_time("effectiveDateTime", offset, report);
+        let monitoring_source = resource.code("method", "MonitoringSource", report);
+
+        let mut monitoring_value_numeric = None;
+        let mut monitoring_units = None;
+        if let Some(quantity) = resource.quantity("valueQuantity", report) {
+            let path = resource.child_path("valueQuantity");
+            monitoring_value_numeric = quantity
+                .value
+                .and_then(|value| parse(report, path.clone(), &value));
+            monitoring_units = quantity.unit.and_then(|unit| parse(report, path, &unit));
+        }
+        if monitoring_units.is_none() {
+            monitoring_units = extensions.read("MonitoringUnits", "valueCode", report);
+        }
+        let monitoring_value_text = resource
+            .read("valueString", report)
+            .or_else(|| extensions.read("MonitoringValueText", "valueString", report));
+        extensions.finish(report);
+
+        match monitoring_name {
+            Some(monitoring_name) => {
+                resource.finish(report);
+                self.monitoring.push(MonitoringPhysiologicType {
+                    monitoring_name,
+                    monitoring_time,
+                    monitoring_units,
+                    monitoring_value_numeric,
+                    monitoring_value_text,
+                    monitoring_source,
+                });
+            }
+            None => unmapped(report, resource.path, "needs a MonitoringName code"),
+        }
+    }
+
+    fn lab(&mut self, mut resource: Object, lab_category_name: Option<LabDataCategoryCodeType>) {
+        let offset = self.offset;
+        let report = &mut self.report;
+        let mut extensions = resource.extensions(report);
+        resource.get("status");
+        resource.get("subject");
+
+        let lab_name = resource.code("code", "LabName", report);
+        let lab_date_time = resource.date_time("effectiveDateTime", offset, report);
+        let lab_comments = resource.note(report);
+
+        let mut lab_value = None;
+        let mut lab_unit = None;
+        if let Some(quantity) = resource.quantity("valueQuantity", report) {
+            let path = resource.child_path("valueQuantity");
+            lab_value = quantity.value;
+            lab_unit = quantity.unit.and_then(|unit| parse(report, path, &unit));
+        }
+        if lab_value.is_none() {
+            lab_value = resource.read("valueString", report);
+        }
+        if lab_unit.is_none() {
+            lab_unit = extensions.read("LabUnit", "valueCode", report);
+        }
+        let lab_value_text = extensions.read("LabValueText", "valueString", report);
+
+        let mut lab_range_low = None;
+        let mut lab_range_high = None;
+        let mut ranges = resource.array("referenceRange", report).into_iter();
+        if let Some(mut range) = ranges.next() {
+            lab_range_low = range.quantity("low", report).and_then(|low| low.value);
+            lab_range_high = range.quantity("high", report).and_then(|high| high.value);
+            range.finish(report);
+        }
+        for range in ranges {
+            unmapped(report, range.path, "only the first reference range is used");
+        }
+
+        let set: Option<String> = extensions.read("LabSet", "valueCode", report);
+        extensions.finish(report);
+
+        let lab = match (lab_name, lab_unit, lab_value, lab_date_time) {
+            (Some(lab_name), Some(lab_unit), Some(lab_value), Some(lab_date_time)) => LabDataType {
+                lab_name,
+                lab_category_name,
+                lab_unit,
+                lab_value,
+                lab_value_text,
+                lab_range_high,
+                lab_range_low,
+                lab_date_time,
+                lab_comments,
+            },
+            _ => {
+                unmapped(
+                    report,
+                    resource.path,
+                    "needs a LabName code, a value with a unit, and an effectiveDateTime",
+                );
+                return;
+            }
+        };
+
+        match set.as_deref() {
+            Some("PreOp") => {
+                resource.finish(report);
+                self.pre_op_labs.push(lab);
+            }
+            Some("PostOp") => {
+                resource.finish(report);
+                self.post_op_labs.push(lab);
+            }
+            _ => unmapped(report, resource.path, "needs a PreOp or PostOp LabSet"),
+        }
+    }
+
+    fn outcome(&mut self, mut resource: Object) {
+        let offset = self.offset;
+        let report = &mut self.report;
+        let mut extensions = resource.extensions(report);
+        resource.get("subject");
+
+        let outcome_occurred = match resource.str("actuality", report) {
+            Some("actual") => Some(true),
+            Some("potential") => Some(false),
+            _ => None,
+        };
+        let outcome_id = resource.code("event", "OutcomeID", report);
+        let outcome_time_stamp = resource.date_time("date", offset, report);
+        let outcome_severity = resource.code("severity", "OutcomeSeverity", report);
+        let outcome_time_frame = extensions.read("OutcomeTimeFrame", "valueCode", report);
+        extensions.finish(report);
+
+        match (outcome_id, outcome_occurred) {
+            (Some(outcome_id), Some(outcome_occurred)) => {
+                resource.finish(report);
+                self.outcomes.push(OutcomeCodeType {
+                    outcome_id,
+                    outcome_occurred,
+                    outcome_time_stamp,
+                    outcome_severity,
+                    outcome_time_frame,
+                });
+            }
+            _ => unmapped(
+                report,
+                resource.path,
+                "needs an OutcomeID event and an actuality",
+            ),
+        }
+    }
+}
+
+/// Reads an extension's `valueCoding` from `element`'s system
+fn read_coding<T: ReadableValue>(
+    mut extension: Object,
+    element: &str,
+    report: &mut Vec<Unmapped>,
+) -> Option<T> {
+    let value = extension
+        .object("valueCoding", report)
+        .and_then(|mut coding| {
+            let system = urn(element);
+            let code = match coding.str("system", report) {
+                Some(found) if found == system => coding.read("code", report),
+                _ => {
+                    unmapped(report, coding.path.clone(), format!("expected {}", system));
+                    coding.get("code");
+                    None
+                }
+            };
+            coding.finish(report);
+            code
+        });
+    extension.finish(report);
+
+    value
+}
+
+/// Reads a CPT coding, after its system
+fn read_cpt(coding: &mut Object, report: &mut Vec<Unmapped>) -> Option<CPTType> {
+    let cpt_value = coding.read("code", report);
+    let mut extensions = coding.extensions(report);
+    let cpt_rank = extensions.read("CPTRank", "valueString", report);
+    let cpt_modifier = extensions.read("CPTModifier", "valueCode", report);
+    extensions.finish(report);
+
+    cpt_value.map(|cpt_value| CPTType {
+        cpt_rank,
+        cpt_value,
+        cpt_modifier,
+    })
+}
+
+fn non_empty<T>(values: Vec<T>) -> Option<Vec<T>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offset() -> FixedOffset {
+        FixedOffset::west_opt(6 * 3600).unwrap()
+    }
+
+    #[test]
+    fn exports_a_collection_bundle() {
+        let exported = to_bundle(&submission().anesthesia_records[0], offset());
+
+        assert_eq!(exported.bundle["resourceType"], json!("Bundle"));
+        assert_eq!(exported.bundle["type"], json!("collection"));
+        assert!(exported
+            .unmapped
+            .iter()
+            .any(|unmapped| unmapped.path == "PreOp/ASAClass"));
+    }
+
+    #[test]
+    fn round_trips_through_a_bundle() {
+        let record = &submission().anesthesia_records[0];
+        let exported = to_bundle(record, offset());
+        let imported = from_bundle(&exported.bundle, offset()).unwrap();
+        assert!(imported.unmapped.is_empty());
+
+        // Fill in the sections FHIR has no mapping for
+        let mut imported = imported;
+        let method = record
+            .anesthesia_case
+            .anesthesia_method_set
+            .anesthesia_method[0]
+            .clone();
+        imported.anesthesia_case = imported.anesthesia_case.add_anesthesia_method(method);
+        imported.pre_op = imported
+            .pre_op
+            .age(record.pre_op.age)
+            .asa_class(record.pre_op.asa_class);
+        for payment_method in &record.post_op.payment_method {
+            imported.post_op = imported.post_op.add_payment_method(payment_method.clone());
+        }
+        let built = imported.into_builder().build().unwrap();
+
+        let demographic = &built.demographic;
+        assert_eq!(demographic.patient_id.as_deref(), Some("MRN1"));
+        assert_eq!(demographic.dob, NaiveDate::from_ymd_opt(1930, 5, 6));
+        assert_eq!(
+            built.procedure.proc_start_time,
+            record.procedure.proc_start_time
+        );
+        assert_eq!(
+            built.procedure.proc_end_time,
+            record.procedure.proc_end_time
+        );
+        assert_eq!(built.anesthesia_case.anesthesia_record_id, "R1");
+
+        let cpt = &built.procedure.cpt_set.as_ref().unwrap().cpt;
+        assert_eq!(cpt.len(), 1);
+        assert_eq!(cpt[0].cpt_value.value(), "27447");
+
+        let staff = &built.anesthesia_case.anesthesia_staff_set.anesthesia_staff;
+        assert_eq!(staff.len(), 1);
+        assert_eq!(staff[0].npi.value(), "1234567890");
+    }
+
+    #[test]
+    fn keeps_decimals_as_written() {
+        assert_eq!(number("+007.50").unwrap().to_string(), "7.50");
+        assert_eq!(number("-.5").unwrap().to_string(), "-0.5");
+        assert_eq!(number("3.").unwrap().to_string(), "3");
+        assert_eq!(
+            number("12345678901234567890.000000001")
+                .unwrap()
+                .to_string(),
+            "12345678901234567890.000000001"
+        );
+        assert_eq!(number("13.1 g/dL"), None);
+    }
+
+    #[test]
+    fn maps_proc_status_to_the_procedure_status() {
+        let procedure = |record: &AnesthesiaRecordType| {
+            let exported = to_bundle(record, offset());
+            exported.bundle["entry"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|entry| entry["resource"].clone())
+                .find(|resource| resource["resourceType"] == json!("Procedure"))
+                .unwrap()
+        };
+        let mut record = submission().anesthesia_records[0].clone();
+        assert_eq!(procedure(&record)["status"], json!("completed"));
+
+        record.procedure.proc_status = ProcStatusCodeType::Unknown;
+        let mut resource = procedure(&record);
+        assert_eq!(resource["status"], json!("unknown"));
+
+        // Without the extension, the status is all there is to go on
+        resource.as_object_mut().unwrap().remove("extension");
+        let bundle = json!({
+            "resourceType": "Bundle",
+            "type": "collection",
+            "entry": [{ "resource": resource }],
+        });
+        let imported = from_bundle(&bundle, offset()).unwrap();
+        let built = imported.procedure.facility_id("F1").build().unwrap();
+        assert_eq!(built.proc_status, ProcStatusCodeType::Unknown);
+    }
+
+    #[test]
+    fn rejects_other_resources() {
+        let patient = json!({ "resourceType": "Patient" });
+
+        assert!(from_bundle(&patient, offset()).is_err());
+    }
+}
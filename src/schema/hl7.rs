@@ -0,0 +1,967 @@
+//! # HL7 v2
+//! Reads demographics, procedures, and staff from ADT, SIU, and DFT messages
+//!
+//! `read_messages` splits a feed, such as a file or stdin, into messages, and
+//! `correlate` groups them into a `PartialRecord` per visit, keyed by the
+//! visit number in PV1-19, or the patient ID in PID-3 if a message has none.
+//! Visit numbers and patient IDs are kept apart, so a visit number that
+//! happens to equal some patient's ID doesn't merge their messages. Later
+//! messages override earlier ones, so an ADT update wins over the admission
+//! it updates. Values the messages don't have, including a PatientSex without
+//! a PID-8, are left unset for the builders to report.
+//!
+//! | Segment  | Fields                                                    |
+//! |----------|-----------------------------------------------------------|
+//! | PID      | PID-3 PatientID, PID-7 DOB, PID-8 PatientSex, PID-11 HomeCity, HomeState, and HomeZip |
+//! | PV1      | PV1-2 AdmissionStatus, PV1-3 FacilityID, PV1-4 ProcStatus, PV1-44 AdmissionDate |
+//! | SCH      | SCH-2 or SCH-1 ProcedureID, SCH-11 ProcStartTime and ProcEndTime |
+//! | AIS      | AIS-3 CPT codes, AIS-4 ProcStartTime                      |
+//! | AIP      | AIP-3 staff NPIs                                          |
+//! | FT1      | FT1-25 and FT1-26 CPT codes and modifiers, FT1-20 staff NPIs |
+//!
+//! CPT codes from 00100 to 01999 are anesthesia codes and go in the
+//! AnesthesiaCase's CPTAnesSet, the rest in the Procedure's CPTSet. FT1
+//! credits (FT1-6 `CR`) remove the code they credit. Messages only identify
+//! staff, without their Tax ID or credentials, so their NPIs are listed for
+//! the rest of their AnesthesiaStaff to be filled in.
+
+use super::builder::*;
+use super::*;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+
+/// The separators a message uses, from its MSH segment
+#[derive(Clone, Copy, Debug)]
+struct Encoding {
+    field: char,
+    component: char,
+    repetition: char,
+    escape: char,
+    subcomponent: char,
+}
+
+impl Default for Encoding {
+    fn default() -> Encoding {
+        Encoding {
+            field: '|',
+            component: '^',
+            repetition: '~',
+            escape: '\\',
+            subcomponent: '&',
+        }
+    }
+}
+
+impl Encoding {
+    /// Replaces escape sequences for the separators, and drops any others
+    fn unescape(&self, value: &str) -> String {
+        let mut out = String::with_capacity(value.len());
+        let mut parts = value.split(self.escape);
+
+        if let Some(first) = parts.next() {
+            out.push_str(first);
+        }
+        // Escapes come in pairs, so every other part is an escape sequence
+        while let Some(sequence) = parts.next() {
+            match sequence {
+                "F" => out.push(self.field),
+                "S" => out.push(self.component),
+                "T" => out.push(self.subcomponent),
+                "R" => out.push(self.repetition),
+                "E" => out.push(self.escape),
+                _ => {}
+            }
+            if let Some(text) = parts.next() {
+                out.push_str(text);
+            }
+        }
+
+        out
+    }
+}
+
+/// A single segment, e.g. `PID`
+#[derive(Clone, Debug)]
+pub struct Segment {
+    pub name: String,
+    fields: Vec<String>,
+    encoding: Encoding,
+}
+
+impl Segment {
+    /// Field `n`, numbered as in the standard, e.g. 7 for PID-7
+    pub fn field(&self, n: usize) -> Option<&str> {
+        // MSH-1 is the field separator itself, so MSH fields are one behind
+        let index = if self.name == "MSH" {
+            n.checked_sub(1)?
+        } else {
+            n
+        };
+
+        self.fields
+            .get(index)
+            .map(String::as_str)
+            .filter(|field| !field.is_empty())
+    }
+
+    /// Every repetition of field `n`
+    pub fn repetitions(&self, n: usize) -> Vec<&str> {
+        match self.field(n) {
+            Some(field) => field.split(self.encoding.repetition).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Component `component` of the first repetition of field `n`, both
+    /// numbered from 1, unescaped
+    pub fn component(&self, n: usize, component: usize) -> Option<String> {
+        self.repetitions(n)
+            .first()
+            .and_then(|repetition| self.component_of(repetition, component))
+    }
+
+    /// Component `component` of a repetition, numbered from 1, unescaped
+    ///
+    /// Only the first subcomponent is kept.
+    pub fn component_of(&self, repetition: &str, component: usize) -> Option<String> {
+        repetition
+            .split(self.encoding.component)
+            .nth(component.checked_sub(1)?)
+            .and_then(|component| component.split(self.encoding.subcomponent).next())
+            .map(|value| self.encoding.unescape(value).trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// A single message, starting with its MSH segment
+#[derive(Clone, Debug)]
+pub struct Message {
+    pub segments: Vec<Segment>,
+}
+
+impl Message {
+    fn header(&self) -> &Segment {
+        &self.segments[0]
+    }
+
+    /// MSH-9's message code, e.g. `ADT`
+    pub fn message_type(&self) -> Option<String> {
+        self.header().component(9, 1)
+    }
+
+    /// MSH-9's trigger event, e.g. `A08`
+    pub fn trigger_event(&self) -> Option<String> {
+        self.header().component(9, 2)
+    }
+
+    /// MSH-10
+    pub fn control_id(&self) -> Option<String> {
+        self.header().component(10, 1)
+    }
+
+    /// Every segment named `name`, in order
+    pub fn segments<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Segment> + 'a {
+        self.segments
+            .iter()
+            .filter(move |segment| segment.name == name)
+    }
+
+    pub fn segment(&self, name: &str) -> Option<&Segment> {
+        self.segments.iter().find(|segment| segment.name == name)
+    }
+}
+
+/// Splits a feed into messages
+///
+/// Segments can end in `\r`, `\n`, or both, and MLLP framing is ignored.
+pub fn parse_messages(text: &str) -> Result<Vec<Message>, AQIError> {
+    let mut messages: Vec<Message> = Vec::new();
+    let mut encoding = Encoding::default();
+
+    let lines = text
+        .split(['\r', '\n'])
+        .map(|line| line.trim_matches(['\u{0b}', '\u{1c}']))
+        .filter(|line| !line.trim().is_empty());
+
+    for (i, line) in lines.enumerate() {
+        if line.starts_with("MSH") {
+            encoding = msh_encoding(line).ok_or_else(|| {
+                AQIError::parse(format!("Invalid MSH segment: {}", line))
+                    .in_element(&format!("Message[{}]", messages.len()))
+            })?;
+            messages.push(Message {
+                segments: Vec::new(),
+            });
+        }
+
+        let message = match messages.last_mut() {
+            Some(message) => message,
+            None => {
+                return Err(AQIError::parse(format!(
+                    "Segment {} before the first MSH segment",
+                    i + 1
+                )))
+            }
+        };
+
+        let mut fields = line.split(encoding.field).map(str::to_string);
+        let name = fields.next().unwrap_or_default();
+        message.segments.push(Segment {
+            name,
+            fields: std::iter::once(String::new()).chain(fields).collect(),
+            encoding,
+        });
+    }
+
+    Ok(messages)
+}
+
+/// Reads and splits a whole feed
+pub fn read_messages<R: Read>(mut source: R) -> Result<Vec<Message>, AQIError> {
+    let mut text = String::new();
+    source.read_to_string(&mut text)?;
+
+    parse_messages(&text)
+}
+
+fn msh_encoding(line: &str) -> Option<Encoding> {
+    let mut chars = line.chars().skip(3);
+    let field = chars.next()?;
+    let mut characters = chars.take_while(|&c| c != field);
+
+    Some(Encoding {
+        field,
+        component: characters.next()?,
+        repetition: characters.next()?,
+        escape: characters.next()?,
+        subcomponent: characters.next()?,
+    })
+}
+
+/// What a visit's messages are grouped by
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CorrelationKey {
+    /// The visit number in PV1-19
+    Visit(String),
+    /// The patient ID in PID-3, for messages without a visit number
+    Patient(String),
+}
+
+impl Default for CorrelationKey {
+    fn default() -> CorrelationKey {
+        CorrelationKey::Visit(String::new())
+    }
+}
+
+impl fmt::Display for CorrelationKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CorrelationKey::Visit(visit) => write!(f, "visit {}", visit),
+            CorrelationKey::Patient(patient) => write!(f, "patient {}", patient),
+        }
+    }
+}
+
+/// What the messages for one visit say about its record
+///
+/// Each section holds whatever the messages had for it, for the rest to be
+/// added before building.
+#[derive(Clone, Debug, Default)]
+pub struct PartialRecord {
+    /// The visit number, or the patient ID if the messages had none
+    pub key: CorrelationKey,
+    /// Indexes of the messages read into this record
+    pub messages: Vec<usize>,
+    pub demographic: DemographicBuilder,
+    pub procedure: ProcedureBuilder,
+    /// Holds the anesthesia CPT codes
+    pub anesthesia_case: AnesthesiaCaseBuilder,
+    /// NPIs of the staff named in the messages, in order of appearance
+    pub staff_npis: Vec<NPIType>,
+}
+
+impl PartialRecord {
+    /// A builder for the whole record, with the sections read from the messages
+    pub fn into_builder(self) -> AnesthesiaRecordBuilder {
+        AnesthesiaRecordBuilder::new()
+            .demographic(self.demographic)
+            .procedure(self.procedure)
+            .anesthesia_case(self.anesthesia_case)
+    }
+}
+
+/// A value that couldn't be read from a message
+#[derive(Debug)]
+pub struct MessageError {
+    /// Index of the message in the feed
+    pub message: usize,
+    /// Path is the field, e.g. `PID-7`
+    pub error: AQIError,
+}
+
+#[derive(Debug, Default)]
+pub struct Correlated {
+    pub records: Vec<PartialRecord>,
+    pub errors: Vec<MessageError>,
+}
+
+/// Everything read for one visit so far
+#[derive(Default)]
+struct Visit {
+    key: CorrelationKey,
+    messages: Vec<usize>,
+    patient_id: Option<String>,
+    dob: Option<NaiveDate>,
+    patient_sex: Option<PatientSexCodeType>,
+    home_city: Option<String>,
+    home_state: Option<USStateCodeType>,
+    home_zip: Option<ZipCodeType>,
+    procedure_id: Option<String>,
+    facility_id: Option<String>,
+    proc_start_time: Option<NaiveDateTime>,
+    proc_end_time: Option<NaiveDateTime>,
+    admission_status: Option<AdmissionStatusCodeType>,
+    proc_status: Option<ProcStatusCodeType>,
+    admission_date: Option<NaiveDateTime>,
+    cpts: Vec<CPTType>,
+    cpt_anes: Vec<CPTAnesType>,
+    staff_npis: Vec<NPIType>,
+}
+
+/// Replaces `$FIELD` with `$VALUE` if it's `Some`
+macro_rules! update {
+    ($VISIT:ident, $($FIELD:ident: $VALUE:expr),* $(,)*) => {
+        $(
+            if let Some(value) = $VALUE {
+                $VISIT.$FIELD = Some(value);
+            }
+        )*
+    };
+}
+
+/// Groups messages by visit, reading each into the record for its visit
+///
+/// Messages other than ADT, SIU, and DFT, and messages without a visit
+/// number or patient ID, are reported and skipped.
+pub fn correlate(messages: &[Message]) -> Correlated {
+    let mut visits: Vec<Visit> = Vec::new();
+    let mut keys = HashMap::new();
+    let mut errors = Vec::new();
+
+    for (i, message) in messages.iter().enumerate() {
+        let mut fields = Fields {
+            message: i,
+            errors: &mut errors,
+        };
+
+        let message_type = message.message_type();
+        match message_type.as_deref() {
+            Some("ADT") | Some("SIU") | Some("DFT") => {}
+            _ => {
+                fields.error(
+                    "MSH-9",
+                    AQIError::parse(format!(
+                        "Unsupported message type {}",
+                        message_type.as_deref().unwrap_or("(none)")
+                    )),
+                );
+                continue;
+            }
+        }
+
+        let pid = message.segment("PID");
+        let pv1 = message.segment("PV1");
+        let key = pv1
+            .and_then(|pv1| pv1.component(19, 1))
+            .map(CorrelationKey::Visit)
+            .or_else(|| {
+                pid.and_then(|pid| pid.component(3, 1))
+                    .map(CorrelationKey::Patient)
+            });
+        let key = match key {
+            Some(key) => key,
+            None => {
+                fields.error(
+                    "PV1-19",
+                    AQIError::parse("No visit number or patient ID to correlate by"),
+                );
+                continue;
+            }
+        };
+
+        let index = *keys.entry(key.clone()).or_insert_with(|| {
+            visits.push(Visit {
+                key,
+                ..Default::default()
+            });
+            visits.len() - 1
+        });
+        let visit = &mut visits[index];
+        visit.messages.push(i);
+
+        if let Some(pid) = pid {
+            read_pid(visit, pid, &mut fields);
+        }
+        if let Some(pv1) = pv1 {
+            read_pv1(visit, pv1, &mut fields);
+        }
+        if let Some(sch) = message.segment("SCH") {
+            read_sch(visit, sch, &mut fields);
+        }
+        for ais in message.segments("AIS") {
+            read_ais(visit, ais, &mut fields);
+        }
+        for aip in message.segments("AIP") {
+            if let Some(npi) = fields.npi(aip, 3) {
+                add_staff(visit, npi);
+            }
+        }
+        for ft1 in message.segments("FT1") {
+            read_ft1(visit, ft1, &mut fields);
+        }
+    }
+
+    Correlated {
+        records: visits.into_iter().map(Visit::into_partial_record).collect(),
+        errors,
+    }
+}
+
+impl Visit {
+    fn into_partial_record(self) -> PartialRecord {
+        let mut demographic = DemographicBuilder::new();
+        if let Some(patient_sex) = self.patient_sex {
+            demographic = demographic.patient_sex(patient_sex);
+        }
+        if let Some(patient_id) = self.patient_id {
+            demographic = demographic.patient_id(patient_id);
+        }
+        if let Some(dob) = self.dob {
+            demographic = demographic.dob(dob);
+        }
+        if let Some(home_city) = self.home_city {
+            demographic = demographic.home_city(home_city);
+        }
+        if let Some(home_state) = self.home_state {
+            demographic = demographic.home_state(home_state);
+        }
+        if let Some(home_zip) = self.home_zip {
+            demographic = demographic.home_zip(home_zip);
+        }
+
+        let mut procedure = self
+            .cpts
+            .into_iter()
+            .fold(ProcedureBuilder::new(), ProcedureBuilder::add_cpt);
+        if let Some(procedure_id) = self.procedure_id {
+            procedure = procedure.procedure_id(procedure_id);
+        }
+        if let Some(facility_id) = self.facility_id {
+            procedure = procedure.facility_id(facility_id);
+        }
+        if let Some(proc_start_time) = self.proc_start_time {
+            procedure = procedure.proc_start_time(proc_start_time);
+        }
+        if let Some(proc_end_time) = self.proc_end_time {
+            procedure = procedure.proc_end_time(proc_end_time);
+        }
+        if let Some(admission_status) = self.admission_status {
+            procedure = procedure.admission_status(admission_status);
+        }
+        if let Some(proc_status) = self.proc_status {
+            procedure = procedure.proc_status(proc_status);
+        }
+        if let Some(admission_date) = self.admission_date {
+            procedure = procedure.admission_date(admission_date);
+        }
+
+        PartialRecord {
+            key: self.key,
+            messages: self.messages,
+            demographic,
+            procedure,
+            anesthesia_case: self.cpt_anes.into_iter().fold(
+                AnesthesiaCaseBuilder::new(),
+                AnesthesiaCaseBuilder::add_cpt_anes,
+            ),
+            staff_npis: self.staff_npis,
+        }
+    }
+}
+
+/// Reads values from one message, recording any errors
+struct Fields<'a> {
+    message: usize,
+    errors: &'a mut Vec<MessageError>,
+}
+
+impl<'a> Fields<'a> {
+    fn error(&mut self, field: &str, error: AQIError) {
+        self.errors.push(MessageError {
+            message: self.message,
+            error: error.in_element(field),
+        });
+    }
+
+    fn read<T, F>(
+        &mut self,
+        segment: &Segment,
+        n: usize,
+        value: Option<String>,
+        read: F,
+    ) -> Option<T>
+    where
+        F: FnOnce(&str) -> Result<T, AQIError>,
+    {
+        match read(&value?) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.error(&format!("{}-{}", segment.name, n), err);
+                None
+            }
+        }
+    }
+
+    fn date(&mut self, segment: &Segment, n: usize) -> Option<NaiveDate> {
+        self.read(segment, n, segment.component(n, 1), parse_date)
+    }
+
+    fn date_time(
+        &mut self,
+        segment: &Segment,
+        n: usize,
+        component: usize,
+    ) -> Option<NaiveDateTime> {
+        self.read(segment, n, segment.component(n, component), parse_date_time)
+    }
+
+    /// The NPI in XCN field `n`, if its ID is marked as one or looks like one
+    fn npi(&mut self, segment: &Segment, n: usize) -> Option<NPIType> {
+        let id = segment.component(n, 1)?;
+        let marked = segment.component(n, 13).as_deref() == Some("NPI")
+            || segment.component(n, 9).as_deref() == Some("NPI");
+
+        match NPIType::from_str(&id) {
+            Ok(npi) => Some(npi),
+            Err(err) => {
+                if marked {
+                    self.error(&format!("{}-{}", segment.name, n), err);
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Reads an HL7 date, ignoring any time
+fn parse_date(value: &str) -> Result<NaiveDate, AQIError> {
+    value
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| AQIError::parse(format!("Invalid date: {}", value)))
+}
+
+/// Reads an HL7 date and time to the minute or finer, ignoring any time zone
+fn parse_date_time(value: &str) -> Result<NaiveDateTime, AQIError> {
+    let local = value.split(['+', '-']).next().unwrap_or("");
+    let (whole, fraction) = match local.find('.') {
+        Some(dot) => (&local[..dot], &local[dot..]),
+        None => (local, ""),
+    };
+
+    let time = match whole.len() {
+        12 => NaiveDateTime::parse_from_str(&format!("{}00", whole), "%Y%m%d%H%M%S").ok(),
+        14 => NaiveDateTime::parse_from_str(whole, "%Y%m%d%H%M%S").ok(),
+        _ => None,
+    };
+    let fraction = match fraction.get(1..) {
+        Some(digits) if !digits.is_empty() => format!("{:0<9}", digits)
+            .get(..9)
+            .and_then(|nanos| nanos.parse::<i64>().ok()),
+        _ => Some(0),
+    };
+
+    match (time, fraction) {
+        (Some(time), Some(nanos)) => Ok(time + chrono::Duration::nanoseconds(nanos)),
+        _ => Err(AQIError::parse(format!("Invalid date and time: {}", value))),
+    }
+}
+
+fn read_pid(visit: &mut Visit, pid: &Segment, fields: &mut Fields) {
+    let dob = fields.date(pid, 7);
+    let patient_sex = fields.read(pid, 8, pid.component(8, 1), |sex| {
+        Ok(match sex {
+            "M" => PatientSexCodeType::Male,
+            "F" => PatientSexCodeType::Female,
+            _ => PatientSexCodeType::Unknown,
+        })
+    });
+    update!(visit, patient_id: pid.component(3, 1), dob: dob, patient_sex: patient_sex);
+
+    // The home address if it's marked, otherwise the first
+    let addresses = pid.repetitions(11);
+    let address = addresses
+        .iter()
+        .find(|address| pid.component_of(address, 7).as_deref() == Some("H"))
+        .or_else(|| addresses.first());
+    if let Some(address) = address {
+        let home_state = fields.read(pid, 11, pid.component_of(address, 4), |state| {
+            state.to_uppercase().parse()
+        });
+        let home_zip = fields.read(pid, 11, pid.component_of(address, 5), |zip| {
+            // Nine-digit ZIPs are often sent without the hyphen
+            if zip.len() == 9 && zip.bytes().all(|b| b.is_ascii_digit()) {
+                ZipCodeType::from_str(&format!("{}-{}", &zip[..5], &zip[5..]))
+            } else {
+                ZipCodeType::from_str(zip)
+            }
+        });
+        update!(
+            visit,
+            home_city: pid.component_of(address, 3),
+            home_state: home_state,
+            home_zip: home_zip,
+        );
+    }
+}
+
+fn read_pv1(visit: &mut Visit, pv1: &Segment, fields: &mut Fields) {
+    let admission_status = fields.read(pv1, 2, pv1.component(2, 1), |class| match class {
+        "I" => Ok(AdmissionStatusCodeType::Inpatient),
+        "O" => Ok(AdmissionStatusCodeType::Ambulatory),
+        "U" => Ok(AdmissionStatusCodeType::Unknown),
+        _ => Err(AQIError::parse(format!(
+            "No AdmissionStatus for patient class {}",
+            class
+        ))),
+    });
+    let proc_status = fields.read(
+        pv1,
+        4,
+        pv1.component(4, 1),
+        |admission_type| match admission_type {
+            "C" | "R" => Ok(ProcStatusCodeType::Elective),
+            "E" => Ok(ProcStatusCodeType::Emergency),
+            "U" => Ok(ProcStatusCodeType::Urgent),
+            _ => Err(AQIError::parse(format!(
+                "No ProcStatus for admission type {}",
+                admission_type
+            ))),
+        },
+    );
+    let admission_date = fields.date_time(pv1, 44, 1);
+
+    update!(
+        visit,
+        facility_id: pv1.component(3, 4),
+        admission_status: admission_status,
+        proc_status: proc_status,
+        admission_date: admission_date,
+    );
+}
+
+fn read_sch(visit: &mut Visit, sch: &Segment, fields: &mut Fields) {
+    let proc_start_time = fields.date_time(sch, 11, 4);
+    let proc_end_time = fields.date_time(sch, 11, 5);
+
+    update!(
+        visit,
+        procedure_id: sch.component(2, 1).or_else(|| sch.component(1, 1)),
+        proc_start_time: proc_start_time,
+        proc_end_time: proc_end_time,
+    );
+}
+
+fn read_ais(visit: &mut Visit, ais: &Segment, fields: &mut Fields) {
+    let system = ais.component(3, 3);
+    if is_cpt_system(system.as_deref()) {
+        let value = fields.read(ais, 3, ais.component(3, 1), CPTValueType::from_str);
+        if let Some(value) = value {
+            add_cpt(visit, value, None, ais.component(3, 2));
+        }
+    }
+
+    if visit.proc_start_time.is_none() {
+        visit.proc_start_time = fields.date_time(ais, 4, 1);
+    }
+}
+
+fn read_ft1(visit: &mut Visit, ft1: &Segment, fields: &mut Fields) {
+    if let Some(npi) = fields.npi(ft1, 20) {
+        add_staff(visit, npi);
+    }
+
+    if !is_cpt_system(ft1.component(25, 3).as_deref()) {
+        return;
+    }
+    let value = match fields.read(ft1, 25, ft1.component(25, 1), CPTValueType::from_str) {
+        Some(value) => value,
+        None => return,
+    };
+
+    let mut modifiers = ft1.repetitions(26).into_iter();
+    let modifier = modifiers.next().and_then(|modifier| {
+        fields.read(ft1, 26, ft1.component_of(modifier, 1), |modifier| {
+            CPTModifierType::from_str(modifier)
+        })
+    });
+    if modifiers.next().is_some() {
+        fields.error(
+            "FT1-26",
+            AQIError::parse("Only the first modifier of a CPT code is kept"),
+        );
+    }
+
+    if ft1.component(6, 1).as_deref() == Some("CR") {
+        remove_cpt(visit, &value, modifier.as_ref());
+    } else {
+        add_cpt(visit, value, modifier, ft1.component(25, 2));
+    }
+}
+
+/// Whether a coding system names CPT, assuming CPT if there's none
+fn is_cpt_system(system: Option<&str>) -> bool {
+    match system {
+        Some(system) => matches!(
+            system.to_uppercase().as_str(),
+            "CPT" | "CPT4" | "CPT-4" | "C4"
+        ),
+        None => true,
+    }
+}
+
+fn is_anesthesia_cpt(value: &CPTValueType) -> bool {
+    value
+        .value()
+        .parse::<u32>()
+        .is_ok_and(|code| (100..=1999).contains(&code))
+}
+
+fn same_modifier(a: Option<&CPTModifierType>, b: Option<&CPTModifierType>) -> bool {
+    a.map(SchemaStringType::value) == b.map(SchemaStringType::value)
+}
+
+/// Adds a CPT code unless the visit already has it
+fn add_cpt(
+    visit: &mut Visit,
+    value: CPTValueType,
+    modifier: Option<CPTModifierType>,
+    description: Option<String>,
+) {
+    if is_anesthesia_cpt(&value) {
+        let exists = visit.cpt_anes.iter().any(|cpt| {
+            cpt.cpt_anes_value.value() == value.value()
+                && same_modifier(cpt.cpt_anes_modifier.as_ref(), modifier.as_ref())
+        });
+        if !exists {
+            visit.cpt_anes.push(CPTAnesType {
+                cpt_anes_value: value,
+                cpt_anes_modifier: modifier,
+                cpt_anes_description: description,
+            });
+        }
+    } else {
+        let exists = visit.cpts.iter().any(|cpt| {
+            cpt.cpt_value.value() == value.value()
+                && same_modifier(cpt.cpt_modifier.as_ref(), modifier.as_ref())
+        });
+        if !exists {
+            visit.cpts.push(CPTType {
+                cpt_rank: None,
+                cpt_value: value,
+                cpt_modifier: modifier,
+            });
+        }
+    }
+}
+
+fn remove_cpt(visit: &mut Visit, value: &CPTValueType, modifier: Option<&CPTModifierType>) {
+    visit.cpt_anes.retain(|cpt| {
+        cpt.cpt_anes_value.value() != value.value()
+            || !same_modifier(cpt.cpt_anes_modifier.as_ref(), modifier)
+    });
+    visit.cpts.retain(|cpt| {
+        cpt.cpt_value.value() != value.value()
+            || !same_modifier(cpt.cpt_modifier.as_ref(), modifier)
+    });
+}
+
+fn add_staff(visit: &mut Visit, npi: NPIType) {
+    if !visit
+        .staff_npis
+        .iter()
+        .any(|staff| staff.value() == npi.value())
+    {
+        visit.staff_npis.push(npi);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADMISSION: &str = concat!(
+        "\u{0b}MSH|^~\\&|ADT|HOSP|AQI|NACOR|202003031430||ADT^A04|MSG1|P|2.5\r",
+        "PID|1||MRN1^^^HOSP^MR||Doe^John||19300506|M|||PO Box 1^^Chicago^IL^60601^^M~123 Main St^^Milwaukee^wi^532261234^^H\r",
+        "PV1|1|I|OR^1^A^F1|R|||||||||||||||V100|||||||||||||||||||||||||202003031430\r",
+        "\u{1c}\r",
+    );
+
+    const CHARGES: &str = concat!(
+        "MSH|^~\\&|BILL|HOSP|AQI|NACOR|202003051200||DFT^P03|MSG2|P|2.5\n",
+        "PID|1||MRN1^^^HOSP^MR\n",
+        "PV1|1|I|||||||||||||||||V100\n",
+        "FT1|1|||20200304||CG||||||||||||||1234567890^Doe^Jane^^^^^^NPI|||||27447^Total knee^CPT|RT\n",
+        "FT1|1|||20200304||CG||||||||||||||1234567890^Doe^Jane^^^^^^NPI|||||64447^Nerve block^CPT\n",
+        "FT1|1|||20200304||CR||||||||||||||1234567890^Doe^Jane^^^^^^NPI|||||64447^Nerve block^CPT\n",
+        "FT1|1|||20200304||CG||||||||||||||1234567890^Doe^Jane^^^^^^NPI|||||01402^Knee anesthesia^CPT\n",
+    );
+
+    #[test]
+    fn parses_segments_and_fields() {
+        let messages = parse_messages(ADMISSION).unwrap();
+        assert_eq!(messages.len(), 1);
+
+        let message = &messages[0];
+        assert_eq!(message.message_type().as_deref(), Some("ADT"));
+        assert_eq!(message.trigger_event().as_deref(), Some("A04"));
+        assert_eq!(message.control_id().as_deref(), Some("MSG1"));
+        assert_eq!(message.segments.len(), 3);
+
+        let pid = message.segment("PID").unwrap();
+        assert_eq!(pid.field(7), Some("19300506"));
+        assert_eq!(pid.component(5, 2).as_deref(), Some("John"));
+        assert_eq!(pid.repetitions(11).len(), 2);
+        assert_eq!(pid.field(12), None);
+    }
+
+    #[test]
+    fn unescapes_components() {
+        let messages = parse_messages("MSH|^~\\&|A\rNTE|1||Knee \\T\\ hip\\F\\left^x").unwrap();
+        let nte = messages[0].segment("NTE").unwrap();
+
+        assert_eq!(nte.component(3, 1).as_deref(), Some("Knee & hip|left"));
+    }
+
+    #[test]
+    fn rejects_segments_before_the_first_msh() {
+        assert!(parse_messages("PID|1||MRN1\rMSH|^~\\&|A").is_err());
+    }
+
+    #[test]
+    fn correlates_pid_pv1_and_ft1() {
+        let messages = parse_messages(&format!("{}{}", ADMISSION, CHARGES)).unwrap();
+        let correlated = correlate(&messages);
+        assert!(correlated.errors.is_empty(), "{:?}", correlated.errors);
+        assert_eq!(correlated.records.len(), 1);
+
+        let record = correlated.records.into_iter().next().unwrap();
+        assert_eq!(record.key, CorrelationKey::Visit("V100".to_string()));
+        assert_eq!(record.messages, vec![0, 1]);
+        assert_eq!(record.staff_npis.len(), 1);
+        assert_eq!(record.staff_npis[0].value(), "1234567890");
+
+        let demographic = record.demographic.build().unwrap();
+        assert_eq!(demographic.patient_id.as_deref(), Some("MRN1"));
+        assert_eq!(demographic.dob, NaiveDate::from_ymd_opt(1930, 5, 6));
+        assert!(matches!(demographic.patient_sex, PatientSexCodeType::Male));
+        assert_eq!(demographic.home_city.as_deref(), Some("Milwaukee"));
+        assert!(matches!(demographic.home_state, Some(USStateCodeType::WI)));
+        assert_eq!(demographic.home_zip.unwrap().value(), "53226-1234");
+
+        let procedure = record.procedure.build().unwrap();
+        assert_eq!(procedure.facility_id, "F1");
+        assert!(matches!(
+            procedure.admission_status,
+            Some(AdmissionStatusCodeType::Inpatient)
+        ));
+        assert!(matches!(
+            procedure.proc_status,
+            ProcStatusCodeType::Elective
+        ));
+        assert_eq!(
+            procedure.admission_date,
+            NaiveDate::from_ymd_opt(2020, 3, 3).and_then(|date| date.and_hms_opt(14, 30, 0))
+        );
+
+        // The credited nerve block is gone, and the anesthesia code is kept apart
+        let cpt = procedure.cpt_set.unwrap().cpt;
+        assert_eq!(cpt.len(), 1);
+        assert_eq!(cpt[0].cpt_value.value(), "27447");
+        assert_eq!(cpt[0].cpt_modifier.as_ref().unwrap().value(), "RT");
+
+        let method = submission().anesthesia_records[0]
+            .anesthesia_case
+            .anesthesia_method_set
+            .anesthesia_method[0]
+            .clone();
+        let staff = AnesthesiaStaffBuilder::new()
+            .tax_id(TaxIdType::from_str("123456789").unwrap())
+            .npi(record.staff_npis[0].clone())
+            .provider_credentials(ProviderCredentialsCodeType::Anesthesiologist)
+            .build()
+            .unwrap();
+        let anesthesia_case = record
+            .anesthesia_case
+            .anesthesia_record_id("R1")
+            .add_anesthesia_staff(staff)
+            .add_anesthesia_method(method)
+            .build()
+            .unwrap();
+        let cpt_anes = anesthesia_case.cpt_anes_set.unwrap().cpt_anes;
+        assert_eq!(cpt_anes.len(), 1);
+        assert_eq!(cpt_anes[0].cpt_anes_value.value(), "01402");
+    }
+
+    #[test]
+    fn reports_unsupported_messages_and_invalid_fields() {
+        let feed = concat!(
+            "MSH|^~\\&|LAB|HOSP|AQI|NACOR|202003051200||ORU^R01|MSG3|P|2.5\r",
+            "PID|1||MRN1\r",
+            "MSH|^~\\&|ADT|HOSP|AQI|NACOR|202003051200||ADT^A08|MSG4|P|2.5\r",
+            "PID|1||MRN1||Doe^John||1930\r",
+        );
+        let correlated = correlate(&parse_messages(feed).unwrap());
+
+        let errors: Vec<_> = correlated
+            .errors
+            .iter()
+            .map(|err| (err.message, err.error.path.as_str()))
+            .collect();
+        assert_eq!(errors, vec![(0, "MSH-9"), (1, "PID-7")]);
+        assert_eq!(correlated.records.len(), 1);
+        assert_eq!(
+            correlated.records[0].key,
+            CorrelationKey::Patient("MRN1".to_string())
+        );
+    }
+
+    #[test]
+    fn keeps_visit_numbers_and_patient_ids_apart() {
+        let feed = concat!(
+            "MSH|^~\\&|ADT|HOSP|AQI|NACOR|202003031430||ADT^A04|MSG1|P|2.5\r",
+            "PID|1||100||Doe^John||19300506|M\r",
+            "MSH|^~\\&|ADT|HOSP|AQI|NACOR|202003031430||ADT^A04|MSG2|P|2.5\r",
+            "PID|1||MRN2||Roe^Jane||19500102\r",
+            "PV1|1|O|||||||||||||||||100\r",
+        );
+        let correlated = correlate(&parse_messages(feed).unwrap());
+        assert!(correlated.errors.is_empty(), "{:?}", correlated.errors);
+
+        let keys: Vec<_> = correlated
+            .records
+            .iter()
+            .map(|record| record.key.to_string())
+            .collect();
+        assert_eq!(keys, vec!["patient 100", "visit 100"]);
+
+        // The second message has no PID-8, which stays missing
+        let demographic = correlated.records[1].demographic.clone().build();
+        assert_eq!(
+            demographic.unwrap_err().paths,
+            vec!["PatientSex".to_string()]
+        );
+    }
+}
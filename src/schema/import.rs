@@ -0,0 +1,1128 @@
+//! # CSV import
+//! Builds records from flat CSV extracts, as described by a `Mapping`
+//!
+//! Rows are grouped into cases by a key column, so a single case can span
+//! many rows. Single-valued elements may repeat across a case's rows as long
+//! as they agree, while each row with values for a repeated element, such as
+//! a staff member or a CPT code, adds one element to its set. Extracts that
+//! join several sets, with a row for every combination, can list repeated
+//! elements as `distinct` to keep only one of each combination of values.
+//! Values are read the same way as from XML, so codes need to be schema
+//! values unless the mapping translates them.
+//!
+//! A mapping in TOML looks like
+//!
+//! ```toml
+//! case_column = "CaseNumber"
+//! distinct = ["AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff"]
+//!
+//! [fields]
+//! "AnesthesiaCase/AnesthesiaRecordID" = "CaseNumber"
+//! "Procedure/FacilityID" = { value = "F1" }
+//! "Demographic/DOB" = { column = "BirthDate", format = "%m/%d/%Y" }
+//! "Demographic/PatientSex" = { column = "Sex", values = { M = "Male", F = "Female" } }
+//! "AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff/NPI" = "ProviderNPI"
+//! ```
+//!
+//! and the same structure works in YAML. Reading mappings from TOML and YAML
+//! needs the `toml` and `yaml` features. `mappable_fields` lists every
+//! element that can be mapped.
+
+use super::builder::*;
+use super::elements::{self, Kind};
+use super::reader::{ReadableValue, DATE_FORMAT, DATE_TIME_FORMAT};
+use super::validate::child_path;
+use super::*;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+#[cfg(any(feature = "toml", feature = "yaml"))]
+use std::fs;
+use std::io::Read;
+#[cfg(any(feature = "toml", feature = "yaml"))]
+use std::path::Path;
+
+/// The elements `read_case` builds, relative to the record, whose simple
+/// elements can be mapped
+const PARENTS: &[&str] = &[
+    "Demographic",
+    "Procedure",
+    "Procedure/CPTSet/CPT",
+    "AnesthesiaCase",
+    "AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff",
+    "AnesthesiaCase/AnesthesiaMethodSet/AnesthesiaMethod",
+    "PreOp",
+    "PreOp/ICDSet/ICD",
+    "IntraOp/MedicationsSet/Medication",
+    "PostOp",
+    "PostOp/PaymentMethod",
+    "PostOp/ICDSet/ICD",
+];
+
+/// Elements that can be mapped, all with the same parent
+struct Parent {
+    path: &'static str,
+    /// Whether the parent repeats within a case
+    repeated: bool,
+    elements: Vec<(&'static str, Kind)>,
+}
+
+lazy_static! {
+    static ref FIELDS: Vec<Parent> = PARENTS.iter().map(|&path| parent(path)).collect();
+}
+
+/// Looks up the parent at `path` in the schema
+fn parent(path: &'static str) -> Parent {
+    let mut complex = elements::complex_type("AnesthesiaRecordType");
+    let mut repeated = false;
+
+    for name in path.split('/') {
+        let element = complex
+            .elements
+            .iter()
+            .find(|element| element.name == name)
+            .unwrap_or_else(|| panic!("{} has no {}", complex.name, name));
+        match element.kind {
+            Kind::Complex(type_name) => complex = elements::complex_type(type_name),
+            _ => panic!("{} in {} isn't a complex element", name, path),
+        }
+        repeated = element.repeated;
+    }
+
+    Parent {
+        path,
+        repeated,
+        elements: complex
+            .elements
+            .iter()
+            .filter(|element| !matches!(element.kind, Kind::Complex(_)))
+            .map(|element| (element.name, element.kind))
+            .collect(),
+    }
+}
+
+/// Every element path that can be used as a key in `Mapping::fields`
+pub fn mappable_fields() -> impl Iterator<Item = String> {
+    FIELDS.iter().flat_map(|parent| {
+        parent
+            .elements
+            .iter()
+            .map(move |&(element, _)| child_path(parent.path, element))
+    })
+}
+
+/// Which CSV columns fill which elements
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Mapping {
+    /// Rows with the same value in this column belong to the same case,
+    /// defaults to the column mapped to `AnesthesiaCase/AnesthesiaRecordID`
+    #[serde(default)]
+    pub case_column: Option<String>,
+    /// Repeated elements, such as `Procedure/CPTSet/CPT`, that are kept once
+    /// for each distinct combination of values rather than once for each row
+    #[serde(default)]
+    pub distinct: BTreeSet<String>,
+    /// Element paths, as listed by `mappable_fields`, and where their values come from
+    pub fields: BTreeMap<String, Field>,
+}
+
+/// Where a single element's value comes from
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(from = "FieldSource")]
+pub struct Field {
+    /// The column to read, mutually exclusive with `value`
+    pub column: Option<String>,
+    /// A constant used for every case
+    pub value: Option<String>,
+    /// A chrono format string for dates and date times that aren't in the
+    /// schema's format
+    pub format: Option<String>,
+    /// Values to translate before reading, e.g. local codes to schema values
+    pub values: BTreeMap<String, String>,
+}
+
+/// A field is either just a column name or the full table
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FieldSource {
+    Column(String),
+    Full {
+        #[serde(default)]
+        column: Option<String>,
+        #[serde(default)]
+        value: Option<String>,
+        #[serde(default)]
+        format: Option<String>,
+        #[serde(default)]
+        values: BTreeMap<String, String>,
+    },
+}
+
+impl From<FieldSource> for Field {
+    fn from(source: FieldSource) -> Field {
+        match source {
+            FieldSource::Column(column) => Field {
+                column: Some(column),
+                ..Default::default()
+            },
+            FieldSource::Full {
+                column,
+                value,
+                format,
+                values,
+            } => Field {
+                column,
+                value,
+                format,
+                values,
+            },
+        }
+    }
+}
+
+impl Mapping {
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Mapping, AQIError> {
+        toml::from_str(source).map_err(|err| AQIError::parse(format!("Invalid mapping: {}", err)))
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(source: &str) -> Result<Mapping, AQIError> {
+        serde_yaml_ng::from_str(source)
+            .map_err(|err| AQIError::parse(format!("Invalid mapping: {}", err)))
+    }
+
+    /// Reads a `.toml`, `.yaml` or `.yml` mapping file, for whichever of the
+    /// `toml` and `yaml` features are enabled
+    #[cfg(any(feature = "toml", feature = "yaml"))]
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Mapping, AQIError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            #[cfg(feature = "toml")]
+            Some("toml") => Mapping::from_toml(&source),
+            #[cfg(feature = "yaml")]
+            Some("yaml") | Some("yml") => Mapping::from_yaml(&source),
+            _ => Err(AQIError::parse(format!(
+                "Unknown mapping format: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Resolves every field against the CSV header
+    fn bind(&self, headers: &csv::StringRecord) -> Result<Vec<Binding<'_>>, AQIError> {
+        let column_index = |column: &str| {
+            headers
+                .iter()
+                .position(|header| header == column)
+                .ok_or_else(|| AQIError::parse(format!("No {} column in the CSV", column)))
+        };
+
+        for path in &self.distinct {
+            if !FIELDS
+                .iter()
+                .any(|parent| parent.repeated && parent.path == path)
+            {
+                return Err(AQIError::parse(format!(
+                    "{} isn't a repeated element that can be mapped",
+                    path
+                )));
+            }
+        }
+
+        self.fields
+            .iter()
+            .map(|(path, field)| {
+                let (parent, element, kind) = FIELDS
+                    .iter()
+                    .flat_map(|parent| {
+                        parent
+                            .elements
+                            .iter()
+                            .map(move |&(element, kind)| (parent.path, element, kind))
+                    })
+                    .find(|&(parent, element, _)| child_path(parent, element) == *path)
+                    .ok_or_else(|| AQIError::parse(format!("{} can't be mapped", path)))?;
+
+                let source = match (&field.column, &field.value) {
+                    (Some(column), None) => Source::Column(column_index(column)?, column),
+                    (None, Some(value)) => Source::Constant(value),
+                    _ => {
+                        return Err(AQIError::parse(format!(
+                            "{} needs exactly one of column and value",
+                            path
+                        )))
+                    }
+                };
+
+                if field.format.is_some() && kind == Kind::Text {
+                    return Err(AQIError::parse(format!(
+                        "{} isn't a date, it can't have a format",
+                        path
+                    )));
+                }
+
+                Ok(Binding {
+                    parent,
+                    element,
+                    kind,
+                    source,
+                    field,
+                })
+            })
+            .collect()
+    }
+
+    fn case_column(&self) -> Result<&str, AQIError> {
+        self.case_column
+            .as_deref()
+            .or_else(|| {
+                self.fields
+                    .get("AnesthesiaCase/AnesthesiaRecordID")
+                    .and_then(|field| field.column.as_deref())
+            })
+            .ok_or_else(|| {
+                AQIError::parse(
+                    "The mapping needs a case_column or a column for AnesthesiaCase/AnesthesiaRecordID",
+                )
+            })
+    }
+}
+
+/// A problem with a single row, or with the case that starts at it
+#[derive(Debug)]
+pub struct RowError {
+    /// Line number in the CSV, counting the header as line 1
+    pub row: usize,
+    /// The column the value came from, if there is one
+    pub column: Option<String>,
+    /// `path` is the element's path relative to the record
+    pub error: AQIError,
+}
+
+/// Records read from a CSV, along with every row that couldn't be used
+///
+/// Cases with any errors are left out of `records`.
+#[derive(Debug)]
+pub struct Imported {
+    pub records: Vec<AnesthesiaRecordType>,
+    pub errors: Vec<RowError>,
+}
+
+/// Reads records from CSV with a header row, as described by `mapping`
+///
+/// Problems with the mapping or the header are returned as an error, while
+/// problems with individual rows and cases end up in `Imported::errors`.
+pub fn import<R: Read>(source: R, mapping: &Mapping) -> Result<Imported, AQIError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(source);
+    let headers = reader
+        .headers()
+        .map_err(|err| AQIError::parse(format!("Invalid CSV header: {}", err)))?
+        .clone();
+    let bindings = mapping.bind(&headers)?;
+    let case_column = mapping.case_column()?;
+    let case_index = headers
+        .iter()
+        .position(|header| header == case_column)
+        .ok_or_else(|| AQIError::parse(format!("No {} column in the CSV", case_column)))?;
+
+    let mut errors = Vec::new();
+    let mut cases: Vec<Vec<(usize, csv::StringRecord)>> = Vec::new();
+    let mut case_indices = HashMap::new();
+
+    for result in reader.records() {
+        let row = match result {
+            Ok(row) => row,
+            Err(err) => {
+                errors.push(RowError {
+                    row: err
+                        .position()
+                        .map_or(0, |position| position.line() as usize),
+                    column: None,
+                    error: AQIError::parse(err.to_string()),
+                });
+                continue;
+            }
+        };
+        let line = row
+            .position()
+            .map_or(0, |position| position.line() as usize);
+
+        match row.get(case_index) {
+            Some(key) if !key.is_empty() => {
+                let index = *case_indices.entry(key.to_string()).or_insert_with(|| {
+                    cases.push(Vec::new());
+                    cases.len() - 1
+                });
+                cases[index].push((line, row));
+            }
+            _ => errors.push(RowError {
+                row: line,
+                column: Some(case_column.to_string()),
+                error: AQIError::parse("Row has no case key"),
+            }),
+        }
+    }
+
+    let records = cases
+        .iter()
+        .filter_map(|rows| read_case(rows, &bindings, &mapping.distinct, &mut errors))
+        .collect();
+    errors.sort_by_key(|error| error.row);
+
+    Ok(Imported { records, errors })
+}
+
+enum Source<'a> {
+    Column(usize, &'a str),
+    Constant(&'a str),
+}
+
+struct Binding<'a> {
+    parent: &'static str,
+    element: &'static str,
+    kind: Kind,
+    source: Source<'a>,
+    field: &'a Field,
+}
+
+impl<'a> Binding<'a> {
+    fn raw<'r>(&self, row: &'r csv::StringRecord) -> Option<&'r str>
+    where
+        'a: 'r,
+    {
+        let raw = match self.source {
+            Source::Column(index, _) => row.get(index).unwrap_or(""),
+            Source::Constant(value) => value,
+        };
+
+        if raw.is_empty() {
+            None
+        } else {
+            Some(raw)
+        }
+    }
+
+    fn column(&self) -> Option<String> {
+        match self.source {
+            Source::Column(_, column) => Some(column.to_string()),
+            Source::Constant(_) => None,
+        }
+    }
+
+    /// Reads `raw` after translating it and converting its date format
+    fn read<T: ReadableValue>(&self, raw: &str) -> Result<T, AQIError> {
+        let raw = self
+            .field
+            .values
+            .get(raw)
+            .map(String::as_str)
+            .unwrap_or(raw);
+
+        match (self.kind, &self.field.format) {
+            (Kind::Date, Some(format)) => NaiveDate::parse_from_str(raw, format)
+                .map_err(|_| AQIError::parse(format!("Invalid date: {}", raw)))
+                .and_then(|date| T::read_value(&date.format(DATE_FORMAT).to_string())),
+            (Kind::DateTime, Some(format)) => NaiveDateTime::parse_from_str(raw, format)
+                .map_err(|_| AQIError::parse(format!("Invalid date time: {}", raw)))
+                .and_then(|time| T::read_value(&time.format(DATE_TIME_FORMAT).to_string())),
+            _ => T::read_value(raw),
+        }
+    }
+}
+
+/// A value for one element, and the row it came from
+struct Value<'a, 'b> {
+    raw: String,
+    row: usize,
+    binding: &'b Binding<'a>,
+}
+
+/// The values of one element, or one of a repeated element, within a case
+struct Item<'a, 'b> {
+    row: usize,
+    values: BTreeMap<&'static str, Value<'a, 'b>>,
+}
+
+/// The items of one case, and the errors found while reading them
+struct Case<'a, 'b> {
+    first_row: usize,
+    items: HashMap<&'static str, Vec<Item<'a, 'b>>>,
+    errors: Vec<RowError>,
+}
+
+impl<'a, 'b> Case<'a, 'b> {
+    fn count(&self, parent: &str) -> usize {
+        self.items.get(parent).map_or(0, Vec::len)
+    }
+
+    /// Whether any row had a value for `element`, or for any of its
+    /// elements if it's a repeated parent
+    fn has_value(&self, parent: &str, element: &str) -> bool {
+        self.count(&child_path(parent, element)) > 0
+            || self
+                .items
+                .get(parent)
+                .is_some_and(|items| items.iter().any(|item| item.values.contains_key(element)))
+    }
+
+    /// The values of `parent`, or of its `index`th item if it repeats
+    fn fields(&mut self, parent: &str, index: Option<usize>) -> Fields<'a, 'b, '_> {
+        let item = self
+            .items
+            .get(parent)
+            .and_then(|items| items.get(index.unwrap_or(0)));
+
+        Fields {
+            path: match index {
+                Some(i) => format!("{}[{}]", parent, i),
+                None => parent.to_string(),
+            },
+            row: item.map_or(self.first_row, |item| item.row),
+            item,
+            errors: &mut self.errors,
+        }
+    }
+}
+
+/// Reads the values of a single item, recording any errors
+struct Fields<'a, 'b, 'c> {
+    path: String,
+    item: Option<&'c Item<'a, 'b>>,
+    row: usize,
+    errors: &'c mut Vec<RowError>,
+}
+
+impl<'a, 'b, 'c> Fields<'a, 'b, 'c> {
+    fn optional<T: ReadableValue>(&mut self, element: &str) -> Option<T> {
+        let value = self.item?.values.get(element)?;
+
+        match value.binding.read(&value.raw) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.errors.push(RowError {
+                    row: value.row,
+                    column: value.binding.column(),
+                    error: err.in_element(&child_path(&self.path, element)),
+                });
+                None
+            }
+        }
+    }
+
+    fn required<T: ReadableValue>(&mut self, element: &str) -> Option<T> {
+        if self
+            .item
+            .is_some_and(|item| item.values.contains_key(element))
+        {
+            return self.optional(element);
+        }
+
+        self.errors.push(RowError {
+            row: self.row,
+            column: None,
+            error: missing_element(&self.path, element),
+        });
+        None
+    }
+}
+
+fn missing_element(path: &str, element: &str) -> AQIError {
+    let error = AQIError::new(ErrorKind::Cardinality {
+        element: element.to_string(),
+        min: 1,
+        max: None,
+        found: 0,
+    });
+
+    if path.is_empty() {
+        error
+    } else {
+        error.in_element(path)
+    }
+}
+
+/// Collects the values of every binding in `rows`, merging single-valued
+/// elements and keeping each repeated one, or each distinct one for parents
+/// in `distinct`
+fn collect_items<'a, 'b>(
+    rows: &[(usize, csv::StringRecord)],
+    bindings: &'b [Binding<'a>],
+    distinct: &BTreeSet<String>,
+) -> Case<'a, 'b> {
+    let mut case = Case {
+        first_row: rows[0].0,
+        items: HashMap::new(),
+        errors: Vec::new(),
+    };
+
+    for &Parent {
+        path: parent,
+        repeated,
+        ..
+    } in FIELDS.iter()
+    {
+        let bindings: Vec<_> = bindings
+            .iter()
+            .filter(|binding| binding.parent == parent)
+            .collect();
+        let mut items = Vec::new();
+
+        if repeated {
+            let distinct = distinct.contains(parent);
+            let mut seen = HashSet::new();
+            for (row, record) in rows {
+                let from_columns = bindings.iter().any(|binding| {
+                    matches!(binding.source, Source::Column(..)) && binding.raw(record).is_some()
+                });
+                if !from_columns {
+                    continue;
+                }
+
+                let values: BTreeMap<_, _> = bindings
+                    .iter()
+                    .filter_map(|&binding| {
+                        binding.raw(record).map(|raw| {
+                            let value = Value {
+                                raw: raw.to_string(),
+                                row: *row,
+                                binding,
+                            };
+                            (binding.element, value)
+                        })
+                    })
+                    .collect();
+
+                if distinct {
+                    let key: Vec<_> = values
+                        .iter()
+                        .map(|(element, value)| (*element, value.raw.clone()))
+                        .collect();
+                    if !seen.insert(key) {
+                        continue;
+                    }
+                }
+                items.push(Item { row: *row, values });
+            }
+
+            // Mapped only to constants, which give the case a single element
+            let constant = !bindings.is_empty()
+                && bindings
+                    .iter()
+                    .all(|binding| matches!(binding.source, Source::Constant(_)));
+            if constant {
+                let values = bindings
+                    .iter()
+                    .filter_map(|&binding| {
+                        binding.raw(&rows[0].1).map(|raw| {
+                            let value = Value {
+                                raw: raw.to_string(),
+                                row: case.first_row,
+                                binding,
+                            };
+                            (binding.element, value)
+                        })
+                    })
+                    .collect();
+                items.push(Item {
+                    row: case.first_row,
+                    values,
+                });
+            }
+        } else {
+            let mut item = Item {
+                row: case.first_row,
+                values: BTreeMap::new(),
+            };
+            for (row, record) in rows {
+                for &binding in &bindings {
+                    let raw = match binding.raw(record) {
+                        Some(raw) => raw,
+                        None => continue,
+                    };
+
+                    match item.values.get(binding.element) {
+                        Some(existing) if existing.raw != raw => case.errors.push(RowError {
+                            row: *row,
+                            column: binding.column(),
+                            error: AQIError::parse(format!(
+                                "{:?} conflicts with {:?} from line {}",
+                                raw, existing.raw, existing.row
+                            ))
+                            .in_element(&child_path(parent, binding.element)),
+                        }),
+                        Some(_) => {}
+                        None => {
+                            let value = Value {
+                                raw: raw.to_string(),
+                                row: *row,
+                                binding,
+                            };
+                            item.values.insert(binding.element, value);
+                        }
+                    }
+                }
+            }
+            items.push(item);
+        }
+
+        case.items.insert(parent, items);
+    }
+
+    case
+}
+
+/// Sets each value that's present and valid on the builder
+macro_rules! set {
+    ($builder:ident, $fields:ident, $($element:expr => $setter:ident: $TYPE:ty),* $(,)*) => {
+        $(
+            if let Some(value) = $fields.optional::<$TYPE>($element) {
+                $builder = $builder.$setter(value);
+            }
+        )*
+    };
+}
+
+/// Builds the record for a single case, adding its errors to `errors`
+fn read_case(
+    rows: &[(usize, csv::StringRecord)],
+    bindings: &[Binding],
+    distinct: &BTreeSet<String>,
+    errors: &mut Vec<RowError>,
+) -> Option<AnesthesiaRecordType> {
+    let mut case = collect_items(rows, bindings, distinct);
+
+    let mut demographic = DemographicBuilder::new();
+    {
+        let mut fields = case.fields("Demographic", None);
+        set!(demographic, fields,
+            "PatientID" => patient_id: String,
+            "DOB" => dob: NaiveDate,
+            "HomeZip" => home_zip: ZipCodeType,
+            "HomeState" => home_state: USStateCodeType,
+            "HomeCity" => home_city: String,
+            "Race" => race: RaceCodeType,
+            "PatientSex" => patient_sex: PatientSexCodeType,
+        );
+    }
+
+    let mut procedure = ProcedureBuilder::new();
+    {
+        let mut fields = case.fields("Procedure", None);
+        set!(procedure, fields,
+            "ProcedureID" => procedure_id: String,
+            "FacilityID" => facility_id: String,
+            "ProcStartTime" => proc_start_time: NaiveDateTime,
+            "ProcEndTime" => proc_end_time: NaiveDateTime,
+            "AdmissionStatus" => admission_status: AdmissionStatusCodeType,
+            "ProcStatus" => proc_status: ProcStatusCodeType,
+            "TransferStatus" => transfer_status: TransferStatusCodeType,
+            "AdmissionDate" => admission_date: NaiveDateTime,
+            "ProcedureNotes" => procedure_notes: String,
+        );
+    }
+    for i in 0..case.count("Procedure/CPTSet/CPT") {
+        if let Some(cpt) = read_cpt(&mut case.fields("Procedure/CPTSet/CPT", Some(i))) {
+            procedure = procedure.add_cpt(cpt);
+        }
+    }
+
+    let mut anesthesia_case = AnesthesiaCaseBuilder::new();
+    {
+        let mut fields = case.fields("AnesthesiaCase", None);
+        set!(anesthesia_case, fields,
+            "AnesthesiaRecordID" => anesthesia_record_id: String,
+            "AnesthesiaCoverage" => anesthesia_coverage: CoverageCodeType,
+        );
+    }
+    let parent = "AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff";
+    for i in 0..case.count(parent) {
+        if let Some(staff) = read_staff(&mut case.fields(parent, Some(i))) {
+            anesthesia_case = anesthesia_case.add_anesthesia_staff(staff);
+        }
+    }
+    let parent = "AnesthesiaCase/AnesthesiaMethodSet/AnesthesiaMethod";
+    for i in 0..case.count(parent) {
+        if let Some(method) = read_method(&mut case.fields(parent, Some(i))) {
+            anesthesia_case = anesthesia_case.add_anesthesia_method(method);
+        }
+    }
+
+    let mut pre_op = PreOpBuilder::new();
+    {
+        let mut fields = case.fields("PreOp", None);
+        set!(pre_op, fields,
+            "Age" => age: u64,
+            "Weight" => weight: DecimalType,
+            "WeightInKg" => weight_in_kg: DecimalType,
+            "Height" => height: DecimalType,
+            "HeightInCm" => height_in_cm: DecimalType,
+            "ASAClass" => asa_class: ASAClassCodeType,
+            "PreAnesthStatus" => pre_anesth_status: PreAnesthStatusCodeType,
+        );
+    }
+    for i in 0..case.count("PreOp/ICDSet/ICD") {
+        if let Some(icd) = read_icd(&mut case.fields("PreOp/ICDSet/ICD", Some(i))) {
+            pre_op = pre_op.add_icd(icd);
+        }
+    }
+
+    let mut intra_op = IntraOpBuilder::new();
+    let parent = "IntraOp/MedicationsSet/Medication";
+    for i in 0..case.count(parent) {
+        if let Some(medication) = read_medication(&mut case.fields(parent, Some(i))) {
+            intra_op = intra_op.add_medication(medication);
+        }
+    }
+
+    let mut post_op = PostOpBuilder::new();
+    {
+        let mut fields = case.fields("PostOp", None);
+        set!(post_op, fields,
+            "PostOpDisposition" => post_op_disposition: PostOpDispositionCodeType,
+            "PostOpDispDateTime" => post_op_disp_date_time: NaiveDateTime,
+            "PostOpDischarge" => post_op_discharge: PostOpDischargeCodeType,
+            "PostOpDischargeDateTime" => post_op_discharge_date_time: NaiveDateTime,
+            "LengthOfHospitalStay" => length_of_hospital_stay: u64,
+        );
+    }
+    for i in 0..case.count("PostOp/PaymentMethod") {
+        if let Some(payment) =
+            read_payment_method(&mut case.fields("PostOp/PaymentMethod", Some(i)))
+        {
+            post_op = post_op.add_payment_method(payment);
+        }
+    }
+    for i in 0..case.count("PostOp/ICDSet/ICD") {
+        if let Some(icd) = read_icd(&mut case.fields("PostOp/ICDSet/ICD", Some(i))) {
+            post_op = post_op.add_icd(icd);
+        }
+    }
+
+    let record = AnesthesiaRecordBuilder::new()
+        .demographic(demographic)
+        .procedure(procedure)
+        .anesthesia_case(anesthesia_case)
+        .pre_op(pre_op)
+        .intra_op(intra_op)
+        .post_op(post_op)
+        .build();
+
+    if let Err(ref missing) = record {
+        for path in &missing.paths {
+            let (parent, element) = match path.rfind('/') {
+                Some(i) => (&path[..i], &path[i + 1..]),
+                None => ("", path.as_str()),
+            };
+            // Values that were there but invalid have been reported already
+            if case.has_value(parent, element) {
+                continue;
+            }
+
+            case.errors.push(RowError {
+                row: case.first_row,
+                column: None,
+                error: missing_element(parent, element),
+            });
+        }
+    }
+
+    let clean = case.errors.is_empty();
+    errors.append(&mut case.errors);
+
+    match record {
+        Ok(record) if clean => Some(record),
+        _ => None,
+    }
+}
+
+fn read_cpt(fields: &mut Fields) -> Option<CPTType> {
+    let cpt_value = fields.required("CPTValue");
+
+    Some(CPTType {
+        cpt_rank: fields.optional("CPTRank"),
+        cpt_value: cpt_value?,
+        cpt_modifier: fields.optional("CPTModifier"),
+    })
+}
+
+fn read_staff(fields: &mut Fields) -> Option<AnesthesiaStaffType> {
+    let tax_id = fields.required("TaxID");
+    let npi = fields.required("NPI");
+    let provider_credentials = fields.required("ProviderCredentials");
+
+    Some(AnesthesiaStaffType {
+        tax_id: tax_id?,
+        npi: npi?,
+        staff_responsibility: fields.optional("StaffResponsibility"),
+        provider_credentials: provider_credentials?,
+        staff_sign_in: fields.optional("StaffSignIn"),
+        staff_sign_out: fields.optional("StaffSignOut"),
+        staff_notes: fields.optional("StaffNotes"),
+    })
+}
+
+fn read_method(fields: &mut Fields) -> Option<AnesthesiaMethodType> {
+    let anesthesia_category = fields.required("AnesthesiaCategory");
+    let anesthesia_start_time = fields.required("AnesthesiaStartTime");
+    let anesthesia_end_time = fields.required("AnesthesiaEndTime");
+
+    Some(AnesthesiaMethodType {
+        anesthesia_category: anesthesia_category?,
+        anesthesia_subcategory: fields.optional("AnesthesiaSubCategory"),
+        anesthesia_start_time: anesthesia_start_time?,
+        anesthesia_end_time: anesthesia_end_time?,
+        anesthesia_induction: fields.optional("AnesthesiaInduction"),
+        anesthesia_induction_start_time: fields.optional("AnesthesiaInductionStartTime"),
+        anesthesia_maintenance: fields.optional("AnesthesiaMaintenance"),
+        anesthesia_notes: fields.optional("AnesthesiaNotes"),
+    })
+}
+
+fn read_icd(fields: &mut Fields) -> Option<ICDType> {
+    let icd_value = fields.required("ICDValue");
+    let icd_version = fields.required("ICDVersion");
+
+    Some(ICDType {
+        icd_rank: fields.optional("ICDRank"),
+        icd_value: icd_value?,
+        icd_version: icd_version?,
+    })
+}
+
+fn read_medication(fields: &mut Fields) -> Option<MedicationType> {
+    let medication_name = fields.required("MedicationName");
+
+    Some(MedicationType {
+        medication_name: medication_name?,
+        medication_type: fields.optional("MedicationType").map(|value| vec![value]),
+        med_dose: fields.optional("MedDose"),
+        dose_units: fields.optional("DoseUnits"),
+        dose_start: fields.optional("DoseStart"),
+        dose_end: fields.optional("DoseEnd"),
+        med_concentration: fields.optional("MedConcentration"),
+        med_concentration_unit: fields.optional("MedConcentrationUnit"),
+        medication_route: fields.optional("MedicationRoute").map(|value| vec![value]),
+        mixture_medications: None,
+    })
+}
+
+fn read_payment_method(fields: &mut Fields) -> Option<PaymentMethodType> {
+    let payment_code = fields.required("PaymentCode");
+
+    Some(PaymentMethodType {
+        payment_code: payment_code?,
+        payment_description: fields.optional("PaymentDescription"),
+        payment_description_sec: fields.optional("PaymentDescriptionSec"),
+        payment_description_th: fields.optional("PaymentDescriptionTh"),
+    })
+}
+
+#[cfg(all(test, feature = "toml"))]
+mod tests {
+    use super::*;
+
+    const MAPPING: &str = r#"
+        case_column = "Case"
+        distinct = [
+            "Procedure/CPTSet/CPT",
+            "AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff",
+            "AnesthesiaCase/AnesthesiaMethodSet/AnesthesiaMethod",
+            "PostOp/PaymentMethod",
+        ]
+
+        [fields]
+        "AnesthesiaCase/AnesthesiaRecordID" = "Case"
+        "Demographic/PatientID" = "MRN"
+        "Demographic/DOB" = { column = "DOB", format = "%m/%d/%Y" }
+        "Demographic/PatientSex" = { column = "Sex", values = { M = "Male", F = "Female" } }
+        "Procedure/FacilityID" = { value = "F1" }
+        "Procedure/ProcStatus" = { value = "Elective" }
+        "Procedure/CPTSet/CPT/CPTValue" = "CPT"
+        "AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff/TaxID" = { value = "123456789" }
+        "AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff/NPI" = "NPI"
+        "AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff/ProviderCredentials" = "Role"
+        "AnesthesiaCase/AnesthesiaMethodSet/AnesthesiaMethod/AnesthesiaCategory" = { value = "General Anesthesia" }
+        "AnesthesiaCase/AnesthesiaMethodSet/AnesthesiaMethod/AnesthesiaStartTime" = { column = "Start", format = "%Y-%m-%d %H:%M" }
+        "AnesthesiaCase/AnesthesiaMethodSet/AnesthesiaMethod/AnesthesiaEndTime" = { column = "End", format = "%Y-%m-%d %H:%M" }
+        "PreOp/Age" = "Age"
+        "PreOp/ASAClass" = "ASA"
+        "PostOp/PaymentMethod/PaymentCode" = "Payment"
+    "#;
+
+    const HEADER: &str = "Case,MRN,DOB,Sex,NPI,Role,Start,End,CPT,Age,ASA,Payment\n";
+
+    #[test]
+    fn groups_rows_into_cases() {
+        let csv = HEADER.to_string()
+            + "C1,MRN1,05/06/1930,M,1234567890,Anesthesiologist,2020-03-04 07:50,2020-03-04 10:05,27447,89,II,Commercial\n"
+            + "C2,MRN2,01/02/1950,F,1234567890,Anesthesiologist,2020-03-05 08:00,2020-03-05 09:00,01402,70,I,Commercial\n"
+            + "C1,MRN1,05/06/1930,M,2345678901,Certified Registered Nurse Anesthetist,2020-03-04 07:50,2020-03-04 10:05,27447,89,II,Commercial\n"
+            + "C1,MRN1,05/06/1930,M,1234567890,Anesthesiologist,2020-03-04 07:50,2020-03-04 10:05,01402,89,II,Commercial\n";
+        let imported = import(csv.as_bytes(), &Mapping::from_toml(MAPPING).unwrap()).unwrap();
+
+        assert!(imported.errors.is_empty(), "{:?}", imported.errors);
+        assert_eq!(imported.records.len(), 2);
+
+        let record = &imported.records[0];
+        assert_eq!(record.anesthesia_case.anesthesia_record_id, "C1");
+        assert_eq!(record.demographic.dob, NaiveDate::from_ymd_opt(1930, 5, 6));
+        assert_eq!(record.demographic.patient_sex, PatientSexCodeType::Male);
+        assert_eq!(
+            record
+                .anesthesia_case
+                .anesthesia_staff_set
+                .anesthesia_staff
+                .iter()
+                .map(|staff| staff.npi.value())
+                .collect::<Vec<_>>(),
+            vec!["1234567890", "2345678901"]
+        );
+        assert_eq!(
+            record
+                .procedure
+                .cpt_set
+                .as_ref()
+                .unwrap()
+                .cpt
+                .iter()
+                .map(|cpt| cpt.cpt_value.value())
+                .collect::<Vec<_>>(),
+            vec!["27447", "01402"]
+        );
+        assert_eq!(
+            record
+                .anesthesia_case
+                .anesthesia_method_set
+                .anesthesia_method
+                .len(),
+            1
+        );
+
+        assert_eq!(
+            imported.records[1].anesthesia_case.anesthesia_record_id,
+            "C2"
+        );
+        assert_eq!(
+            imported.records[1].demographic.patient_sex,
+            PatientSexCodeType::Female
+        );
+    }
+
+    #[test]
+    fn keeps_every_row_of_elements_that_arent_distinct() {
+        let mapping = MAPPING.replace(r#""Procedure/CPTSet/CPT","#, "");
+        let csv = HEADER.to_string()
+            + "C1,MRN1,05/06/1930,M,1234567890,Anesthesiologist,2020-03-04 07:50,2020-03-04 10:05,01402,89,II,Commercial\n"
+            + "C1,MRN1,05/06/1930,M,2345678901,Certified Registered Nurse Anesthetist,2020-03-04 07:50,2020-03-04 10:05,01402,89,II,Commercial\n";
+        let imported = import(csv.as_bytes(), &Mapping::from_toml(&mapping).unwrap()).unwrap();
+
+        assert!(imported.errors.is_empty(), "{:?}", imported.errors);
+        let record = &imported.records[0];
+        assert_eq!(record.procedure.cpt_set.as_ref().unwrap().cpt.len(), 2);
+        assert_eq!(
+            record
+                .anesthesia_case
+                .anesthesia_staff_set
+                .anesthesia_staff
+                .len(),
+            2
+        );
+        assert_eq!(record.post_op.payment_method.len(), 1);
+    }
+
+    #[test]
+    fn reports_conflicts_and_rows_without_a_case() {
+        let csv = HEADER.to_string()
+            + "C1,MRN1,05/06/1930,M,1234567890,Anesthesiologist,2020-03-04 07:50,2020-03-04 10:05,27447,89,II,Commercial\n"
+            + "C1,MRN1,05/07/1930,M,1234567890,Anesthesiologist,2020-03-04 07:50,2020-03-04 10:05,27447,89,II,Commercial\n"
+            + ",MRN2,01/02/1950,F,1234567890,Anesthesiologist,2020-03-05 08:00,2020-03-05 09:00,01402,70,I,Commercial\n"
+            + "C3,MRN3,01/02/1950,F,1234567890,Anesthesiologist,2020-03-05 08:00,2020-03-05 09:00,01402,70,,Commercial\n";
+        let imported = import(csv.as_bytes(), &Mapping::from_toml(MAPPING).unwrap()).unwrap();
+
+        assert!(imported.records.is_empty());
+        assert_eq!(
+            imported
+                .errors
+                .iter()
+                .map(|error| (error.row, error.column.as_deref(), error.error.to_string()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    3,
+                    Some("DOB"),
+                    "Demographic/DOB: \"05/07/1930\" conflicts with \"05/06/1930\" from line 2"
+                        .to_string()
+                ),
+                (4, Some("Case"), "Row has no case key".to_string()),
+                (
+                    5,
+                    None,
+                    "PreOp: expected at least 1 ASAClass, found 0".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_repeated_elements_to_constants() {
+        let mapping = MAPPING.replace(
+            r#""PostOp/PaymentMethod/PaymentCode" = "Payment""#,
+            r#""PostOp/PaymentMethod/PaymentCode" = { value = "Charity" }"#,
+        );
+        let csv = HEADER.to_string()
+            + "C1,MRN1,05/06/1930,M,1234567890,Anesthesiologist,2020-03-04 07:50,2020-03-04 10:05,27447,89,II,Commercial\n"
+            + "C1,MRN1,05/06/1930,M,2345678901,Anesthesiologist,2020-03-04 07:50,2020-03-04 10:05,27447,89,II,Commercial\n";
+        let imported = import(csv.as_bytes(), &Mapping::from_toml(&mapping).unwrap()).unwrap();
+
+        assert!(imported.errors.is_empty(), "{:?}", imported.errors);
+        let payment_method = &imported.records[0].post_op.payment_method;
+        assert_eq!(payment_method.len(), 1);
+        assert_eq!(
+            payment_method[0].payment_code,
+            PaymentMethodCodeType::Charity
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_columns() {
+        let mapping = Mapping::from_toml("[fields]\n\"PreOp/Nope\" = \"Case\"").unwrap();
+        let err = import(HEADER.as_bytes(), &mapping).unwrap_err();
+        assert_eq!(err.to_string(), "PreOp/Nope can't be mapped");
+
+        let mapping =
+            Mapping::from_toml("distinct = [\"PreOp\"]\n[fields]\n\"PreOp/Age\" = \"Case\"")
+                .unwrap();
+        let err = import(HEADER.as_bytes(), &mapping).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "PreOp isn't a repeated element that can be mapped"
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn reads_yaml_mappings() {
+        let mapping =
+            Mapping::from_yaml("fields:\n  AnesthesiaCase/AnesthesiaRecordID: Missing\n").unwrap();
+        let err = import(HEADER.as_bytes(), &mapping).unwrap_err();
+        assert_eq!(err.to_string(), "No Missing column in the CSV");
+    }
+
+    #[test]
+    fn lists_the_simple_elements_of_each_parent() {
+        let fields: Vec<_> = mappable_fields().collect();
+
+        assert_eq!(fields.len(), 67);
+        assert!(fields.contains(&"Demographic/DOB".to_string()));
+        assert!(fields.contains(&"PostOp/PaymentMethod/PaymentDescriptionTh".to_string()));
+        assert!(!fields.contains(&"Procedure/CPTSet".to_string()));
+        assert!(FIELDS
+            .iter()
+            .any(|parent| parent.path == "PostOp/PaymentMethod" && parent.repeated));
+    }
+}
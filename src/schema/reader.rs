@@ -0,0 +1,1225 @@
+extern crate xml;
+
+use self::xml::attribute::OwnedAttribute;
+use self::xml::reader::{EventReader, XmlEvent};
+use super::*;
+
+use chrono::DateTime;
+use std::io::Read;
+
+pub(crate) const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+pub(crate) const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// An element read into memory along with its text content and child elements
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub text: String,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    /// Reads the next element from `reader`, skipping anything before its start tag
+    pub fn read<R: Read>(reader: &mut EventReader<R>) -> Result<Element, AQIError> {
+        loop {
+            match reader.next()? {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    return Element::read_contents(name.local_name, attributes, reader);
+                }
+                XmlEvent::EndElement { name } => {
+                    return Err(AQIError::parse(format!(
+                        "Unexpected end of element {}",
+                        name.local_name
+                    )));
+                }
+                XmlEvent::EndDocument => {
+                    return Err(AQIError::parse("Unexpected end of document".to_string()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn read_contents<R: Read>(
+        name: String,
+        attributes: Vec<OwnedAttribute>,
+        reader: &mut EventReader<R>,
+    ) -> Result<Element, AQIError> {
+        let mut element = Element {
+            name,
+            attributes: attributes
+                .into_iter()
+                .map(|attribute| (attribute.name.local_name, attribute.value))
+                .collect(),
+            text: String::new(),
+            children: Vec::new(),
+        };
+
+        loop {
+            match reader.next()? {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    let child = Element::read_contents(name.local_name, attributes, reader)?;
+                    element.children.push(child);
+                }
+                XmlEvent::Characters(text) | XmlEvent::CData(text) | XmlEvent::Whitespace(text) => {
+                    element.text.push_str(&text);
+                }
+                XmlEvent::EndElement { .. } => return Ok(element),
+                XmlEvent::EndDocument => {
+                    return Err(AQIError::parse(format!(
+                        "Unexpected end of document in {}",
+                        element.name
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.0 == name)
+            .map(|attribute| attribute.1.as_str())
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+/// Reads a complete document into `AnesthesiaRecordsType`
+pub fn read_document<R: Read>(source: R) -> Result<AnesthesiaRecordsType, AQIError> {
+    let mut records = RecordReader::new(source)?;
+    let anesthesia_records = records.by_ref().collect::<Result<Vec<_>, _>>()?;
+
+    Ok(AnesthesiaRecordsType {
+        record_header: records.record_header,
+        anesthesia_records,
+    })
+}
+
+/// Reads a document one `AnesthesiaRecord` at a time
+///
+/// The `RecordHeader` is read up front, after which records are parsed on
+/// demand so only a single record is held in memory. Error paths start with
+/// the offending record, e.g. `AnesthesiaRecord[3]/PreOp/ASAClass`.
+pub struct RecordReader<R: Read> {
+    reader: EventReader<R>,
+    record_header: RecordHeaderType,
+    index: usize,
+    finished: bool,
+}
+
+impl<R: Read> RecordReader<R> {
+    pub fn new(source: R) -> Result<RecordReader<R>, AQIError> {
+        let mut reader = EventReader::new(source);
+
+        loop {
+            match reader.next()? {
+                XmlEvent::StartElement { name, .. } if name.local_name == "AnesthesiaRecords" => {
+                    break
+                }
+                XmlEvent::StartElement { name, .. } => {
+                    return Err(AQIError::parse(format!(
+                        "Expected AnesthesiaRecords, found {}",
+                        name.local_name
+                    )));
+                }
+                XmlEvent::EndDocument => {
+                    return Err(AQIError::parse("Missing root element".to_string()));
+                }
+                _ => {}
+            }
+        }
+
+        let header = Element::read(&mut reader)?;
+        if header.name != "RecordHeader" {
+            return Err(AQIError::parse(format!(
+                "Expected RecordHeader, found {}",
+                header.name
+            )));
+        }
+
+        Ok(RecordReader {
+            record_header: RecordHeaderType::read(&header)
+                .map_err(|err| err.in_element("RecordHeader"))?,
+            reader,
+            index: 0,
+            finished: false,
+        })
+    }
+
+    pub fn record_header(&self) -> &RecordHeaderType {
+        &self.record_header
+    }
+
+    /// The next record, or an error for it or for whatever is in its place
+    fn next_record(&mut self) -> Result<Option<AnesthesiaRecordType>, AQIError> {
+        loop {
+            match self.reader.next()? {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    let element =
+                        Element::read_contents(name.local_name, attributes, &mut self.reader)?;
+
+                    if element.name != "AnesthesiaRecord" {
+                        return Err(AQIError::parse(format!(
+                            "Expected AnesthesiaRecord, found {}",
+                            element.name
+                        )));
+                    }
+
+                    self.index += 1;
+                    return AnesthesiaRecordType::read(&element).map(Some);
+                }
+                XmlEvent::EndElement { .. } | XmlEvent::EndDocument => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for RecordReader<R> {
+    type Item = Result<AnesthesiaRecordType, AQIError>;
+
+    /// Errors for elements other than `AnesthesiaRecord` among the records
+    /// carry the index of the record expected in their place, and don't
+    /// count as a record themselves
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let index = self.index;
+
+        match self.next_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(err) => {
+                // Malformed XML leaves the reader in an unknown state, but
+                // invalid values only affect the record they're in
+                if let ErrorKind::XmlParse(_) = err.kind {
+                    self.finished = true;
+                }
+
+                Some(Err(err.in_element(&indexed("AnesthesiaRecord", index))))
+            }
+        }
+    }
+}
+
+fn indexed(name: &str, index: usize) -> String {
+    format!("{}[{}]", name, index)
+}
+
+pub trait ReadableSchemaType: Sized {
+    fn read(element: &Element) -> Result<Self, AQIError>;
+}
+
+pub(crate) trait ReadableValue: Sized {
+    fn read_value(value: &str) -> Result<Self, AQIError>;
+}
+
+fn missing_element(name: &str) -> AQIError {
+    AQIError::new(ErrorKind::Cardinality {
+        element: name.to_string(),
+        min: 1,
+        max: Some(1),
+        found: 0,
+    })
+}
+
+fn read_child<T: ReadableSchemaType>(element: &Element, name: &str) -> Result<T, AQIError> {
+    match element.child(name) {
+        Some(child) => T::read(child).map_err(|err| err.in_element(name)),
+        None => Err(missing_element(name)),
+    }
+}
+
+fn read_optional_child<T: ReadableSchemaType>(
+    element: &Element,
+    name: &str,
+) -> Result<Option<T>, AQIError> {
+    element
+        .child(name)
+        .map(|child| T::read(child).map_err(|err| err.in_element(name)))
+        .transpose()
+}
+
+fn read_children<T: ReadableSchemaType>(element: &Element, name: &str) -> Result<Vec<T>, AQIError> {
+    element
+        .children(name)
+        .enumerate()
+        .map(|(i, child)| T::read(child).map_err(|err| err.in_element(&indexed(name, i))))
+        .collect()
+}
+
+fn read_optional_children<T: ReadableSchemaType>(
+    element: &Element,
+    name: &str,
+) -> Result<Option<Vec<T>>, AQIError> {
+    let children = read_children(element, name)?;
+
+    Ok(if children.is_empty() {
+        None
+    } else {
+        Some(children)
+    })
+}
+
+fn read_value<T: ReadableValue>(element: &Element, name: &str) -> Result<T, AQIError> {
+    match element.child(name) {
+        Some(child) => T::read_value(&child.text).map_err(|err| err.in_element(name)),
+        None => Err(missing_element(name)),
+    }
+}
+
+fn read_optional_value<T: ReadableValue>(
+    element: &Element,
+    name: &str,
+) -> Result<Option<T>, AQIError> {
+    element
+        .child(name)
+        .map(|child| T::read_value(&child.text).map_err(|err| err.in_element(name)))
+        .transpose()
+}
+
+fn read_values<T: ReadableValue>(element: &Element, name: &str) -> Result<Vec<T>, AQIError> {
+    element
+        .children(name)
+        .enumerate()
+        .map(|(i, child)| {
+            T::read_value(&child.text).map_err(|err| err.in_element(&indexed(name, i)))
+        })
+        .collect()
+}
+
+fn read_optional_values<T: ReadableValue>(
+    element: &Element,
+    name: &str,
+) -> Result<Option<Vec<T>>, AQIError> {
+    let values = read_values(element, name)?;
+
+    Ok(if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    })
+}
+
+impl ReadableValue for String {
+    fn read_value(value: &str) -> Result<String, AQIError> {
+        Ok(value.to_string())
+    }
+}
+
+impl ReadableValue for u64 {
+    fn read_value(value: &str) -> Result<u64, AQIError> {
+        value
+            .trim()
+            .parse()
+            .map_err(|_| AQIError::parse(format!("Invalid integer: {}", value)))
+    }
+}
+
+impl ReadableValue for u8 {
+    fn read_value(value: &str) -> Result<u8, AQIError> {
+        value
+            .trim()
+            .parse()
+            .map_err(|_| AQIError::parse(format!("Invalid integer: {}", value)))
+    }
+}
+
+impl ReadableValue for bool {
+    fn read_value(value: &str) -> Result<bool, AQIError> {
+        match value.trim() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(AQIError::parse(format!("Invalid boolean: {}", value))),
+        }
+    }
+}
+
+impl ReadableValue for NaiveDateTime {
+    fn read_value(value: &str) -> Result<NaiveDateTime, AQIError> {
+        let value = value.trim();
+
+        NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT)
+            .or_else(|_| DateTime::parse_from_rfc3339(value).map(|dt| dt.naive_local()))
+            .map_err(|_| AQIError::parse(format!("Invalid date time: {}", value)))
+    }
+}
+
+impl ReadableValue for NaiveDate {
+    fn read_value(value: &str) -> Result<NaiveDate, AQIError> {
+        NaiveDate::parse_from_str(value.trim(), DATE_FORMAT)
+            .map_err(|_| AQIError::parse(format!("Invalid date: {}", value)))
+    }
+}
+
+macro_rules! readable_enums {
+    ($($NAME:ident),*) => {
+        $(
+            impl ReadableValue for $NAME {
+                fn read_value(value: &str) -> Result<$NAME, AQIError> {
+                    value.trim().parse()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! readable_pattern_types {
+    ($($NAME:ident),*) => {
+        $(
+            impl ReadableValue for $NAME {
+                fn read_value(value: &str) -> Result<$NAME, AQIError> {
+                    <$NAME as SchemaRegexInput>::from_str(value.trim())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! readable_open_enums {
+    ($($NAME:ident),*) => {
+        $(
+            impl ReadableValue for $NAME {
+                fn read_value(value: &str) -> Result<$NAME, AQIError> {
+                    Ok($NAME::from_value(value))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! readable_string_tuple_structs {
+    ($($NAME:ident),*) => {
+        $(
+            impl ReadableValue for $NAME {
+                fn read_value(value: &str) -> Result<$NAME, AQIError> {
+                    Ok($NAME(value.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+readable_enums!(
+    USStateCodeType,
+    RaceCodeType,
+    PatientSexCodeType,
+    AdmissionStatusCodeType,
+    ProcStatusCodeType,
+    TransferStatusCodeType,
+    CoverageCodeType,
+    StaffResponsibilityCodeType,
+    ProviderCredentialsCodeType,
+    AnesthesiaCategoryCodeType,
+    AnesthesiaSubCategoryCodeType,
+    ASAClassCodeType,
+    PreAnesthStatusCodeType,
+    ICDVersionType,
+    PostOpDischargeCodeType,
+    PaymentMethodCodeType,
+    ICCategoryCodeType,
+    LabDataCategoryCodeType,
+    MonitoringSourceCodeType,
+    OutcomeTimeFrameCodeType,
+    OutcomeSeverityCodeType,
+    IntakeOutputDirectionCodeType,
+    IntubationApproachCodeType,
+    AnesthesiaDetailsCategoryCodeType,
+    QCDRModifierType,
+    AQIXMLVersionType,
+    LocationTypeCodeType,
+    OutcomeIDType
+);
+
+readable_pattern_types!(
+    PracticeIdType,
+    VendorIDType,
+    EmailAddressType,
+    ZipCodeType,
+    TaxIdType,
+    NPIType,
+    CPTValueType,
+    CPTModifierType,
+    ICDValueType,
+    QCDRMeasureType,
+    QCDRCodeValueType,
+    DecimalType
+);
+
+readable_open_enums!(
+    MonitorCodeType,
+    AnesthesiaInductionCodeType,
+    AnesthesiaMaintenanceCodeType,
+    AirwayManagementMethodCodeType,
+    AirwayManagementSubMethodCodeType,
+    MedicationTypeCodeType,
+    RouteCodeType,
+    OutputCodeType,
+    PostOpDispositionCodeType,
+    TimingMilestoneCodeType
+);
+
+readable_string_tuple_structs!(LabDataNameCodeType, CommonUnit, MonitoringNameCodeType);
+
+impl ReadableSchemaType for AnesthesiaRecordsType {
+    fn read(element: &Element) -> Result<AnesthesiaRecordsType, AQIError> {
+        Ok(AnesthesiaRecordsType {
+            record_header: read_child(element, "RecordHeader")?,
+            anesthesia_records: read_children(element, "AnesthesiaRecord")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for RecordHeaderType {
+    fn read(element: &Element) -> Result<RecordHeaderType, AQIError> {
+        Ok(RecordHeaderType {
+            practice_id: read_value(element, "PracticeID")?,
+            created_by: read_value(element, "CreatedBy")?,
+            create_date: read_value(element, "CreateDate")?,
+            email_set: read_child(element, "EmailSet")?,
+            aqi_xml_version: read_value(element, "AQIXMLVersion")?,
+            vendor_set: read_optional_child(element, "VendorSet")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for EmailSetType {
+    fn read(element: &Element) -> Result<EmailSetType, AQIError> {
+        Ok(EmailSetType {
+            email_notification_set: read_children(element, "EmailNotificationSet")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for EmailNotificationSetType {
+    fn read(element: &Element) -> Result<EmailNotificationSetType, AQIError> {
+        Ok(EmailNotificationSetType {
+            email_notification_first_name: read_value(element, "EmailNotificationFirstName")?,
+            email_notification_last_name: read_value(element, "EmailNotificationLastName")?,
+            email_notification_address: read_value(element, "EmailNotificationAddress")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for VendorSetType {
+    fn read(element: &Element) -> Result<VendorSetType, AQIError> {
+        Ok(VendorSetType {
+            vendor: read_children(element, "Vendor")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for Vendors {
+    fn read(element: &Element) -> Result<Vendors, AQIError> {
+        Ok(Vendors {
+            vendor_id: read_optional_value(element, "VendorID")?,
+            vendor_set_type: read_child(element, "VendorSetType")?,
+            vendor_name: read_value(element, "VendorName")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for SetVendorSetType {
+    fn read(element: &Element) -> Result<SetVendorSetType, AQIError> {
+        Ok(SetVendorSetType {
+            vendor_type: read_values(element, "VendorType")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AnesthesiaRecordType {
+    fn read(element: &Element) -> Result<AnesthesiaRecordType, AQIError> {
+        Ok(AnesthesiaRecordType {
+            demographic: read_child(element, "Demographic")?,
+            procedure: read_child(element, "Procedure")?,
+            anesthesia_case: read_child(element, "AnesthesiaCase")?,
+            pre_op: read_child(element, "PreOp")?,
+            intra_op: read_child(element, "IntraOp")?,
+            post_op: read_child(element, "PostOp")?,
+            timing_milestones: read_optional_child(element, "TimingMilestones")?,
+            outcomes_events: read_optional_child(element, "OutcomesEvents")?,
+            anesthesia_details: read_optional_child(element, "AnesthesiaDetails")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for DemographicType {
+    fn read(element: &Element) -> Result<DemographicType, AQIError> {
+        Ok(DemographicType {
+            patient_id: read_optional_value(element, "PatientID")?,
+            dob: read_optional_value(element, "DOB")?,
+            home_zip: read_optional_value(element, "HomeZip")?,
+            home_state: read_optional_value(element, "HomeState")?,
+            home_city: read_optional_value(element, "HomeCity")?,
+            race: read_optional_value(element, "Race")?,
+            patient_sex: read_value(element, "PatientSex")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for ProcedureType {
+    fn read(element: &Element) -> Result<ProcedureType, AQIError> {
+        Ok(ProcedureType {
+            procedure_id: read_optional_value(element, "ProcedureID")?,
+            facility_id: read_value(element, "FacilityID")?,
+            procedure_location: read_optional_child(element, "ProcedureLocation")?,
+            proc_start_time: read_optional_value(element, "ProcStartTime")?,
+            proc_end_time: read_optional_value(element, "ProcEndTime")?,
+            admission_status: read_optional_value(element, "AdmissionStatus")?,
+            proc_status: read_value(element, "ProcStatus")?,
+            transfer_status: read_optional_value(element, "TransferStatus")?,
+            admission_date: read_optional_value(element, "AdmissionDate")?,
+            procedure_notes: read_optional_value(element, "ProcedureNotes")?,
+            cpt_set: read_optional_child(element, "CPTSet")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for ProcedureLocationType {
+    fn read(element: &Element) -> Result<ProcedureLocationType, AQIError> {
+        Ok(ProcedureLocationType {
+            location_type: read_value(element, "LocationType")?,
+            location_details: read_value(element, "LocationDetails")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for CPTSetType {
+    fn read(element: &Element) -> Result<CPTSetType, AQIError> {
+        Ok(CPTSetType {
+            cpt: read_children(element, "CPT")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for CPTType {
+    fn read(element: &Element) -> Result<CPTType, AQIError> {
+        Ok(CPTType {
+            cpt_rank: read_optional_value(element, "CPTRank")?,
+            cpt_value: read_value(element, "CPTValue")?,
+            cpt_modifier: read_optional_value(element, "CPTModifier")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AnesthesiaCaseType {
+    fn read(element: &Element) -> Result<AnesthesiaCaseType, AQIError> {
+        Ok(AnesthesiaCaseType {
+            anesthesia_record_id: read_value(element, "AnesthesiaRecordID")?,
+            anesthesia_coverage: read_optional_value(element, "AnesthesiaCoverage")?,
+            anesthesia_staff_set: read_child(element, "AnesthesiaStaffSet")?,
+            monitoring_set: read_optional_child(element, "MonitoringSet")?,
+            anesthesia_method_set: read_child(element, "AnesthesiaMethodSet")?,
+            airway_management_set: read_optional_child(element, "AirwayManagementSet")?,
+            cpt_anes_set: read_optional_child(element, "CPTAnesSet")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AnesthesiaStaffSetType {
+    fn read(element: &Element) -> Result<AnesthesiaStaffSetType, AQIError> {
+        Ok(AnesthesiaStaffSetType {
+            anesthesia_staff: read_children(element, "AnesthesiaStaff")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AnesthesiaStaffType {
+    fn read(element: &Element) -> Result<AnesthesiaStaffType, AQIError> {
+        Ok(AnesthesiaStaffType {
+            tax_id: read_value(element, "TaxID")?,
+            npi: read_value(element, "NPI")?,
+            staff_responsibility: read_optional_value(element, "StaffResponsibility")?,
+            provider_credentials: read_value(element, "ProviderCredentials")?,
+            staff_sign_in: read_optional_value(element, "StaffSignIn")?,
+            staff_sign_out: read_optional_value(element, "StaffSignOut")?,
+            staff_notes: read_optional_value(element, "StaffNotes")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for MonitoringSetType {
+    fn read(element: &Element) -> Result<MonitoringSetType, AQIError> {
+        Ok(MonitoringSetType {
+            monitor: read_values(element, "Monitor")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AnesthesiaMethodSetType {
+    fn read(element: &Element) -> Result<AnesthesiaMethodSetType, AQIError> {
+        Ok(AnesthesiaMethodSetType {
+            anesthesia_method: read_children(element, "AnesthesiaMethod")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AnesthesiaMethodType {
+    fn read(element: &Element) -> Result<AnesthesiaMethodType, AQIError> {
+        Ok(AnesthesiaMethodType {
+            anesthesia_category: read_value(element, "AnesthesiaCategory")?,
+            anesthesia_subcategory: read_optional_value(element, "AnesthesiaSubCategory")?,
+            anesthesia_start_time: read_value(element, "AnesthesiaStartTime")?,
+            anesthesia_end_time: read_value(element, "AnesthesiaEndTime")?,
+            anesthesia_induction: read_optional_value(element, "AnesthesiaInduction")?,
+            anesthesia_induction_start_time: read_optional_value(
+                element,
+                "AnesthesiaInductionStartTime",
+            )?,
+            anesthesia_maintenance: read_optional_value(element, "AnesthesiaMaintenance")?,
+            anesthesia_notes: read_optional_value(element, "AnesthesiaNotes")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AirwayManagementSetType {
+    fn read(element: &Element) -> Result<AirwayManagementSetType, AQIError> {
+        Ok(AirwayManagementSetType {
+            airway_management: read_children(element, "AirwayManagement")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AirwayManagementType {
+    fn read(element: &Element) -> Result<AirwayManagementType, AQIError> {
+        Ok(AirwayManagementType {
+            airway_management_method: read_value(element, "AirwayManagementMethod")?,
+            airway_sub_management_method: read_optional_value(
+                element,
+                "AirwaySubManagementMethod",
+            )?,
+        })
+    }
+}
+
+impl ReadableSchemaType for CPTAnesSetType {
+    fn read(element: &Element) -> Result<CPTAnesSetType, AQIError> {
+        Ok(CPTAnesSetType {
+            cpt_anes: read_children(element, "CPTAnes")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for CPTAnesType {
+    fn read(element: &Element) -> Result<CPTAnesType, AQIError> {
+        Ok(CPTAnesType {
+            cpt_anes_value: read_value(element, "CPTAnesValue")?,
+            cpt_anes_modifier: read_optional_value(element, "CPTAnesModifier")?,
+            cpt_anes_description: read_optional_value(element, "CPTAnesDescription")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for PreOpType {
+    fn read(element: &Element) -> Result<PreOpType, AQIError> {
+        Ok(PreOpType {
+            age: read_value(element, "Age")?,
+            weight: read_optional_value(element, "Weight")?,
+            weight_in_kg: read_optional_value(element, "WeightInKg")?,
+            height: read_optional_value(element, "Height")?,
+            height_in_cm: read_optional_value(element, "HeightInCm")?,
+            asa_class: read_value(element, "ASAClass")?,
+            pre_anesth_status: read_optional_value(element, "PreAnesthStatus")?,
+            icd_set: read_optional_child(element, "ICDSet")?,
+            pre_lab_set: read_optional_child(element, "PreLabSet")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for ICDSetType {
+    fn read(element: &Element) -> Result<ICDSetType, AQIError> {
+        Ok(ICDSetType {
+            icd: read_children(element, "ICD")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for ICDType {
+    fn read(element: &Element) -> Result<ICDType, AQIError> {
+        Ok(ICDType {
+            icd_rank: read_optional_value(element, "ICDRank")?,
+            icd_value: read_value(element, "ICDValue")?,
+            icd_version: read_value(element, "ICDVersion")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for PreLabDataSetType {
+    fn read(element: &Element) -> Result<PreLabDataSetType, AQIError> {
+        Ok(PreLabDataSetType {
+            pre_lab_data: read_children(element, "PreLabData")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for LabDataType {
+    fn read(element: &Element) -> Result<LabDataType, AQIError> {
+        Ok(LabDataType {
+            lab_name: read_value(element, "LabName")?,
+            lab_category_name: read_optional_value(element, "LabCategoryName")?,
+            lab_unit: read_value(element, "LabUnit")?,
+            lab_value: read_value(element, "LabValue")?,
+            lab_value_text: read_optional_value(element, "LabValueText")?,
+            lab_range_high: read_optional_value(element, "LabRangeHigh")?,
+            lab_range_low: read_optional_value(element, "LabRangeLow")?,
+            lab_date_time: read_value(element, "LabDateTime")?,
+            lab_comments: read_optional_value(element, "LabComments")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for IntraOpType {
+    fn read(element: &Element) -> Result<IntraOpType, AQIError> {
+        Ok(IntraOpType {
+            medications_set: read_optional_child(element, "MedicationsSet")?,
+            monitoring_physiologic_set: read_optional_child(element, "MonitoringPhysiologicSet")?,
+            outputs_set: read_optional_child(element, "OutputsSet")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for MedicationsSetType {
+    fn read(element: &Element) -> Result<MedicationsSetType, AQIError> {
+        Ok(MedicationsSetType {
+            medication: read_children(element, "Medication")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for MedicationType {
+    fn read(element: &Element) -> Result<MedicationType, AQIError> {
+        Ok(MedicationType {
+            medication_name: read_value(element, "MedicationName")?,
+            medication_type: read_optional_values(element, "MedicationType")?,
+            med_dose: read_optional_value(element, "MedDose")?,
+            dose_units: read_optional_value(element, "DoseUnits")?,
+            dose_start: read_optional_value(element, "DoseStart")?,
+            dose_end: read_optional_value(element, "DoseEnd")?,
+            med_concentration: read_optional_value(element, "MedConcentration")?,
+            med_concentration_unit: read_optional_value(element, "MedConcentrationUnit")?,
+            medication_route: read_optional_values(element, "MedicationRoute")?,
+            mixture_medications: read_optional_children(element, "MixtureMedication")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for MixtureMedicationType {
+    fn read(element: &Element) -> Result<MixtureMedicationType, AQIError> {
+        Ok(MixtureMedicationType {
+            mixture_medication_name: read_value(element, "MixtureMedicationName")?,
+            mixture_medication_type: read_optional_values(element, "MixtureMedicationType")?,
+            mixture_med_dose: read_optional_value(element, "MixtureMedDose")?,
+            mixture_dose_units: read_optional_value(element, "MixtureDoseUnits")?,
+            mixture_med_concentration: read_optional_value(element, "MixtureMedConcentration")?,
+            mixture_med_concentration_unit: read_optional_value(
+                element,
+                "MixtureMedConcentrationUnit",
+            )?,
+        })
+    }
+}
+
+impl ReadableSchemaType for MonitoringPhysiologicSetType {
+    fn read(element: &Element) -> Result<MonitoringPhysiologicSetType, AQIError> {
+        Ok(MonitoringPhysiologicSetType {
+            monitoring: read_children(element, "Monitoring")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for MonitoringPhysiologicType {
+    fn read(element: &Element) -> Result<MonitoringPhysiologicType, AQIError> {
+        Ok(MonitoringPhysiologicType {
+            monitoring_name: read_value(element, "MonitoringName")?,
+            monitoring_time: read_optional_value(element, "MonitoringTime")?,
+            monitoring_units: read_optional_value(element, "MonitoringUnits")?,
+            monitoring_value_numeric: read_optional_value(element, "MonitoringValueNumeric")?,
+            monitoring_value_text: read_optional_value(element, "MonitoringValueText")?,
+            monitoring_source: read_optional_value(element, "MonitoringSource")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for OutputsSetType {
+    fn read(element: &Element) -> Result<OutputsSetType, AQIError> {
+        Ok(OutputsSetType {
+            output_event: read_children(element, "OutputEvent")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for OutputEventType {
+    fn read(element: &Element) -> Result<OutputEventType, AQIError> {
+        Ok(OutputEventType {
+            output_event_name: read_value(element, "OutputEventName")?,
+            output_units: read_optional_value(element, "OutputUnits")?,
+            output_value_numeric: read_optional_value(element, "OutputValueNumeric")?,
+            output_value_text: read_optional_value(element, "OutputValueText")?,
+            output_start_date_time: read_optional_value(element, "OutputStartDateTime")?,
+            output_end_date_time: read_optional_value(element, "OutputEndDateTime")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for PostOpType {
+    fn read(element: &Element) -> Result<PostOpType, AQIError> {
+        Ok(PostOpType {
+            post_op_disposition: read_optional_value(element, "PostOpDisposition")?,
+            post_op_disp_date_time: read_optional_value(element, "PostOpDispDateTime")?,
+            post_op_discharge: read_optional_value(element, "PostOpDischarge")?,
+            post_op_discharge_date_time: read_optional_value(element, "PostOpDischargeDateTime")?,
+            length_of_hospital_stay: read_optional_value(element, "LengthOfHospitalStay")?,
+            payment_method: read_children(element, "PaymentMethod")?,
+            post_op_lab_set: read_optional_child(element, "PostOpLabSet")?,
+            icd_set: read_optional_child(element, "ICDSet")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for PaymentMethodType {
+    fn read(element: &Element) -> Result<PaymentMethodType, AQIError> {
+        Ok(PaymentMethodType {
+            payment_code: read_value(element, "PaymentCode")?,
+            payment_description: read_optional_value(element, "PaymentDescription")?,
+            payment_description_sec: read_optional_value(element, "PaymentDescriptionSec")?,
+            payment_description_th: read_optional_value(element, "PaymentDescriptionTh")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for PostOpLabSetType {
+    fn read(element: &Element) -> Result<PostOpLabSetType, AQIError> {
+        Ok(PostOpLabSetType {
+            post_lab_data: read_children(element, "PostLabData")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for TimingMilestonesSetType {
+    fn read(element: &Element) -> Result<TimingMilestonesSetType, AQIError> {
+        Ok(TimingMilestonesSetType {
+            timing_milestone: read_children(element, "TimingMilestone")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for TimingMilestoneType {
+    fn read(element: &Element) -> Result<TimingMilestoneType, AQIError> {
+        Ok(TimingMilestoneType {
+            tm_type: read_value(element, "TMType")?,
+            tm_start_time: read_value(element, "TMStartTime")?,
+            tm_end_time: read_optional_value(element, "TMEndTime")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for OutcomesEventsType {
+    fn read(element: &Element) -> Result<OutcomesEventsType, AQIError> {
+        Ok(OutcomesEventsType {
+            ic_event_set: read_optional_child(element, "ICEventSet")?,
+            outcome_set: read_optional_child(element, "OutcomeSet")?,
+            qcdr_set: read_optional_child(element, "QCDRSet")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for ICEventSetType {
+    fn read(element: &Element) -> Result<ICEventSetType, AQIError> {
+        Ok(ICEventSetType {
+            ic_event: read_children(element, "ICEvent")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for ICEventType {
+    fn read(element: &Element) -> Result<ICEventType, AQIError> {
+        Ok(ICEventType {
+            ic_event_time_date: read_optional_value(element, "ICEventTimeDate")?,
+            ic_category: read_optional_value(element, "ICCategory")?,
+            ic_severity: read_optional_value(element, "ICSeverity")?,
+            ic_name: read_optional_value(element, "ICName")?,
+            ic_description: read_optional_value(element, "ICDescription")?,
+            ic_value: read_optional_value(element, "ICValue")?,
+            ic_notes: read_optional_value(element, "ICNotes")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for OutcomeSetSetType {
+    fn read(element: &Element) -> Result<OutcomeSetSetType, AQIError> {
+        Ok(OutcomeSetSetType {
+            outcome: read_children(element, "Outcome")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for OutcomeCodeType {
+    fn read(element: &Element) -> Result<OutcomeCodeType, AQIError> {
+        Ok(OutcomeCodeType {
+            outcome_id: read_value(element, "OutcomeID")?,
+            outcome_occurred: read_value(element, "OutcomeOccurred")?,
+            outcome_time_stamp: read_optional_value(element, "OutcomeTimeStamp")?,
+            outcome_severity: read_optional_value(element, "OutcomeSeverity")?,
+            outcome_time_frame: read_optional_value(element, "OutcomeTimeFrame")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for QCDRSetTypeSet {
+    fn read(element: &Element) -> Result<QCDRSetTypeSet, AQIError> {
+        Ok(QCDRSetTypeSet {
+            qcdr: read_children(element, "QCDR")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for QCDRSetType {
+    fn read(element: &Element) -> Result<QCDRSetType, AQIError> {
+        Ok(QCDRSetType {
+            qcdr_measure: read_value(element, "QCDRMeasure")?,
+            qcdr_code_value: read_value(element, "QCDRCodeValue")?,
+            qcdr_modifier: read_optional_value(element, "QCDRModifier")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AnesthesiaDetailsType {
+    fn read(element: &Element) -> Result<AnesthesiaDetailsType, AQIError> {
+        Ok(AnesthesiaDetailsType {
+            intake_output_set: read_optional_child(element, "IntakeOutputSet")?,
+            intubation_details: read_optional_child(element, "IntubationDetails")?,
+            anesthesia_details_set: read_optional_child(element, "AnesthesiaDetailsSet")?,
+            medications_total_set: read_optional_child(element, "MedicationsTotalSet")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for IntakeOutputSetType {
+    fn read(element: &Element) -> Result<IntakeOutputSetType, AQIError> {
+        Ok(IntakeOutputSetType {
+            intake_output_total: read_children(element, "IntakeOutputTotal")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for IntakeOutputTotalType {
+    fn read(element: &Element) -> Result<IntakeOutputTotalType, AQIError> {
+        Ok(IntakeOutputTotalType {
+            intake_output_direction: read_optional_value(element, "IntakeOutputDirection")?,
+            input_output_name: read_optional_value(element, "InputOutputName")?,
+            output_units: read_optional_value(element, "OutputUnits")?,
+            input_output_total: read_optional_value(element, "InputOutputTotal")?,
+            input_output_route: read_optional_value(element, "InputOutputRoute")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for IntubationDetailsType {
+    fn read(element: &Element) -> Result<IntubationDetailsType, AQIError> {
+        Ok(IntubationDetailsType {
+            intubation_approach: read_optional_value(element, "IntubationApproach")?,
+            intubation_attempts: read_optional_value(element, "IntubationAttempts")?,
+            tube_size: read_optional_value(element, "TubeSize")?,
+            tube_type: read_optional_value(element, "TubeType")?,
+            intubation_details_properties_set: read_optional_child(
+                element,
+                "IntubationDetailsPropertiesSet",
+            )?,
+        })
+    }
+}
+
+impl ReadableSchemaType for IntubationDetailsPropertiesType {
+    fn read(element: &Element) -> Result<IntubationDetailsPropertiesType, AQIError> {
+        Ok(IntubationDetailsPropertiesType {
+            intubation_details_properties: read_children(element, "IntubationDetailsProperties")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for IntubationDetailsPropertyType {
+    fn read(element: &Element) -> Result<IntubationDetailsPropertyType, AQIError> {
+        Ok(IntubationDetailsPropertyType {
+            intubation_property: read_value(element, "IntubationProperty")?,
+            intubation_details_property_value: read_optional_value(
+                element,
+                "IntubationDetailsPropertyValue",
+            )?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AnesthesiaDetailsSetType {
+    fn read(element: &Element) -> Result<AnesthesiaDetailsSetType, AQIError> {
+        Ok(AnesthesiaDetailsSetType {
+            anesthesia_details_data: read_children(element, "AnesthesiaDetailsData")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for AnesthesiaDetailsDataType {
+    fn read(element: &Element) -> Result<AnesthesiaDetailsDataType, AQIError> {
+        Ok(AnesthesiaDetailsDataType {
+            anesthesia_details_category: read_value(element, "AnesthesiaDetailsCategory")?,
+            anesthesia_details_value: read_optional_value(element, "AnesthesiaDetailsValue")?,
+        })
+    }
+}
+
+impl ReadableSchemaType for MedicationsTotalSetType {
+    fn read(element: &Element) -> Result<MedicationsTotalSetType, AQIError> {
+        Ok(MedicationsTotalSetType {
+            medications_totals: read_children(element, "MedicationsTotal")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_values() {
+        let records = submission();
+        assert_eq!(records.record_header.practice_id.value(), "1234");
+        assert_eq!(records.anesthesia_records.len(), 2);
+
+        let record = &records.anesthesia_records[0];
+        assert_eq!(record.demographic.patient_id.as_deref(), Some("MRN1"));
+        assert_eq!(record.demographic.dob, NaiveDate::from_ymd_opt(1930, 5, 6));
+        assert_eq!(
+            record.procedure.procedure_notes.as_deref(),
+            Some("Left knee & hip")
+        );
+        assert_eq!(
+            record.procedure.proc_start_time,
+            NaiveDate::from_ymd_opt(2020, 3, 4).and_then(|date| date.and_hms_opt(8, 0, 0))
+        );
+        assert_eq!(record.post_op.payment_method.len(), 2);
+    }
+
+    #[test]
+    fn round_trips_through_the_writer() {
+        assert_eq!(
+            submission().to_xml_string(true).unwrap(),
+            SUBMISSION.trim_end()
+        );
+    }
+
+    #[test]
+    fn reports_the_path_of_a_missing_element() {
+        let source = SUBMISSION.replacen("<ASAClass>III</ASAClass>", "", 1);
+        let err = read_document(source.as_bytes()).unwrap_err();
+
+        assert_eq!(err.path, "AnesthesiaRecord[1]/PreOp");
+        assert!(matches!(
+            err.kind,
+            ErrorKind::Cardinality {
+                ref element,
+                found: 0,
+                ..
+            } if element == "ASAClass"
+        ));
+    }
+
+    #[test]
+    fn reports_the_path_of_an_invalid_value() {
+        let source = SUBMISSION.replacen("<NPI>1234567890</NPI>", "<NPI>123</NPI>", 1);
+        let err = read_document(source.as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err.path,
+            "AnesthesiaRecord[0]/AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff[0]/NPI"
+        );
+        assert!(matches!(err.kind, ErrorKind::PatternMismatch { .. }));
+    }
+
+    fn record_ids<R: Read>(records: RecordReader<R>) -> Vec<Result<String, String>> {
+        records
+            .map(|record| {
+                record
+                    .map(|record| record.anesthesia_case.anesthesia_record_id)
+                    .map_err(|err| err.path)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn streams_records_after_the_header() {
+        let records = RecordReader::new(SUBMISSION.as_bytes()).unwrap();
+        assert_eq!(records.record_header().created_by, "Export Job");
+        assert_eq!(
+            record_ids(records),
+            vec![Ok("R1".to_string()), Ok("R2".to_string())]
+        );
+    }
+
+    #[test]
+    fn continues_past_an_invalid_record() {
+        let source = SUBMISSION.replacen("<ASAClass>II</ASAClass>", "<ASAClass>0</ASAClass>", 1);
+        let records = RecordReader::new(source.as_bytes()).unwrap();
+
+        assert_eq!(
+            record_ids(records),
+            vec![
+                Err("AnesthesiaRecord[0]/PreOp/ASAClass".to_string()),
+                Ok("R2".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn stops_at_malformed_xml() {
+        let source = SUBMISSION.replacen("</PreOp>", "</Pre>", 1);
+        let records = RecordReader::new(source.as_bytes()).unwrap();
+        let ids = record_ids(records);
+
+        assert_eq!(ids.len(), 1);
+        assert!(ids[0].is_err());
+    }
+
+    #[test]
+    fn rejects_another_root_element() {
+        let source = SUBMISSION.replace("AnesthesiaRecords", "Submission");
+
+        assert!(RecordReader::new(source.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn reports_elements_other_than_records() {
+        let source = SUBMISSION.replacen(
+            "<AnesthesiaRecord>",
+            "<Note>Resubmitted</Note><AnesthesiaRecord>",
+            1,
+        );
+        let records = RecordReader::new(source.as_bytes()).unwrap();
+
+        assert_eq!(
+            record_ids(records),
+            vec![
+                Err("AnesthesiaRecord[0]".to_string()),
+                Ok("R1".to_string()),
+                Ok("R2".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,886 @@
+//! # SQLite storage
+//! Keeps a history of submissions and their records in a SQLite database
+//!
+//! Records are stored in normalized tables that mirror the schema types:
+//! a table for every element path, named after the fields that lead to it,
+//! e.g. `procedure`, `procedure_cpt_set` and `procedure_cpt_set_cpt`, with
+//! a column for each of its simple elements, named after their fields. Each
+//! row has an `id`, and every table but the roots has the `parent_id` of the
+//! row it belongs to, along with a zero-based `position` if it repeats.
+//! Repeated simple elements, such as `anesthesia_case_monitoring_set_monitor`,
+//! get a table of their own with a `value` column.
+//!
+//! Each record is stored once, as a row of `anesthesia_record` keyed by its
+//! content hash, the SHA-256 of its `AnesthesiaRecord` XML, and each
+//! submission lists the hashes of its records in order, with its
+//! `record_header` and the tables below it stored the same way as a record's.
+//! `load_submission` reads them back into the same `AnesthesiaRecordsType`,
+//! which writes the same document as when it was saved.
+//!
+//! | Table                | Columns                                                  |
+//! |----------------------|----------------------------------------------------------|
+//! | `submissions`        | `submission_id`, `submitted_at`                          |
+//! | `record_header`      | `id`, `parent_id` (the `submission_id`), `practice_id`, `created_by`, `create_date`, `aqi_xml_version` |
+//! | `submission_records` | `submission_id`, `position`, `content_hash`              |
+//! | `anesthesia_record`  | `id`, `content_hash`                                     |
+//! | `demographic`, …     | `id`, `parent_id`, `position` if repeated, then the element's simple elements |
+//!
+//! Values are stored as text, in the same format as in XML.
+
+use super::elements::{self, Kind, SchemaElement};
+use super::reader::{Element, ReadableSchemaType, DATE_TIME_FORMAT as READ_DATE_TIME_FORMAT};
+use super::writer::{WritableSchemaType, DATE_TIME_FORMAT};
+use super::*;
+
+use rusqlite::types::ToSql;
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use xml::reader::EventReader;
+use xml::writer::EmitterConfig;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS submissions (
+    submission_id INTEGER PRIMARY KEY,
+    submitted_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS anesthesia_record (
+    id INTEGER PRIMARY KEY,
+    content_hash TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS submission_records (
+    submission_id INTEGER NOT NULL REFERENCES submissions (submission_id),
+    position INTEGER NOT NULL,
+    content_hash TEXT NOT NULL REFERENCES anesthesia_record (content_hash),
+    PRIMARY KEY (submission_id, position)
+);
+
+CREATE INDEX IF NOT EXISTS submission_records_content_hash
+    ON submission_records (content_hash);
+"#;
+
+/// A saved submission, without its records
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub submission_id: i64,
+    pub submitted_at: NaiveDateTime,
+    pub practice_id: PracticeIdType,
+    pub created_by: String,
+    pub create_date: NaiveDateTime,
+    pub aqi_xml_version: AQIXMLVersionType,
+    pub record_count: usize,
+}
+
+/// A record as it was in a saved submission
+#[derive(Clone, Debug)]
+pub struct StoredRecord {
+    pub submission_id: i64,
+    pub submitted_at: NaiveDateTime,
+    /// Zero-based position of the record in its submission
+    pub position: usize,
+    pub anesthesia_record_id: String,
+    pub content_hash: String,
+}
+
+/// Criteria for `find_records`, each ignored if it's `None`
+#[derive(Clone, Debug, Default)]
+pub struct RecordQuery {
+    pub submission_id: Option<i64>,
+    /// `ProcStartTime` at or after
+    pub proc_start_from: Option<NaiveDateTime>,
+    /// `ProcStartTime` before
+    pub proc_start_to: Option<NaiveDateTime>,
+    /// Any of the `AnesthesiaStaff` has this NPI
+    pub npi: Option<NPIType>,
+    pub facility_id: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordChange {
+    Added,
+    Modified,
+    Unchanged,
+    /// In the earlier submission but not among the records compared
+    Removed,
+}
+
+#[derive(Clone, Debug)]
+pub struct Change {
+    pub anesthesia_record_id: String,
+    pub change: RecordChange,
+}
+
+/// A database of saved submissions
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    /// Opens or creates the database at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Store, AQIError> {
+        Store::from_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Store, AQIError> {
+        Store::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// Creates any missing tables
+    pub fn from_connection(connection: Connection) -> Result<Store, AQIError> {
+        connection.execute_batch("PRAGMA foreign_keys = ON;")?;
+        connection.execute_batch(SCHEMA)?;
+        connection.execute_batch(&RECORD_HEADER.create("submissions (submission_id)"))?;
+        connection.execute_batch(&ANESTHESIA_RECORD.create_children())?;
+
+        Ok(Store { connection })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Saves every record of a submission, returning its `submission_id`
+    ///
+    /// Records already saved with another submission are shared with it.
+    pub fn save_submission(
+        &mut self,
+        records: &AnesthesiaRecordsType,
+        submitted_at: NaiveDateTime,
+    ) -> Result<i64, AQIError> {
+        let transaction = self.connection.transaction()?;
+
+        transaction.execute(
+            "INSERT INTO submissions (submitted_at) VALUES (?1)",
+            params![date_time(&submitted_at)],
+        )?;
+        let submission_id = transaction.last_insert_rowid();
+
+        let header = to_element("RecordHeader", &records.record_header)
+            .map_err(|err| err.in_element("RecordHeader"))?;
+        RECORD_HEADER.insert(&transaction, &header, submission_id, None)?;
+
+        for (i, record) in records.anesthesia_records.iter().enumerate() {
+            let path = format!("AnesthesiaRecord[{}]", i);
+            let content =
+                to_xml("AnesthesiaRecord", record).map_err(|err| err.in_element(&path))?;
+            let content_hash = hash(&content);
+
+            let inserted = transaction.execute(
+                "INSERT OR IGNORE INTO anesthesia_record (content_hash) VALUES (?1)",
+                [&content_hash],
+            )?;
+            if inserted > 0 {
+                let id = transaction.last_insert_rowid();
+                let element = from_xml(&content).map_err(|err| err.in_element(&path))?;
+                ANESTHESIA_RECORD.insert_children(&transaction, &element, id)?;
+            }
+
+            transaction.execute(
+                "INSERT INTO submission_records (submission_id, position, content_hash) \
+                 VALUES (?1, ?2, ?3)",
+                params![submission_id, i as i64, content_hash],
+            )?;
+        }
+
+        transaction.commit()?;
+
+        Ok(submission_id)
+    }
+
+    /// Reads a saved submission back, with its records in their original order
+    pub fn load_submission(&self, submission_id: i64) -> Result<AnesthesiaRecordsType, AQIError> {
+        let header = RECORD_HEADER
+            .load(&self.connection, "parent_id", &submission_id)?
+            .pop()
+            .ok_or_else(|| AQIError::parse(format!("No submission {}", submission_id)))?;
+
+        let mut statement = self.connection.prepare(
+            "SELECT content_hash FROM submission_records WHERE submission_id = ?1 \
+             ORDER BY position",
+        )?;
+        let hashes = statement
+            .query_map([submission_id], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let anesthesia_records = hashes
+            .iter()
+            .enumerate()
+            .map(|(i, content_hash)| {
+                self.load_record(content_hash)
+                    .map_err(|err| err.in_element(&format!("AnesthesiaRecord[{}]", i)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(AnesthesiaRecordsType {
+            record_header: RecordHeaderType::read(&header)
+                .map_err(|err| err.in_element("RecordHeader"))?,
+            anesthesia_records,
+        })
+    }
+
+    /// Every saved submission, oldest first
+    pub fn submissions(&self) -> Result<Vec<Submission>, AQIError> {
+        self.query_submissions("ORDER BY submission_id")
+    }
+
+    /// The most recently saved submission
+    pub fn latest_submission(&self) -> Result<Option<Submission>, AQIError> {
+        self.query_submissions("ORDER BY submission_id DESC LIMIT 1")
+            .map(|submissions| submissions.into_iter().next())
+    }
+
+    fn query_submissions(&self, order: &str) -> Result<Vec<Submission>, AQIError> {
+        let mut statement = self.connection.prepare(&format!(
+            "SELECT submission_id, submitted_at, practice_id, created_by, create_date, \
+             aqi_xml_version, (SELECT COUNT(*) FROM submission_records r \
+             WHERE r.submission_id = s.submission_id) \
+             FROM submissions s JOIN record_header h ON h.parent_id = s.submission_id {}",
+            order
+        ))?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, i64>(6)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(
+                |(
+                    submission_id,
+                    submitted_at,
+                    practice_id,
+                    created_by,
+                    create_date,
+                    version,
+                    count,
+                )| {
+                    Ok(Submission {
+                        submission_id,
+                        submitted_at: parse_date_time(&submitted_at)?,
+                        practice_id: PracticeIdType::from_str(&practice_id)?,
+                        created_by,
+                        create_date: parse_date_time(&create_date)?,
+                        aqi_xml_version: version.parse()?,
+                        record_count: count as usize,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    /// Reads a single saved record by its content hash
+    pub fn load_record(&self, content_hash: &str) -> Result<AnesthesiaRecordType, AQIError> {
+        let id: i64 = self
+            .connection
+            .query_row(
+                "SELECT id FROM anesthesia_record WHERE content_hash = ?1",
+                [content_hash],
+                |row| row.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| AQIError::parse(format!("No record with hash {}", content_hash)))?;
+
+        let mut element = Element {
+            name: "AnesthesiaRecord".to_string(),
+            attributes: Vec::new(),
+            text: String::new(),
+            children: Vec::new(),
+        };
+        ANESTHESIA_RECORD.load_children(&self.connection, id, &mut element)?;
+
+        AnesthesiaRecordType::read(&element)
+    }
+
+    /// Records of every saved submission that match `query`, by submission
+    /// and then position
+    pub fn find_records(&self, query: &RecordQuery) -> Result<Vec<StoredRecord>, AQIError> {
+        let mut statement = self.connection.prepare(
+            "SELECT r.submission_id, s.submitted_at, r.position, c.anesthesia_record_id, \
+             r.content_hash \
+             FROM submission_records r \
+             JOIN submissions s ON s.submission_id = r.submission_id \
+             JOIN anesthesia_record a ON a.content_hash = r.content_hash \
+             JOIN anesthesia_case c ON c.parent_id = a.id \
+             JOIN \"procedure\" p ON p.parent_id = a.id \
+             WHERE (?1 IS NULL OR r.submission_id = ?1) \
+             AND (?2 IS NULL OR p.proc_start_time >= ?2) \
+             AND (?3 IS NULL OR p.proc_start_time < ?3) \
+             AND (?4 IS NULL OR EXISTS (SELECT 1 FROM anesthesia_case_anesthesia_staff_set ss \
+             JOIN anesthesia_case_anesthesia_staff_set_anesthesia_staff st \
+             ON st.parent_id = ss.id WHERE ss.parent_id = c.id AND st.npi = ?4)) \
+             AND (?5 IS NULL OR p.facility_id = ?5) \
+             ORDER BY r.submission_id, r.position",
+        )?;
+
+        let rows = statement
+            .query_map(
+                params![
+                    query.submission_id,
+                    query.proc_start_from.as_ref().map(date_time),
+                    query.proc_start_to.as_ref().map(date_time),
+                    query.npi.as_ref().map(|npi| npi.value()),
+                    query.facility_id,
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, i64>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(
+                |(submission_id, submitted_at, position, anesthesia_record_id, content_hash)| {
+                    Ok(StoredRecord {
+                        submission_id,
+                        submitted_at: parse_date_time(&submitted_at)?,
+                        position: position as usize,
+                        anesthesia_record_id,
+                        content_hash,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    /// Compares records against those saved with a submission, by
+    /// `AnesthesiaRecordID` and content hash
+    ///
+    /// Changes are in the order of `records`, followed by any removed records
+    /// in their order in the submission. An `AnesthesiaRecordID` used by more
+    /// than one record, in either, is an error, as there's no telling which
+    /// record it was changed into.
+    pub fn changes(
+        &self,
+        records: &[AnesthesiaRecordType],
+        submission_id: i64,
+    ) -> Result<Vec<Change>, AQIError> {
+        let mut statement = self.connection.prepare(
+            "SELECT c.anesthesia_record_id, r.content_hash FROM submission_records r \
+             JOIN anesthesia_record a ON a.content_hash = r.content_hash \
+             JOIN anesthesia_case c ON c.parent_id = a.id \
+             WHERE r.submission_id = ?1 ORDER BY r.position",
+        )?;
+        let saved = statement
+            .query_map([submission_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut hashes: HashMap<&str, &str> = HashMap::new();
+        for (i, (id, hash)) in saved.iter().enumerate() {
+            if hashes.insert(id, hash).is_some() {
+                return Err(duplicate_id(id).in_element(&format!(
+                    "Submission {}/AnesthesiaRecord[{}]",
+                    submission_id, i
+                )));
+            }
+        }
+
+        let mut seen = HashMap::new();
+        let mut changes = Vec::with_capacity(records.len());
+        for (i, record) in records.iter().enumerate() {
+            let path = format!("AnesthesiaRecord[{}]", i);
+            let id = &record.anesthesia_case.anesthesia_record_id;
+            if seen.insert(id.as_str(), i).is_some() {
+                return Err(duplicate_id(id).in_element(&path));
+            }
+            let content_hash = content_hash(record).map_err(|err| err.in_element(&path))?;
+
+            let change = match hashes.remove(id.as_str()) {
+                None => RecordChange::Added,
+                Some(saved) if saved == content_hash => RecordChange::Unchanged,
+                Some(_) => RecordChange::Modified,
+            };
+            changes.push(Change {
+                anesthesia_record_id: id.clone(),
+                change,
+            });
+        }
+
+        for (id, _) in &saved {
+            if hashes.remove(id.as_str()).is_some() {
+                changes.push(Change {
+                    anesthesia_record_id: id.clone(),
+                    change: RecordChange::Removed,
+                });
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+fn duplicate_id(id: &str) -> AQIError {
+    AQIError::parse(format!("AnesthesiaRecordID {} is used more than once", id))
+        .in_element("AnesthesiaCase/AnesthesiaRecordID")
+}
+
+/// The SHA-256 of a record's `AnesthesiaRecord` XML, in hex
+pub fn content_hash(record: &AnesthesiaRecordType) -> Result<String, AQIError> {
+    to_xml("AnesthesiaRecord", record).map(|content| hash(&content))
+}
+
+fn hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+fn to_xml<T: WritableSchemaType>(name: &str, value: &T) -> Result<String, AQIError> {
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(Vec::new());
+    value.write(name, &mut writer)?;
+
+    // The emitter only ever writes UTF-8
+    Ok(String::from_utf8(writer.into_inner()).expect("Writer produced invalid UTF-8"))
+}
+
+fn from_xml(content: &str) -> Result<Element, AQIError> {
+    Element::read(&mut EventReader::new(content.as_bytes()))
+}
+
+fn to_element<T: WritableSchemaType>(name: &str, value: &T) -> Result<Element, AQIError> {
+    from_xml(&to_xml(name, value)?)
+}
+
+fn date_time(value: &NaiveDateTime) -> String {
+    value.format(DATE_TIME_FORMAT).to_string()
+}
+
+fn parse_date_time(value: &str) -> Result<NaiveDateTime, AQIError> {
+    NaiveDateTime::parse_from_str(value, READ_DATE_TIME_FORMAT)
+        .map_err(|_| AQIError::parse(format!("Invalid date and time: {}", value)))
+}
+
+lazy_static! {
+    static ref RECORD_HEADER: ElementTable =
+        ElementTable::root("record_header", "RecordHeader", "RecordHeaderType");
+    static ref ANESTHESIA_RECORD: ElementTable = ElementTable::root(
+        "anesthesia_record",
+        "AnesthesiaRecord",
+        "AnesthesiaRecordType"
+    );
+}
+
+/// The table for an element path, and the tables below it
+struct ElementTable {
+    name: String,
+    element: &'static str,
+    repeated: bool,
+    /// The simple elements in columns and the rest in tables of their own, in
+    /// schema order, or `None` for the `value` table of a repeated simple
+    /// element
+    parts: Option<Vec<Part>>,
+}
+
+enum Part {
+    Column(&'static SchemaElement),
+    Table(ElementTable),
+}
+
+impl ElementTable {
+    /// The table for a complex `element` with no parent in the tree, whose
+    /// children's tables are named after just their fields
+    fn root(name: &str, element: &'static str, type_name: &str) -> ElementTable {
+        ElementTable {
+            name: name.to_string(),
+            element,
+            repeated: false,
+            parts: Some(ElementTable::parts("", type_name)),
+        }
+    }
+
+    fn parts(prefix: &str, type_name: &str) -> Vec<Part> {
+        elements::complex_type(type_name)
+            .elements
+            .iter()
+            .map(|element| match element.kind {
+                Kind::Complex(type_name) => {
+                    let name = format!("{}{}", prefix, element.field);
+                    Part::Table(ElementTable {
+                        parts: Some(ElementTable::parts(&format!("{}_", name), type_name)),
+                        name,
+                        element: element.name,
+                        repeated: element.repeated,
+                    })
+                }
+                _ if element.repeated => Part::Table(ElementTable {
+                    name: format!("{}{}", prefix, element.field),
+                    element: element.name,
+                    repeated: true,
+                    parts: None,
+                }),
+                _ => Part::Column(element),
+            })
+            .collect()
+    }
+
+    fn columns(&self) -> Vec<&'static str> {
+        match self.parts {
+            Some(ref parts) => parts
+                .iter()
+                .filter_map(|part| match *part {
+                    Part::Column(element) => Some(element.field),
+                    Part::Table(_) => None,
+                })
+                .collect(),
+            None => vec!["value"],
+        }
+    }
+
+    fn tables(&self) -> impl Iterator<Item = &ElementTable> {
+        self.parts.iter().flatten().filter_map(|part| match *part {
+            Part::Table(ref table) => Some(table),
+            Part::Column(_) => None,
+        })
+    }
+
+    /// `CREATE` statements for this table, whose rows belong to `parent`, and
+    /// every table below it
+    fn create(&self, parent: &str) -> String {
+        let mut columns = vec![
+            "id INTEGER PRIMARY KEY".to_string(),
+            format!("parent_id INTEGER NOT NULL REFERENCES {}", parent),
+        ];
+        if self.repeated {
+            columns.push("position INTEGER NOT NULL".to_string());
+        }
+        columns.extend(
+            self.columns()
+                .iter()
+                .map(|column| format!("\"{}\" TEXT", column)),
+        );
+        columns.push(if self.repeated {
+            "UNIQUE (parent_id, position)".to_string()
+        } else {
+            "UNIQUE (parent_id)".to_string()
+        });
+
+        format!(
+            "CREATE TABLE IF NOT EXISTS \"{}\" (\n    {}\n);\n{}",
+            self.name,
+            columns.join(",\n    "),
+            self.create_children()
+        )
+    }
+
+    fn create_children(&self) -> String {
+        let parent = format!("\"{}\" (id)", self.name);
+        self.tables().map(|table| table.create(&parent)).collect()
+    }
+
+    /// Inserts `element` as a row belonging to `parent_id`, and its children
+    fn insert(
+        &self,
+        connection: &Connection,
+        element: &Element,
+        parent_id: i64,
+        position: Option<usize>,
+    ) -> Result<(), AQIError> {
+        let columns = self.columns();
+        let values: Vec<Option<&str>> = match self.parts {
+            Some(ref parts) => parts
+                .iter()
+                .filter_map(|part| match *part {
+                    Part::Column(column) => Some(
+                        element
+                            .children
+                            .iter()
+                            .find(|child| child.name == column.name)
+                            .map(|child| child.text.as_str()),
+                    ),
+                    Part::Table(_) => None,
+                })
+                .collect(),
+            None => vec![Some(element.text.as_str())],
+        };
+
+        let mut names = vec!["parent_id".to_string()];
+        let mut parameters: Vec<&dyn ToSql> = vec![&parent_id];
+        let position = position.map(|position| position as i64);
+        if let Some(ref position) = position {
+            names.push("position".to_string());
+            parameters.push(position);
+        }
+        names.extend(columns.iter().map(|column| format!("\"{}\"", column)));
+        parameters.extend(values.iter().map(|value| value as &dyn ToSql));
+        let placeholders: Vec<String> = (1..=names.len()).map(|i| format!("?{}", i)).collect();
+
+        connection
+            .prepare_cached(&format!(
+                "INSERT INTO \"{}\" ({}) VALUES ({})",
+                self.name,
+                names.join(", "),
+                placeholders.join(", ")
+            ))?
+            .execute(parameters.as_slice())?;
+
+        self.insert_children(connection, element, connection.last_insert_rowid())
+    }
+
+    fn insert_children(
+        &self,
+        connection: &Connection,
+        element: &Element,
+        id: i64,
+    ) -> Result<(), AQIError> {
+        for table in self.tables() {
+            let children = element
+                .children
+                .iter()
+                .filter(|child| child.name == table.element);
+            for (i, child) in children.enumerate() {
+                let position = if table.repeated { Some(i) } else { None };
+                table.insert(connection, child, id, position)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the rows where `column` is `value` back into elements, in order
+    fn load(
+        &self,
+        connection: &Connection,
+        column: &str,
+        value: &dyn ToSql,
+    ) -> Result<Vec<Element>, AQIError> {
+        let columns: Vec<String> = self
+            .columns()
+            .iter()
+            .map(|column| format!(", \"{}\"", column))
+            .collect();
+        let mut statement = connection.prepare_cached(&format!(
+            "SELECT id{} FROM \"{}\" WHERE {} = ?1{}",
+            columns.concat(),
+            self.name,
+            column,
+            if self.repeated {
+                " ORDER BY position"
+            } else {
+                ""
+            }
+        ))?;
+        let rows = statement
+            .query_map([value], |row| {
+                let values = (1..=columns.len())
+                    .map(|i| row.get::<_, Option<String>>(i))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((row.get::<_, i64>(0)?, values))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(|(id, values)| {
+                let mut element = Element {
+                    name: self.element.to_string(),
+                    attributes: Vec::new(),
+                    text: String::new(),
+                    children: Vec::new(),
+                };
+
+                match self.parts {
+                    Some(_) => {
+                        let mut values = values.into_iter();
+                        for part in self.parts.iter().flatten() {
+                            match *part {
+                                Part::Column(column) => {
+                                    if let Some(Some(text)) = values.next() {
+                                        element.children.push(Element {
+                                            name: column.name.to_string(),
+                                            attributes: Vec::new(),
+                                            text,
+                                            children: Vec::new(),
+                                        });
+                                    }
+                                }
+                                Part::Table(ref table) => element.children.extend(table.load(
+                                    connection,
+                                    "parent_id",
+                                    &id,
+                                )?),
+                            }
+                        }
+                    }
+                    None => element.text = values.into_iter().flatten().collect(),
+                }
+
+                Ok(element)
+            })
+            .collect()
+    }
+
+    /// Reads the children of the row `id` into `element`
+    fn load_children(
+        &self,
+        connection: &Connection,
+        id: i64,
+        element: &mut Element,
+    ) -> Result<(), AQIError> {
+        for table in self.tables() {
+            element
+                .children
+                .extend(table.load(connection, "parent_id", &id)?);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submitted_at() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2020, 3, 6)
+            .and_then(|date| date.and_hms_opt(9, 0, 0))
+            .unwrap()
+    }
+
+    #[test]
+    fn loads_the_document_it_saved() {
+        let records = submission();
+        let mut store = Store::open_in_memory().unwrap();
+        let submission_id = store.save_submission(&records, submitted_at()).unwrap();
+
+        let loaded = store.load_submission(submission_id).unwrap();
+        assert_eq!(
+            loaded.write_document(Vec::new(), true).unwrap(),
+            records.write_document(Vec::new(), true).unwrap()
+        );
+        assert_eq!(loaded.to_xml_string(true).unwrap() + "\n", SUBMISSION);
+
+        let hash = content_hash(&records.anesthesia_records[1]).unwrap();
+        let record = store.load_record(&hash).unwrap();
+        assert_eq!(record.anesthesia_case.anesthesia_record_id, "R2");
+        assert!(store.load_submission(submission_id + 1).is_err());
+    }
+
+    #[test]
+    fn shares_records_between_submissions() {
+        let records = submission();
+        let mut store = Store::open_in_memory().unwrap();
+        let first = store.save_submission(&records, submitted_at()).unwrap();
+        let second = store.save_submission(&records, submitted_at()).unwrap();
+
+        let stored: i64 = store
+            .connection()
+            .query_row("SELECT COUNT(*) FROM anesthesia_record", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(stored, 2);
+        assert_eq!(store.submissions().unwrap().len(), 2);
+        assert_eq!(
+            store.latest_submission().unwrap().unwrap().submission_id,
+            second
+        );
+
+        let mut changed = records.anesthesia_records.clone();
+        changed[0].pre_op.age = 88;
+        changed.remove(1);
+        let changes: Vec<_> = store
+            .changes(&changed, first)
+            .unwrap()
+            .into_iter()
+            .map(|change| (change.anesthesia_record_id, change.change))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("R1".to_string(), RecordChange::Modified),
+                ("R2".to_string(), RecordChange::Removed),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_records_by_staff_and_time() {
+        let mut store = Store::open_in_memory().unwrap();
+        store
+            .save_submission(&submission(), submitted_at())
+            .unwrap();
+
+        let query = RecordQuery {
+            npi: Some(NPIType::from_str("1234567890").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(store.find_records(&query).unwrap().len(), 2);
+
+        let query = RecordQuery {
+            proc_start_from: NaiveDate::from_ymd_opt(2020, 6, 1)
+                .and_then(|date| date.and_hms_opt(0, 0, 0)),
+            ..query
+        };
+        let found = store.find_records(&query).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].anesthesia_record_id, "R2");
+        assert_eq!(found[0].position, 1);
+    }
+
+    #[test]
+    fn refuses_duplicate_record_ids() {
+        let records = submission();
+        let mut store = Store::open_in_memory().unwrap();
+        let first = store.save_submission(&records, submitted_at()).unwrap();
+
+        let mut duplicated = records.anesthesia_records.clone();
+        duplicated[1].anesthesia_case.anesthesia_record_id = "R1".to_string();
+        let err = store.changes(&duplicated, first).unwrap_err();
+        assert!(err.to_string().contains("AnesthesiaRecord[1]"));
+
+        let mut saved = records.clone();
+        saved.anesthesia_records = duplicated;
+        let second = store.save_submission(&saved, submitted_at()).unwrap();
+        assert!(store.changes(&records.anesthesia_records, second).is_err());
+    }
+
+    #[test]
+    fn names_tables_after_fields() {
+        let mut names = Vec::new();
+        let mut tables: Vec<&ElementTable> = vec![&RECORD_HEADER, &ANESTHESIA_RECORD];
+        while let Some(table) = tables.pop() {
+            names.push(table.name.as_str());
+            tables.extend(table.tables());
+        }
+        let count = names.len();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), count);
+        for name in &[
+            "demographic",
+            "procedure_cpt_set_cpt",
+            "anesthesia_case_anesthesia_staff_set_anesthesia_staff",
+        ] {
+            assert!(names.contains(name), "{}", name);
+        }
+
+        let store = Store::open_in_memory().unwrap();
+        let created: i64 = store
+            .connection()
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(created as usize, count + 2);
+    }
+}
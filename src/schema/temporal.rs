@@ -0,0 +1,538 @@
+//! # Temporal consistency
+//! Cross-checks the times within a single anesthesia record
+//!
+//! Times are compared against each other and against the case window, which
+//! runs from the earliest of `ProcStartTime` and the `AnesthesiaStartTime`s to
+//! the latest of `ProcEndTime` and the `AnesthesiaEndTime`s. Staff only have
+//! to be signed in for part of the window, and milestones that routinely fall
+//! before or after it, like `Patient In Room` or `PACU Arrival`, are only held
+//! to the side of the window they can't cross.
+
+use super::validate::child_path;
+use super::*;
+
+use chrono::Duration;
+use std::fmt;
+
+/// A single pair of times that contradict each other
+///
+/// `path` follows the same conventions as `validate::Violation`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemporalViolation {
+    pub path: String,
+    pub kind: TemporalViolationKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TemporalViolationKind {
+    /// A time that falls after one it should precede
+    OutOfOrder {
+        earlier: &'static str,
+        later: &'static str,
+    },
+    /// A time that falls outside the case window
+    OutsideWindow { element: &'static str },
+}
+
+impl fmt::Display for TemporalViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            TemporalViolationKind::OutOfOrder { earlier, later } => {
+                write!(f, "{}: {} is after {}", self.path, earlier, later)
+            }
+            TemporalViolationKind::OutsideWindow { element } => {
+                write!(f, "{}: {} is outside the case window", self.path, element)
+            }
+        }
+    }
+}
+
+/// Slack allowed when comparing times, e.g. for clocks that are slightly out of sync
+#[derive(Clone, Copy, Debug)]
+pub struct TemporalOptions {
+    /// How far a time may fall after one it should precede
+    pub order_tolerance: Duration,
+    /// How far a time may fall outside the case window
+    pub window_tolerance: Duration,
+}
+
+impl Default for TemporalOptions {
+    fn default() -> TemporalOptions {
+        TemporalOptions {
+            order_tolerance: Duration::zero(),
+            window_tolerance: Duration::zero(),
+        }
+    }
+}
+
+/// Checks every record, using the same paths as `Validate`
+pub fn validate_records(
+    records: &AnesthesiaRecordsType,
+    options: &TemporalOptions,
+) -> Vec<TemporalViolation> {
+    let mut violations = Vec::new();
+
+    for (i, record) in records.anesthesia_records.iter().enumerate() {
+        validate_record(
+            record,
+            &format!("AnesthesiaRecord[{}]", i),
+            options,
+            &mut violations,
+        );
+    }
+
+    violations
+}
+
+/// Appends any ordering or containment violations found in `record`, which lives at `path`
+pub fn validate_record(
+    record: &AnesthesiaRecordType,
+    path: &str,
+    options: &TemporalOptions,
+    violations: &mut Vec<TemporalViolation>,
+) {
+    let mut checker = Checker {
+        options,
+        window: case_window(record),
+        violations,
+    };
+
+    let procedure = &record.procedure;
+    let procedure_path = child_path(path, "Procedure");
+    checker.order(
+        &procedure_path,
+        ("ProcStartTime", procedure.proc_start_time),
+        ("ProcEndTime", procedure.proc_end_time),
+    );
+    checker.order(
+        &procedure_path,
+        ("AdmissionDate", procedure.admission_date),
+        ("ProcStartTime", procedure.proc_start_time),
+    );
+
+    let case_path = child_path(path, "AnesthesiaCase");
+    let staff_path = child_path(&case_path, "AnesthesiaStaffSet/AnesthesiaStaff");
+    for (i, staff) in record
+        .anesthesia_case
+        .anesthesia_staff_set
+        .anesthesia_staff
+        .iter()
+        .enumerate()
+    {
+        let path = format!("{}[{}]", staff_path, i);
+        checker.order(
+            &path,
+            ("StaffSignIn", staff.staff_sign_in),
+            ("StaffSignOut", staff.staff_sign_out),
+        );
+        // Staff routinely sign in before anesthesia starts and out after
+        // the procedure ends, they only have to overlap the case
+        checker.not_after_window(&path, "StaffSignIn", staff.staff_sign_in);
+        checker.not_before_window(&path, "StaffSignOut", staff.staff_sign_out);
+    }
+
+    let method_path = child_path(&case_path, "AnesthesiaMethodSet/AnesthesiaMethod");
+    for (i, method) in record
+        .anesthesia_case
+        .anesthesia_method_set
+        .anesthesia_method
+        .iter()
+        .enumerate()
+    {
+        let path = format!("{}[{}]", method_path, i);
+        checker.order(
+            &path,
+            ("AnesthesiaStartTime", Some(method.anesthesia_start_time)),
+            ("AnesthesiaEndTime", Some(method.anesthesia_end_time)),
+        );
+        checker.order(
+            &path,
+            ("AnesthesiaStartTime", Some(method.anesthesia_start_time)),
+            (
+                "AnesthesiaInductionStartTime",
+                method.anesthesia_induction_start_time,
+            ),
+        );
+        checker.order(
+            &path,
+            (
+                "AnesthesiaInductionStartTime",
+                method.anesthesia_induction_start_time,
+            ),
+            ("AnesthesiaEndTime", Some(method.anesthesia_end_time)),
+        );
+    }
+
+    let intra_op_path = child_path(path, "IntraOp");
+    if let Some(ref medications_set) = record.intra_op.medications_set {
+        checker.medications(
+            &child_path(&intra_op_path, "MedicationsSet/Medication"),
+            &medications_set.medication,
+        );
+    }
+
+    if let Some(ref monitoring_set) = record.intra_op.monitoring_physiologic_set {
+        let monitoring_path = child_path(&intra_op_path, "MonitoringPhysiologicSet/Monitoring");
+        for (i, monitoring) in monitoring_set.monitoring.iter().enumerate() {
+            checker.within_window(
+                &format!("{}[{}]", monitoring_path, i),
+                "MonitoringTime",
+                monitoring.monitoring_time,
+            );
+        }
+    }
+
+    if let Some(ref outputs_set) = record.intra_op.outputs_set {
+        let output_path = child_path(&intra_op_path, "OutputsSet/OutputEvent");
+        for (i, output) in outputs_set.output_event.iter().enumerate() {
+            let path = format!("{}[{}]", output_path, i);
+            checker.order(
+                &path,
+                ("OutputStartDateTime", output.output_start_date_time),
+                ("OutputEndDateTime", output.output_end_date_time),
+            );
+            checker.within_window(&path, "OutputStartDateTime", output.output_start_date_time);
+            checker.within_window(&path, "OutputEndDateTime", output.output_end_date_time);
+        }
+    }
+
+    let post_op = &record.post_op;
+    let post_op_path = child_path(path, "PostOp");
+    checker.order(
+        &post_op_path,
+        ("ProcEndTime", procedure.proc_end_time),
+        ("PostOpDispDateTime", post_op.post_op_disp_date_time),
+    );
+    checker.order(
+        &post_op_path,
+        ("PostOpDispDateTime", post_op.post_op_disp_date_time),
+        (
+            "PostOpDischargeDateTime",
+            post_op.post_op_discharge_date_time,
+        ),
+    );
+    checker.order(
+        &post_op_path,
+        ("ProcEndTime", procedure.proc_end_time),
+        (
+            "PostOpDischargeDateTime",
+            post_op.post_op_discharge_date_time,
+        ),
+    );
+
+    if let Some(ref timing_milestones) = record.timing_milestones {
+        let milestone_path = child_path(path, "TimingMilestones/TimingMilestone");
+        for (i, milestone) in timing_milestones.timing_milestone.iter().enumerate() {
+            let path = format!("{}[{}]", milestone_path, i);
+            checker.order(
+                &path,
+                ("TMStartTime", Some(milestone.tm_start_time)),
+                ("TMEndTime", milestone.tm_end_time),
+            );
+            checker.milestone(&path, milestone);
+        }
+    }
+
+    if let Some(ref outcomes_events) = record.outcomes_events {
+        let outcomes_path = child_path(path, "OutcomesEvents");
+
+        if let Some(ref ic_event_set) = outcomes_events.ic_event_set {
+            let ic_event_path = child_path(&outcomes_path, "ICEventSet/ICEvent");
+            for (i, ic_event) in ic_event_set.ic_event.iter().enumerate() {
+                checker.not_before_window(
+                    &format!("{}[{}]", ic_event_path, i),
+                    "ICEventTimeDate",
+                    ic_event.ic_event_time_date,
+                );
+            }
+        }
+
+        if let Some(ref outcome_set) = outcomes_events.outcome_set {
+            let outcome_path = child_path(&outcomes_path, "OutcomeSet/Outcome");
+            for (i, outcome) in outcome_set.outcome.iter().enumerate() {
+                checker.not_before_window(
+                    &format!("{}[{}]", outcome_path, i),
+                    "OutcomeTimeStamp",
+                    outcome.outcome_time_stamp,
+                );
+            }
+        }
+    }
+
+    if let Some(ref anesthesia_details) = record.anesthesia_details {
+        if let Some(ref medications_total_set) = anesthesia_details.medications_total_set {
+            checker.medications(
+                &child_path(
+                    path,
+                    "AnesthesiaDetails/MedicationsTotalSet/MedicationsTotal",
+                ),
+                &medications_total_set.medications_totals,
+            );
+        }
+    }
+}
+
+fn case_window(record: &AnesthesiaRecordType) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let methods = &record
+        .anesthesia_case
+        .anesthesia_method_set
+        .anesthesia_method;
+
+    let start = methods
+        .iter()
+        .map(|method| method.anesthesia_start_time)
+        .chain(record.procedure.proc_start_time)
+        .min();
+    let end = methods
+        .iter()
+        .map(|method| method.anesthesia_end_time)
+        .chain(record.procedure.proc_end_time)
+        .max();
+
+    match (start, end) {
+        (Some(start), Some(end)) => Some((start, end)),
+        _ => None,
+    }
+}
+
+struct Checker<'a> {
+    options: &'a TemporalOptions,
+    window: Option<(NaiveDateTime, NaiveDateTime)>,
+    violations: &'a mut Vec<TemporalViolation>,
+}
+
+impl<'a> Checker<'a> {
+    fn order(
+        &mut self,
+        path: &str,
+        earlier: (&'static str, Option<NaiveDateTime>),
+        later: (&'static str, Option<NaiveDateTime>),
+    ) {
+        if let (Some(earlier_time), Some(later_time)) = (earlier.1, later.1) {
+            // A limit past the end of time can't be exceeded
+            let too_late = later_time
+                .checked_add_signed(self.options.order_tolerance)
+                .is_some_and(|limit| earlier_time > limit);
+
+            if too_late {
+                self.violations.push(TemporalViolation {
+                    path: path.to_string(),
+                    kind: TemporalViolationKind::OutOfOrder {
+                        earlier: earlier.0,
+                        later: later.0,
+                    },
+                });
+            }
+        }
+    }
+
+    fn within_window(&mut self, path: &str, element: &'static str, time: Option<NaiveDateTime>) {
+        if let (Some(time), Some((start, end))) = (time, self.window) {
+            let tolerance = self.options.window_tolerance;
+            let too_early = start
+                .checked_sub_signed(tolerance)
+                .is_some_and(|limit| time < limit);
+            let too_late = end
+                .checked_add_signed(tolerance)
+                .is_some_and(|limit| time > limit);
+
+            if too_early || too_late {
+                self.outside_window(path, element);
+            }
+        }
+    }
+
+    fn not_before_window(
+        &mut self,
+        path: &str,
+        element: &'static str,
+        time: Option<NaiveDateTime>,
+    ) {
+        if let (Some(time), Some((start, _))) = (time, self.window) {
+            let too_early = start
+                .checked_sub_signed(self.options.window_tolerance)
+                .is_some_and(|limit| time < limit);
+
+            if too_early {
+                self.outside_window(path, element);
+            }
+        }
+    }
+
+    fn not_after_window(&mut self, path: &str, element: &'static str, time: Option<NaiveDateTime>) {
+        if let (Some(time), Some((_, end))) = (time, self.window) {
+            let too_late = end
+                .checked_add_signed(self.options.window_tolerance)
+                .is_some_and(|limit| time > limit);
+
+            if too_late {
+                self.outside_window(path, element);
+            }
+        }
+    }
+
+    fn outside_window(&mut self, path: &str, element: &'static str) {
+        self.violations.push(TemporalViolation {
+            path: path.to_string(),
+            kind: TemporalViolationKind::OutsideWindow { element },
+        });
+    }
+
+    /// Holds milestones that happen around the case to the side of the
+    /// window they can't cross, and leaves codes it doesn't know unchecked
+    fn milestone(&mut self, path: &str, milestone: &TimingMilestoneType) {
+        use self::TimingMilestoneCodeType::*;
+
+        let tm_type = milestone.tm_type.clone().normalize();
+        let times = [
+            ("TMStartTime", Some(milestone.tm_start_time)),
+            ("TMEndTime", milestone.tm_end_time),
+        ];
+        for (element, time) in times {
+            match tm_type {
+                PatientInRoom | AnesthesiaReady => self.not_after_window(path, element, time),
+                IncisionStart | IncisionClose => self.within_window(path, element, time),
+                PatientOutOfRoom | PACUArrival | PACUReadyForDischarge | PACUDischarge => {
+                    self.not_before_window(path, element, time)
+                }
+                Unknown | Other(_) => {}
+            }
+        }
+    }
+
+    fn medications(&mut self, path: &str, medications: &[MedicationType]) {
+        for (i, medication) in medications.iter().enumerate() {
+            let path = format!("{}[{}]", path, i);
+            self.order(
+                &path,
+                ("DoseStart", medication.dose_start),
+                ("DoseEnd", medication.dose_end),
+            );
+            self.within_window(&path, "DoseStart", medication.dose_start);
+            self.within_window(&path, "DoseEnd", medication.dose_end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2020, 3, 4)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .unwrap()
+    }
+
+    fn messages(records: &AnesthesiaRecordsType, options: &TemporalOptions) -> Vec<String> {
+        validate_records(records, options)
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn accepts_the_sample_submission() {
+        assert_eq!(
+            messages(&submission(), &Default::default()),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn reports_times_out_of_order() {
+        let mut records = submission();
+        records.anesthesia_records[0].procedure.proc_end_time = Some(time(7, 0));
+
+        assert_eq!(
+            messages(&records, &Default::default()),
+            vec!["AnesthesiaRecord[0]/Procedure: ProcStartTime is after ProcEndTime"]
+        );
+    }
+
+    fn milestone(
+        tm_type: TimingMilestoneCodeType,
+        tm_start_time: NaiveDateTime,
+    ) -> TimingMilestoneType {
+        TimingMilestoneType {
+            tm_type,
+            tm_start_time,
+            tm_end_time: None,
+        }
+    }
+
+    /// The first sample record, anesthetized 07:50 to 10:05, with staff and
+    /// milestones around the case the way they're usually charted
+    fn charted_submission() -> AnesthesiaRecordsType {
+        use self::TimingMilestoneCodeType::*;
+
+        let mut records = submission();
+        let record = &mut records.anesthesia_records[0];
+        let staff = &mut record.anesthesia_case.anesthesia_staff_set.anesthesia_staff[0];
+        staff.staff_sign_in = Some(time(7, 20));
+        staff.staff_sign_out = Some(time(10, 30));
+        record.timing_milestones = Some(TimingMilestonesSetType {
+            timing_milestone: vec![
+                milestone(PatientInRoom, time(7, 30)),
+                milestone(AnesthesiaReady, time(7, 45)),
+                milestone(IncisionStart, time(8, 5)),
+                milestone(IncisionClose, time(9, 50)),
+                milestone(PatientOutOfRoom, time(10, 10)),
+                milestone(PACUArrival, time(10, 15)),
+                milestone(PACUDischarge, time(11, 40)),
+                milestone(Other("Surgeon Time Out".to_string()), time(6, 0)),
+            ],
+        });
+
+        records
+    }
+
+    #[test]
+    fn accepts_staff_and_milestones_around_the_case() {
+        assert_eq!(
+            messages(&charted_submission(), &Default::default()),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn checks_staff_and_milestones_against_the_case_window() {
+        let mut records = charted_submission();
+        let record = &mut records.anesthesia_records[0];
+        record.anesthesia_case.anesthesia_staff_set.anesthesia_staff[0].staff_sign_in =
+            Some(time(10, 20));
+        let milestones = &mut record.timing_milestones.as_mut().unwrap().timing_milestone;
+        milestones[2].tm_start_time = time(7, 40);
+        milestones[5].tm_start_time = time(7, 35);
+
+        assert_eq!(
+            messages(&records, &Default::default()),
+            vec![
+                "AnesthesiaRecord[0]/AnesthesiaCase/AnesthesiaStaffSet/AnesthesiaStaff[0]: \
+                 StaffSignIn is outside the case window",
+                "AnesthesiaRecord[0]/TimingMilestones/TimingMilestone[2]: \
+                 TMStartTime is outside the case window",
+                "AnesthesiaRecord[0]/TimingMilestones/TimingMilestone[5]: \
+                 TMStartTime is outside the case window",
+            ]
+        );
+
+        let options = TemporalOptions {
+            order_tolerance: Duration::zero(),
+            window_tolerance: Duration::minutes(30),
+        };
+        assert_eq!(messages(&records, &options), Vec::<String>::new());
+    }
+
+    #[test]
+    fn allows_tolerances_past_the_end_of_time() {
+        let mut records = submission();
+        records.anesthesia_records[0].procedure.proc_end_time = Some(time(7, 0));
+        let options = TemporalOptions {
+            order_tolerance: Duration::MAX,
+            window_tolerance: Duration::MAX,
+        };
+
+        assert_eq!(messages(&records, &options), Vec::<String>::new());
+    }
+}
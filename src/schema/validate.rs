@@ -0,0 +1,252 @@
+//! # Structural validation
+//! Checks for values the writer will happily emit but the XSD rejects
+//!
+//! The `Validate` impls are generated from `schemas/AQISchema.xsd` by
+//! `aqi-nacor-codegen`, using the helpers here.
+
+use super::*;
+
+use std::fmt;
+
+mod generated;
+
+/// A single way a value fails to satisfy the schema
+///
+/// `path` is the slash-separated element path relative to the value
+/// `validate` was first called on, with zero-based indices for repeated
+/// elements, e.g. `AnesthesiaRecord[12]/AnesthesiaCase/AnesthesiaStaffSet`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    pub path: String,
+    pub kind: ViolationKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ViolationKind {
+    /// Fewer or more occurrences of a child element than the schema allows
+    Cardinality {
+        element: &'static str,
+        min: usize,
+        /// `None` for `unbounded`
+        max: Option<usize>,
+        found: usize,
+    },
+    /// A required element with an empty value
+    EmptyString { element: &'static str },
+    /// An optional wrapper element present without any content, which the
+    /// writer would emit as an empty element
+    EmptyWrapper,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ViolationKind::Cardinality {
+                element,
+                min,
+                max,
+                found,
+            } => match max {
+                Some(max) if found > max => write!(
+                    f,
+                    "{}: expected at most {} {}, found {}",
+                    self.path, max, element, found
+                ),
+                _ => write!(
+                    f,
+                    "{}: expected at least {} {}, found {}",
+                    self.path, min, element, found
+                ),
+            },
+            ViolationKind::EmptyString { element } => {
+                write!(f, "{}: {} is empty", self.path, element)
+            }
+            ViolationKind::EmptyWrapper => write!(f, "{}: element has no content", self.path),
+        }
+    }
+}
+
+pub trait Validate {
+    /// Appends any violations found in `self`, which lives at `path`
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>);
+
+    fn violations(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.validate("", &mut violations);
+
+        violations
+    }
+}
+
+pub(crate) fn child_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", path, name)
+    }
+}
+
+/// Checks `found` against `minOccurs` and `maxOccurs`, with `None` for
+/// `unbounded`
+fn check_count(
+    path: &str,
+    element: &'static str,
+    found: usize,
+    (min, max): (usize, Option<usize>),
+    violations: &mut Vec<Violation>,
+) {
+    if found < min || max.is_some_and(|max| found > max) {
+        violations.push(Violation {
+            path: path.to_string(),
+            kind: ViolationKind::Cardinality {
+                element,
+                min,
+                max,
+                found,
+            },
+        });
+    }
+}
+
+fn check_string(path: &str, element: &'static str, value: &str, violations: &mut Vec<Violation>) {
+    if value.trim().is_empty() {
+        violations.push(Violation {
+            path: path.to_string(),
+            kind: ViolationKind::EmptyString { element },
+        });
+    }
+}
+
+fn check_wrapper(path: &str, is_empty: bool, violations: &mut Vec<Violation>) {
+    if is_empty {
+        violations.push(Violation {
+            path: path.to_string(),
+            kind: ViolationKind::EmptyWrapper,
+        });
+    }
+}
+
+fn validate_child<T: Validate>(path: &str, name: &str, child: &T, violations: &mut Vec<Violation>) {
+    child.validate(&child_path(path, name), violations);
+}
+
+fn validate_optional_child<T: Validate>(
+    path: &str,
+    name: &str,
+    child: &Option<T>,
+    violations: &mut Vec<Violation>,
+) {
+    if let Some(ref child) = *child {
+        validate_child(path, name, child, violations);
+    }
+}
+
+fn validate_children<T: Validate>(
+    path: &str,
+    name: &str,
+    children: &[T],
+    violations: &mut Vec<Violation>,
+) {
+    for (i, child) in children.iter().enumerate() {
+        child.validate(&format!("{}[{}]", child_path(path, name), i), violations);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(violations: Vec<Violation>) -> Vec<String> {
+        violations
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn accepts_the_sample_submission() {
+        assert_eq!(submission().violations(), vec![]);
+    }
+
+    #[test]
+    fn reports_missing_repeated_elements() {
+        let mut records = submission();
+        records.anesthesia_records[1]
+            .anesthesia_case
+            .anesthesia_staff_set
+            .anesthesia_staff
+            .clear();
+
+        assert_eq!(
+            messages(records.violations()),
+            vec![
+                "AnesthesiaRecord[1]/AnesthesiaCase/AnesthesiaStaffSet: \
+                 expected at least 1 AnesthesiaStaff, found 0"
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_a_submission_without_records() {
+        let mut records = submission();
+        records.anesthesia_records.clear();
+
+        assert_eq!(
+            messages(records.violations()),
+            vec![": expected at least 1 AnesthesiaRecord, found 0"]
+        );
+    }
+
+    #[test]
+    fn reports_more_elements_than_max_occurs() {
+        let mut violations = Vec::new();
+        check_count("Set", "Element", 2, (0, Some(1)), &mut violations);
+        check_count("Set", "Element", 2, (1, None), &mut violations);
+
+        assert_eq!(
+            messages(violations),
+            vec!["Set: expected at most 1 Element, found 2"]
+        );
+    }
+
+    #[test]
+    fn indexes_empty_repeated_values() {
+        let monitoring_set = MonitoringSetType {
+            monitor: vec![
+                MonitorCodeType::from_value("ECG"),
+                MonitorCodeType::from_value(" "),
+            ],
+        };
+
+        assert_eq!(
+            messages(monitoring_set.violations()),
+            vec!["Monitor[1]: Monitor is empty"]
+        );
+    }
+
+    #[test]
+    fn reports_empty_optional_wrappers() {
+        let mut records = submission();
+        records.anesthesia_records[0].outcomes_events = Some(OutcomesEventsType {
+            ic_event_set: None,
+            outcome_set: None,
+            qcdr_set: None,
+        });
+
+        assert_eq!(
+            messages(records.violations()),
+            vec!["AnesthesiaRecord[0]/OutcomesEvents: element has no content"]
+        );
+    }
+
+    #[test]
+    fn accepts_an_empty_intra_op() {
+        let mut records = submission();
+        let intra_op = &mut records.anesthesia_records[0].intra_op;
+        intra_op.medications_set = None;
+        intra_op.monitoring_physiologic_set = None;
+        intra_op.outputs_set = None;
+
+        assert_eq!(records.violations(), vec![]);
+    }
+}
@@ -0,0 +1,729 @@
+// Generated by aqi-nacor-codegen from schemas/AQISchema.xsd, don't edit by hand.
+// Change the schema or codegen/src/names.rs and run `cargo run -p aqi-nacor-codegen`.
+
+use super::*;
+
+impl Validate for AnesthesiaRecordsType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        validate_child(path, "RecordHeader", &self.record_header, violations);
+
+        check_count(
+            path,
+            "AnesthesiaRecord",
+            self.anesthesia_records.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(
+            path,
+            "AnesthesiaRecord",
+            &self.anesthesia_records,
+            violations,
+        );
+    }
+}
+
+impl Validate for RecordHeaderType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(path, "CreatedBy", &self.created_by, violations);
+
+        validate_child(path, "EmailSet", &self.email_set, violations);
+
+        validate_optional_child(path, "VendorSet", &self.vendor_set, violations);
+    }
+}
+
+impl Validate for EmailSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "EmailNotificationSet",
+            self.email_notification_set.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(
+            path,
+            "EmailNotificationSet",
+            &self.email_notification_set,
+            violations,
+        );
+    }
+}
+
+impl Validate for EmailNotificationSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(
+            path,
+            "EmailNotificationFirstName",
+            &self.email_notification_first_name,
+            violations,
+        );
+
+        check_string(
+            path,
+            "EmailNotificationLastName",
+            &self.email_notification_last_name,
+            violations,
+        );
+    }
+}
+
+impl Validate for VendorSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(path, "Vendor", self.vendor.len(), (1, None), violations);
+
+        validate_children(path, "Vendor", &self.vendor, violations);
+    }
+}
+
+impl Validate for Vendors {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        validate_child(path, "VendorSetType", &self.vendor_set_type, violations);
+
+        check_string(path, "VendorName", &self.vendor_name, violations);
+    }
+}
+
+impl Validate for SetVendorSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "VendorType",
+            self.vendor_type.len(),
+            (1, None),
+            violations,
+        );
+    }
+}
+
+impl Validate for AnesthesiaRecordType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        validate_child(path, "Demographic", &self.demographic, violations);
+
+        validate_child(path, "Procedure", &self.procedure, violations);
+
+        validate_child(path, "AnesthesiaCase", &self.anesthesia_case, violations);
+
+        validate_child(path, "PreOp", &self.pre_op, violations);
+
+        validate_child(path, "IntraOp", &self.intra_op, violations);
+
+        validate_child(path, "PostOp", &self.post_op, violations);
+
+        validate_optional_child(
+            path,
+            "TimingMilestones",
+            &self.timing_milestones,
+            violations,
+        );
+
+        validate_optional_child(path, "OutcomesEvents", &self.outcomes_events, violations);
+
+        validate_optional_child(
+            path,
+            "AnesthesiaDetails",
+            &self.anesthesia_details,
+            violations,
+        );
+    }
+}
+
+impl Validate for DemographicType {
+    fn validate(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for ProcedureType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(path, "FacilityID", &self.facility_id, violations);
+
+        validate_optional_child(
+            path,
+            "ProcedureLocation",
+            &self.procedure_location,
+            violations,
+        );
+
+        validate_optional_child(path, "CPTSet", &self.cpt_set, violations);
+    }
+}
+
+impl Validate for ProcedureLocationType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(path, "LocationDetails", &self.location_details, violations);
+    }
+}
+
+impl Validate for CPTSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(path, "CPT", self.cpt.len(), (1, None), violations);
+
+        validate_children(path, "CPT", &self.cpt, violations);
+    }
+}
+
+impl Validate for CPTType {
+    fn validate(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for AnesthesiaCaseType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(
+            path,
+            "AnesthesiaRecordID",
+            &self.anesthesia_record_id,
+            violations,
+        );
+
+        validate_child(
+            path,
+            "AnesthesiaStaffSet",
+            &self.anesthesia_staff_set,
+            violations,
+        );
+
+        validate_optional_child(path, "MonitoringSet", &self.monitoring_set, violations);
+
+        validate_child(
+            path,
+            "AnesthesiaMethodSet",
+            &self.anesthesia_method_set,
+            violations,
+        );
+
+        validate_optional_child(
+            path,
+            "AirwayManagementSet",
+            &self.airway_management_set,
+            violations,
+        );
+
+        validate_optional_child(path, "CPTAnesSet", &self.cpt_anes_set, violations);
+    }
+}
+
+impl Validate for AnesthesiaStaffSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "AnesthesiaStaff",
+            self.anesthesia_staff.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(path, "AnesthesiaStaff", &self.anesthesia_staff, violations);
+    }
+}
+
+impl Validate for AnesthesiaStaffType {
+    fn validate(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for MonitoringSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(path, "Monitor", self.monitor.len(), (1, None), violations);
+
+        for (i, value) in self.monitor.iter().enumerate() {
+            check_string(
+                &format!("{}[{}]", child_path(path, "Monitor"), i),
+                "Monitor",
+                value.value(),
+                violations,
+            );
+        }
+    }
+}
+
+impl Validate for AnesthesiaMethodSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "AnesthesiaMethod",
+            self.anesthesia_method.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(
+            path,
+            "AnesthesiaMethod",
+            &self.anesthesia_method,
+            violations,
+        );
+    }
+}
+
+impl Validate for AnesthesiaMethodType {
+    fn validate(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for AirwayManagementSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "AirwayManagement",
+            self.airway_management.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(
+            path,
+            "AirwayManagement",
+            &self.airway_management,
+            violations,
+        );
+    }
+}
+
+impl Validate for AirwayManagementType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(
+            path,
+            "AirwayManagementMethod",
+            self.airway_management_method.value(),
+            violations,
+        );
+    }
+}
+
+impl Validate for CPTAnesSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(path, "CPTAnes", self.cpt_anes.len(), (1, None), violations);
+
+        validate_children(path, "CPTAnes", &self.cpt_anes, violations);
+    }
+}
+
+impl Validate for CPTAnesType {
+    fn validate(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for PreOpType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        validate_optional_child(path, "ICDSet", &self.icd_set, violations);
+
+        validate_optional_child(path, "PreLabSet", &self.pre_lab_set, violations);
+    }
+}
+
+impl Validate for ICDSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(path, "ICD", self.icd.len(), (1, None), violations);
+
+        validate_children(path, "ICD", &self.icd, violations);
+    }
+}
+
+impl Validate for ICDType {
+    fn validate(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for PreLabDataSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "PreLabData",
+            self.pre_lab_data.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(path, "PreLabData", &self.pre_lab_data, violations);
+    }
+}
+
+impl Validate for LabDataType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(path, "LabName", self.lab_name.value(), violations);
+
+        check_string(path, "LabUnit", self.lab_unit.value(), violations);
+
+        check_string(path, "LabValue", &self.lab_value, violations);
+    }
+}
+
+impl Validate for IntraOpType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        validate_optional_child(path, "MedicationsSet", &self.medications_set, violations);
+
+        validate_optional_child(
+            path,
+            "MonitoringPhysiologicSet",
+            &self.monitoring_physiologic_set,
+            violations,
+        );
+
+        validate_optional_child(path, "OutputsSet", &self.outputs_set, violations);
+    }
+}
+
+impl Validate for MedicationsSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "Medication",
+            self.medication.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(path, "Medication", &self.medication, violations);
+    }
+}
+
+impl Validate for MedicationType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(path, "MedicationName", &self.medication_name, violations);
+
+        if let Some(ref mixture_medications) = self.mixture_medications {
+            validate_children(path, "MixtureMedication", mixture_medications, violations);
+        }
+    }
+}
+
+impl Validate for MixtureMedicationType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(
+            path,
+            "MixtureMedicationName",
+            &self.mixture_medication_name,
+            violations,
+        );
+    }
+}
+
+impl Validate for MonitoringPhysiologicSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "Monitoring",
+            self.monitoring.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(path, "Monitoring", &self.monitoring, violations);
+    }
+}
+
+impl Validate for MonitoringPhysiologicType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(
+            path,
+            "MonitoringName",
+            self.monitoring_name.value(),
+            violations,
+        );
+    }
+}
+
+impl Validate for OutputsSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "OutputEvent",
+            self.output_event.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(path, "OutputEvent", &self.output_event, violations);
+    }
+}
+
+impl Validate for OutputEventType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(
+            path,
+            "OutputEventName",
+            self.output_event_name.value(),
+            violations,
+        );
+    }
+}
+
+impl Validate for PostOpType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "PaymentMethod",
+            self.payment_method.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(path, "PaymentMethod", &self.payment_method, violations);
+
+        validate_optional_child(path, "PostOpLabSet", &self.post_op_lab_set, violations);
+
+        validate_optional_child(path, "ICDSet", &self.icd_set, violations);
+    }
+}
+
+impl Validate for PaymentMethodType {
+    fn validate(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for PostOpLabSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "PostLabData",
+            self.post_lab_data.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(path, "PostLabData", &self.post_lab_data, violations);
+    }
+}
+
+impl Validate for TimingMilestonesSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "TimingMilestone",
+            self.timing_milestone.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(path, "TimingMilestone", &self.timing_milestone, violations);
+    }
+}
+
+impl Validate for TimingMilestoneType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(path, "TMType", self.tm_type.value(), violations);
+    }
+}
+
+impl Validate for OutcomesEventsType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_wrapper(
+            path,
+            self.ic_event_set.is_none() && self.outcome_set.is_none() && self.qcdr_set.is_none(),
+            violations,
+        );
+
+        validate_optional_child(path, "ICEventSet", &self.ic_event_set, violations);
+
+        validate_optional_child(path, "OutcomeSet", &self.outcome_set, violations);
+
+        validate_optional_child(path, "QCDRSet", &self.qcdr_set, violations);
+    }
+}
+
+impl Validate for ICEventSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(path, "ICEvent", self.ic_event.len(), (1, None), violations);
+
+        validate_children(path, "ICEvent", &self.ic_event, violations);
+    }
+}
+
+impl Validate for ICEventType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_wrapper(
+            path,
+            self.ic_event_time_date.is_none()
+                && self.ic_category.is_none()
+                && self.ic_severity.is_none()
+                && self.ic_name.is_none()
+                && self.ic_description.is_none()
+                && self.ic_value.is_none()
+                && self.ic_notes.is_none(),
+            violations,
+        );
+    }
+}
+
+impl Validate for OutcomeSetSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(path, "Outcome", self.outcome.len(), (1, None), violations);
+
+        validate_children(path, "Outcome", &self.outcome, violations);
+    }
+}
+
+impl Validate for OutcomeCodeType {
+    fn validate(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for QCDRSetTypeSet {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(path, "QCDR", self.qcdr.len(), (1, None), violations);
+
+        validate_children(path, "QCDR", &self.qcdr, violations);
+    }
+}
+
+impl Validate for QCDRSetType {
+    fn validate(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for AnesthesiaDetailsType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_wrapper(
+            path,
+            self.intake_output_set.is_none()
+                && self.intubation_details.is_none()
+                && self.anesthesia_details_set.is_none()
+                && self.medications_total_set.is_none(),
+            violations,
+        );
+
+        validate_optional_child(path, "IntakeOutputSet", &self.intake_output_set, violations);
+
+        validate_optional_child(
+            path,
+            "IntubationDetails",
+            &self.intubation_details,
+            violations,
+        );
+
+        validate_optional_child(
+            path,
+            "AnesthesiaDetailsSet",
+            &self.anesthesia_details_set,
+            violations,
+        );
+
+        validate_optional_child(
+            path,
+            "MedicationsTotalSet",
+            &self.medications_total_set,
+            violations,
+        );
+    }
+}
+
+impl Validate for IntakeOutputSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "IntakeOutputTotal",
+            self.intake_output_total.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(
+            path,
+            "IntakeOutputTotal",
+            &self.intake_output_total,
+            violations,
+        );
+    }
+}
+
+impl Validate for IntakeOutputTotalType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_wrapper(
+            path,
+            self.intake_output_direction.is_none()
+                && self.input_output_name.is_none()
+                && self.output_units.is_none()
+                && self.input_output_total.is_none()
+                && self.input_output_route.is_none(),
+            violations,
+        );
+    }
+}
+
+impl Validate for IntubationDetailsType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_wrapper(
+            path,
+            self.intubation_approach.is_none()
+                && self.intubation_attempts.is_none()
+                && self.tube_size.is_none()
+                && self.tube_type.is_none()
+                && self.intubation_details_properties_set.is_none(),
+            violations,
+        );
+
+        validate_optional_child(
+            path,
+            "IntubationDetailsPropertiesSet",
+            &self.intubation_details_properties_set,
+            violations,
+        );
+    }
+}
+
+impl Validate for IntubationDetailsPropertiesType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "IntubationDetailsProperties",
+            self.intubation_details_properties.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(
+            path,
+            "IntubationDetailsProperties",
+            &self.intubation_details_properties,
+            violations,
+        );
+    }
+}
+
+impl Validate for IntubationDetailsPropertyType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_string(
+            path,
+            "IntubationProperty",
+            &self.intubation_property,
+            violations,
+        );
+    }
+}
+
+impl Validate for AnesthesiaDetailsSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "AnesthesiaDetailsData",
+            self.anesthesia_details_data.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(
+            path,
+            "AnesthesiaDetailsData",
+            &self.anesthesia_details_data,
+            violations,
+        );
+    }
+}
+
+impl Validate for AnesthesiaDetailsDataType {
+    fn validate(&self, _path: &str, _violations: &mut Vec<Violation>) {}
+}
+
+impl Validate for MedicationsTotalSetType {
+    fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_count(
+            path,
+            "MedicationsTotal",
+            self.medications_totals.len(),
+            (1, None),
+            violations,
+        );
+
+        validate_children(
+            path,
+            "MedicationsTotal",
+            &self.medications_totals,
+            violations,
+        );
+    }
+}
@@ -0,0 +1,1372 @@
+// Generated by aqi-nacor-codegen from schemas/AQISchema.xsd, don't edit by hand.
+// Change the schema or codegen/src/names.rs and run `cargo run -p aqi-nacor-codegen`.
+
+use super::*;
+
+impl WritableSchemaType for RecordHeaderType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("PracticeID", self.practice_id.value(), writer)?;
+
+        write_value("CreatedBy", &self.created_by, writer)?;
+
+        write_value(
+            "CreateDate",
+            self.create_date.format(DATE_TIME_FORMAT),
+            writer,
+        )?;
+
+        write_child("EmailSet", &self.email_set, writer)?;
+
+        write_value("AQIXMLVersion", self.aqi_xml_version.value(), writer)?;
+
+        if let Some(ref vendor_set) = self.vendor_set {
+            write_child("VendorSet", vendor_set, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for EmailSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("EmailNotificationSet", &self.email_notification_set, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for EmailNotificationSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value(
+            "EmailNotificationFirstName",
+            &self.email_notification_first_name,
+            writer,
+        )?;
+
+        write_value(
+            "EmailNotificationLastName",
+            &self.email_notification_last_name,
+            writer,
+        )?;
+
+        write_value(
+            "EmailNotificationAddress",
+            self.email_notification_address.value(),
+            writer,
+        )?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for VendorSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("Vendor", &self.vendor, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for Vendors {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref vendor_id) = self.vendor_id {
+            write_value("VendorID", vendor_id.value(), writer)?;
+        }
+
+        write_child("VendorSetType", &self.vendor_set_type, writer)?;
+
+        write_value("VendorName", &self.vendor_name, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for SetVendorSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        for value in &self.vendor_type {
+            write_value("VendorType", value, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AnesthesiaRecordType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_child("Demographic", &self.demographic, writer)?;
+
+        write_child("Procedure", &self.procedure, writer)?;
+
+        write_child("AnesthesiaCase", &self.anesthesia_case, writer)?;
+
+        write_child("PreOp", &self.pre_op, writer)?;
+
+        write_child("IntraOp", &self.intra_op, writer)?;
+
+        write_child("PostOp", &self.post_op, writer)?;
+
+        if let Some(ref timing_milestones) = self.timing_milestones {
+            write_child("TimingMilestones", timing_milestones, writer)?;
+        }
+
+        if let Some(ref outcomes_events) = self.outcomes_events {
+            write_child("OutcomesEvents", outcomes_events, writer)?;
+        }
+
+        if let Some(ref anesthesia_details) = self.anesthesia_details {
+            write_child("AnesthesiaDetails", anesthesia_details, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for DemographicType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref patient_id) = self.patient_id {
+            write_value("PatientID", patient_id, writer)?;
+        }
+
+        if let Some(ref dob) = self.dob {
+            write_value("DOB", dob.format("%F"), writer)?;
+        }
+
+        if let Some(ref home_zip) = self.home_zip {
+            write_value("HomeZip", home_zip.value(), writer)?;
+        }
+
+        if let Some(ref home_state) = self.home_state {
+            write_value("HomeState", home_state.value(), writer)?;
+        }
+
+        if let Some(ref home_city) = self.home_city {
+            write_value("HomeCity", home_city, writer)?;
+        }
+
+        if let Some(ref race) = self.race {
+            write_value("Race", race.value(), writer)?;
+        }
+
+        write_value("PatientSex", self.patient_sex.value(), writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for ProcedureType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref procedure_id) = self.procedure_id {
+            write_value("ProcedureID", procedure_id, writer)?;
+        }
+
+        write_value("FacilityID", &self.facility_id, writer)?;
+
+        if let Some(ref procedure_location) = self.procedure_location {
+            write_child("ProcedureLocation", procedure_location, writer)?;
+        }
+
+        if let Some(ref proc_start_time) = self.proc_start_time {
+            write_value(
+                "ProcStartTime",
+                proc_start_time.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref proc_end_time) = self.proc_end_time {
+            write_value(
+                "ProcEndTime",
+                proc_end_time.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref admission_status) = self.admission_status {
+            write_value("AdmissionStatus", admission_status.value(), writer)?;
+        }
+
+        write_value("ProcStatus", self.proc_status.value(), writer)?;
+
+        if let Some(ref transfer_status) = self.transfer_status {
+            write_value("TransferStatus", transfer_status.value(), writer)?;
+        }
+
+        if let Some(ref admission_date) = self.admission_date {
+            write_value(
+                "AdmissionDate",
+                admission_date.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref procedure_notes) = self.procedure_notes {
+            write_value("ProcedureNotes", procedure_notes, writer)?;
+        }
+
+        if let Some(ref cpt_set) = self.cpt_set {
+            write_child("CPTSet", cpt_set, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for ProcedureLocationType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("LocationType", self.location_type.value(), writer)?;
+
+        write_value("LocationDetails", &self.location_details, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for CPTSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("CPT", &self.cpt, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for CPTType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref cpt_rank) = self.cpt_rank {
+            write_value("CPTRank", cpt_rank, writer)?;
+        }
+
+        write_value("CPTValue", self.cpt_value.value(), writer)?;
+
+        if let Some(ref cpt_modifier) = self.cpt_modifier {
+            write_value("CPTModifier", cpt_modifier.value(), writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AnesthesiaCaseType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("AnesthesiaRecordID", &self.anesthesia_record_id, writer)?;
+
+        if let Some(ref anesthesia_coverage) = self.anesthesia_coverage {
+            write_value("AnesthesiaCoverage", anesthesia_coverage.value(), writer)?;
+        }
+
+        write_child("AnesthesiaStaffSet", &self.anesthesia_staff_set, writer)?;
+
+        if let Some(ref monitoring_set) = self.monitoring_set {
+            write_child("MonitoringSet", monitoring_set, writer)?;
+        }
+
+        write_child("AnesthesiaMethodSet", &self.anesthesia_method_set, writer)?;
+
+        if let Some(ref airway_management_set) = self.airway_management_set {
+            write_child("AirwayManagementSet", airway_management_set, writer)?;
+        }
+
+        if let Some(ref cpt_anes_set) = self.cpt_anes_set {
+            write_child("CPTAnesSet", cpt_anes_set, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AnesthesiaStaffSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("AnesthesiaStaff", &self.anesthesia_staff, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AnesthesiaStaffType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("TaxID", self.tax_id.value(), writer)?;
+
+        write_value("NPI", self.npi.value(), writer)?;
+
+        if let Some(ref staff_responsibility) = self.staff_responsibility {
+            write_value("StaffResponsibility", staff_responsibility.value(), writer)?;
+        }
+
+        write_value(
+            "ProviderCredentials",
+            self.provider_credentials.value(),
+            writer,
+        )?;
+
+        if let Some(ref staff_sign_in) = self.staff_sign_in {
+            write_value(
+                "StaffSignIn",
+                staff_sign_in.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref staff_sign_out) = self.staff_sign_out {
+            write_value(
+                "StaffSignOut",
+                staff_sign_out.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref staff_notes) = self.staff_notes {
+            write_value("StaffNotes", staff_notes, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for MonitoringSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        for value in &self.monitor {
+            write_value("Monitor", value.value(), writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AnesthesiaMethodSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("AnesthesiaMethod", &self.anesthesia_method, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AnesthesiaMethodType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value(
+            "AnesthesiaCategory",
+            self.anesthesia_category.value(),
+            writer,
+        )?;
+
+        if let Some(ref anesthesia_subcategory) = self.anesthesia_subcategory {
+            write_value(
+                "AnesthesiaSubCategory",
+                anesthesia_subcategory.value(),
+                writer,
+            )?;
+        }
+
+        write_value(
+            "AnesthesiaStartTime",
+            self.anesthesia_start_time.format(DATE_TIME_FORMAT),
+            writer,
+        )?;
+
+        write_value(
+            "AnesthesiaEndTime",
+            self.anesthesia_end_time.format(DATE_TIME_FORMAT),
+            writer,
+        )?;
+
+        if let Some(ref anesthesia_induction) = self.anesthesia_induction {
+            write_value("AnesthesiaInduction", anesthesia_induction.value(), writer)?;
+        }
+
+        if let Some(ref anesthesia_induction_start_time) = self.anesthesia_induction_start_time {
+            write_value(
+                "AnesthesiaInductionStartTime",
+                anesthesia_induction_start_time.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref anesthesia_maintenance) = self.anesthesia_maintenance {
+            write_value(
+                "AnesthesiaMaintenance",
+                anesthesia_maintenance.value(),
+                writer,
+            )?;
+        }
+
+        if let Some(ref anesthesia_notes) = self.anesthesia_notes {
+            write_value("AnesthesiaNotes", anesthesia_notes, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AirwayManagementSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("AirwayManagement", &self.airway_management, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AirwayManagementType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value(
+            "AirwayManagementMethod",
+            self.airway_management_method.value(),
+            writer,
+        )?;
+
+        if let Some(ref airway_sub_management_method) = self.airway_sub_management_method {
+            write_value(
+                "AirwaySubManagementMethod",
+                airway_sub_management_method.value(),
+                writer,
+            )?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for CPTAnesSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("CPTAnes", &self.cpt_anes, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for CPTAnesType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("CPTAnesValue", self.cpt_anes_value.value(), writer)?;
+
+        if let Some(ref cpt_anes_modifier) = self.cpt_anes_modifier {
+            write_value("CPTAnesModifier", cpt_anes_modifier.value(), writer)?;
+        }
+
+        if let Some(ref cpt_anes_description) = self.cpt_anes_description {
+            write_value("CPTAnesDescription", cpt_anes_description, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for PreOpType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("Age", self.age, writer)?;
+
+        if let Some(ref weight) = self.weight {
+            write_value("Weight", weight.value(), writer)?;
+        }
+
+        if let Some(ref weight_in_kg) = self.weight_in_kg {
+            write_value("WeightInKg", weight_in_kg.value(), writer)?;
+        }
+
+        if let Some(ref height) = self.height {
+            write_value("Height", height.value(), writer)?;
+        }
+
+        if let Some(ref height_in_cm) = self.height_in_cm {
+            write_value("HeightInCm", height_in_cm.value(), writer)?;
+        }
+
+        write_value("ASAClass", self.asa_class.value(), writer)?;
+
+        if let Some(ref pre_anesth_status) = self.pre_anesth_status {
+            write_value("PreAnesthStatus", pre_anesth_status.value(), writer)?;
+        }
+
+        if let Some(ref icd_set) = self.icd_set {
+            write_child("ICDSet", icd_set, writer)?;
+        }
+
+        if let Some(ref pre_lab_set) = self.pre_lab_set {
+            write_child("PreLabSet", pre_lab_set, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for ICDSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("ICD", &self.icd, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for ICDType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref icd_rank) = self.icd_rank {
+            write_value("ICDRank", icd_rank, writer)?;
+        }
+
+        write_value("ICDValue", self.icd_value.value(), writer)?;
+
+        write_value("ICDVersion", self.icd_version.value(), writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for PreLabDataSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("PreLabData", &self.pre_lab_data, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for LabDataType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("LabName", self.lab_name.value(), writer)?;
+
+        if let Some(ref lab_category_name) = self.lab_category_name {
+            write_value("LabCategoryName", lab_category_name.value(), writer)?;
+        }
+
+        write_value("LabUnit", self.lab_unit.value(), writer)?;
+
+        write_value("LabValue", &self.lab_value, writer)?;
+
+        if let Some(ref lab_value_text) = self.lab_value_text {
+            write_value("LabValueText", lab_value_text, writer)?;
+        }
+
+        if let Some(ref lab_range_high) = self.lab_range_high {
+            write_value("LabRangeHigh", lab_range_high, writer)?;
+        }
+
+        if let Some(ref lab_range_low) = self.lab_range_low {
+            write_value("LabRangeLow", lab_range_low, writer)?;
+        }
+
+        write_value(
+            "LabDateTime",
+            self.lab_date_time.format(DATE_TIME_FORMAT),
+            writer,
+        )?;
+
+        if let Some(ref lab_comments) = self.lab_comments {
+            write_value("LabComments", lab_comments, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for IntraOpType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref medications_set) = self.medications_set {
+            write_child("MedicationsSet", medications_set, writer)?;
+        }
+
+        if let Some(ref monitoring_physiologic_set) = self.monitoring_physiologic_set {
+            write_child(
+                "MonitoringPhysiologicSet",
+                monitoring_physiologic_set,
+                writer,
+            )?;
+        }
+
+        if let Some(ref outputs_set) = self.outputs_set {
+            write_child("OutputsSet", outputs_set, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for MedicationsSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("Medication", &self.medication, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for MedicationType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("MedicationName", &self.medication_name, writer)?;
+
+        if let Some(ref medication_type) = self.medication_type {
+            for value in medication_type {
+                write_value("MedicationType", value.value(), writer)?;
+            }
+        }
+
+        if let Some(ref med_dose) = self.med_dose {
+            write_value("MedDose", med_dose.value(), writer)?;
+        }
+
+        if let Some(ref dose_units) = self.dose_units {
+            write_value("DoseUnits", dose_units.value(), writer)?;
+        }
+
+        if let Some(ref dose_start) = self.dose_start {
+            write_value("DoseStart", dose_start.format(DATE_TIME_FORMAT), writer)?;
+        }
+
+        if let Some(ref dose_end) = self.dose_end {
+            write_value("DoseEnd", dose_end.format(DATE_TIME_FORMAT), writer)?;
+        }
+
+        if let Some(ref med_concentration) = self.med_concentration {
+            write_value("MedConcentration", med_concentration.value(), writer)?;
+        }
+
+        if let Some(ref med_concentration_unit) = self.med_concentration_unit {
+            write_value(
+                "MedConcentrationUnit",
+                med_concentration_unit.value(),
+                writer,
+            )?;
+        }
+
+        if let Some(ref medication_route) = self.medication_route {
+            for value in medication_route {
+                write_value("MedicationRoute", value.value(), writer)?;
+            }
+        }
+
+        if let Some(ref mixture_medications) = self.mixture_medications {
+            write_children("MixtureMedication", mixture_medications, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for MixtureMedicationType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value(
+            "MixtureMedicationName",
+            &self.mixture_medication_name,
+            writer,
+        )?;
+
+        if let Some(ref mixture_medication_type) = self.mixture_medication_type {
+            for value in mixture_medication_type {
+                write_value("MixtureMedicationType", value.value(), writer)?;
+            }
+        }
+
+        if let Some(ref mixture_med_dose) = self.mixture_med_dose {
+            write_value("MixtureMedDose", mixture_med_dose.value(), writer)?;
+        }
+
+        if let Some(ref mixture_dose_units) = self.mixture_dose_units {
+            write_value("MixtureDoseUnits", mixture_dose_units.value(), writer)?;
+        }
+
+        if let Some(ref mixture_med_concentration) = self.mixture_med_concentration {
+            write_value(
+                "MixtureMedConcentration",
+                mixture_med_concentration.value(),
+                writer,
+            )?;
+        }
+
+        if let Some(ref mixture_med_concentration_unit) = self.mixture_med_concentration_unit {
+            write_value(
+                "MixtureMedConcentrationUnit",
+                mixture_med_concentration_unit.value(),
+                writer,
+            )?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for MonitoringPhysiologicSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("Monitoring", &self.monitoring, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for MonitoringPhysiologicType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("MonitoringName", self.monitoring_name.value(), writer)?;
+
+        if let Some(ref monitoring_time) = self.monitoring_time {
+            write_value(
+                "MonitoringTime",
+                monitoring_time.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref monitoring_units) = self.monitoring_units {
+            write_value("MonitoringUnits", monitoring_units.value(), writer)?;
+        }
+
+        if let Some(ref monitoring_value_numeric) = self.monitoring_value_numeric {
+            write_value(
+                "MonitoringValueNumeric",
+                monitoring_value_numeric.value(),
+                writer,
+            )?;
+        }
+
+        if let Some(ref monitoring_value_text) = self.monitoring_value_text {
+            write_value("MonitoringValueText", monitoring_value_text, writer)?;
+        }
+
+        if let Some(ref monitoring_source) = self.monitoring_source {
+            write_value("MonitoringSource", monitoring_source.value(), writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for OutputsSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("OutputEvent", &self.output_event, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for OutputEventType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("OutputEventName", self.output_event_name.value(), writer)?;
+
+        if let Some(ref output_units) = self.output_units {
+            write_value("OutputUnits", output_units.value(), writer)?;
+        }
+
+        if let Some(ref output_value_numeric) = self.output_value_numeric {
+            write_value("OutputValueNumeric", output_value_numeric.value(), writer)?;
+        }
+
+        if let Some(ref output_value_text) = self.output_value_text {
+            write_value("OutputValueText", output_value_text, writer)?;
+        }
+
+        if let Some(ref output_start_date_time) = self.output_start_date_time {
+            write_value(
+                "OutputStartDateTime",
+                output_start_date_time.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref output_end_date_time) = self.output_end_date_time {
+            write_value(
+                "OutputEndDateTime",
+                output_end_date_time.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for PostOpType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref post_op_disposition) = self.post_op_disposition {
+            write_value("PostOpDisposition", post_op_disposition.value(), writer)?;
+        }
+
+        if let Some(ref post_op_disp_date_time) = self.post_op_disp_date_time {
+            write_value(
+                "PostOpDispDateTime",
+                post_op_disp_date_time.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref post_op_discharge) = self.post_op_discharge {
+            write_value("PostOpDischarge", post_op_discharge.value(), writer)?;
+        }
+
+        if let Some(ref post_op_discharge_date_time) = self.post_op_discharge_date_time {
+            write_value(
+                "PostOpDischargeDateTime",
+                post_op_discharge_date_time.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref length_of_hospital_stay) = self.length_of_hospital_stay {
+            write_value("LengthOfHospitalStay", length_of_hospital_stay, writer)?;
+        }
+
+        write_children("PaymentMethod", &self.payment_method, writer)?;
+
+        if let Some(ref post_op_lab_set) = self.post_op_lab_set {
+            write_child("PostOpLabSet", post_op_lab_set, writer)?;
+        }
+
+        if let Some(ref icd_set) = self.icd_set {
+            write_child("ICDSet", icd_set, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for PaymentMethodType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("PaymentCode", self.payment_code.value(), writer)?;
+
+        if let Some(ref payment_description) = self.payment_description {
+            write_value("PaymentDescription", payment_description.value(), writer)?;
+        }
+
+        if let Some(ref payment_description_sec) = self.payment_description_sec {
+            write_value(
+                "PaymentDescriptionSec",
+                payment_description_sec.value(),
+                writer,
+            )?;
+        }
+
+        if let Some(ref payment_description_th) = self.payment_description_th {
+            write_value(
+                "PaymentDescriptionTh",
+                payment_description_th.value(),
+                writer,
+            )?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for PostOpLabSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("PostLabData", &self.post_lab_data, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for TimingMilestonesSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("TimingMilestone", &self.timing_milestone, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for TimingMilestoneType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("TMType", self.tm_type.value(), writer)?;
+
+        write_value(
+            "TMStartTime",
+            self.tm_start_time.format(DATE_TIME_FORMAT),
+            writer,
+        )?;
+
+        if let Some(ref tm_end_time) = self.tm_end_time {
+            write_value("TMEndTime", tm_end_time.format(DATE_TIME_FORMAT), writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for OutcomesEventsType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref ic_event_set) = self.ic_event_set {
+            write_child("ICEventSet", ic_event_set, writer)?;
+        }
+
+        if let Some(ref outcome_set) = self.outcome_set {
+            write_child("OutcomeSet", outcome_set, writer)?;
+        }
+
+        if let Some(ref qcdr_set) = self.qcdr_set {
+            write_child("QCDRSet", qcdr_set, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for ICEventSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("ICEvent", &self.ic_event, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for ICEventType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref ic_event_time_date) = self.ic_event_time_date {
+            write_value(
+                "ICEventTimeDate",
+                ic_event_time_date.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref ic_category) = self.ic_category {
+            write_value("ICCategory", ic_category.value(), writer)?;
+        }
+
+        if let Some(ref ic_severity) = self.ic_severity {
+            write_value("ICSeverity", ic_severity, writer)?;
+        }
+
+        if let Some(ref ic_name) = self.ic_name {
+            write_value("ICName", ic_name, writer)?;
+        }
+
+        if let Some(ref ic_description) = self.ic_description {
+            write_value("ICDescription", ic_description, writer)?;
+        }
+
+        if let Some(ref ic_value) = self.ic_value {
+            write_value("ICValue", ic_value, writer)?;
+        }
+
+        if let Some(ref ic_notes) = self.ic_notes {
+            write_value("ICNotes", ic_notes, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for OutcomeSetSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("Outcome", &self.outcome, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for OutcomeCodeType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("OutcomeID", self.outcome_id.value(), writer)?;
+
+        write_value("OutcomeOccurred", self.outcome_occurred, writer)?;
+
+        if let Some(ref outcome_time_stamp) = self.outcome_time_stamp {
+            write_value(
+                "OutcomeTimeStamp",
+                outcome_time_stamp.format(DATE_TIME_FORMAT),
+                writer,
+            )?;
+        }
+
+        if let Some(ref outcome_severity) = self.outcome_severity {
+            write_value("OutcomeSeverity", outcome_severity.value(), writer)?;
+        }
+
+        if let Some(ref outcome_time_frame) = self.outcome_time_frame {
+            write_value("OutcomeTimeFrame", outcome_time_frame.value(), writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for QCDRSetTypeSet {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("QCDR", &self.qcdr, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for QCDRSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("QCDRMeasure", self.qcdr_measure.value(), writer)?;
+
+        write_value("QCDRCodeValue", self.qcdr_code_value.value(), writer)?;
+
+        if let Some(ref qcdr_modifier) = self.qcdr_modifier {
+            write_value("QCDRModifier", qcdr_modifier.value(), writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AnesthesiaDetailsType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref intake_output_set) = self.intake_output_set {
+            write_child("IntakeOutputSet", intake_output_set, writer)?;
+        }
+
+        if let Some(ref intubation_details) = self.intubation_details {
+            write_child("IntubationDetails", intubation_details, writer)?;
+        }
+
+        if let Some(ref anesthesia_details_set) = self.anesthesia_details_set {
+            write_child("AnesthesiaDetailsSet", anesthesia_details_set, writer)?;
+        }
+
+        if let Some(ref medications_total_set) = self.medications_total_set {
+            write_child("MedicationsTotalSet", medications_total_set, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for IntakeOutputSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("IntakeOutputTotal", &self.intake_output_total, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for IntakeOutputTotalType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref intake_output_direction) = self.intake_output_direction {
+            write_value(
+                "IntakeOutputDirection",
+                intake_output_direction.value(),
+                writer,
+            )?;
+        }
+
+        if let Some(ref input_output_name) = self.input_output_name {
+            write_value("InputOutputName", input_output_name.value(), writer)?;
+        }
+
+        if let Some(ref output_units) = self.output_units {
+            write_value("OutputUnits", output_units.value(), writer)?;
+        }
+
+        if let Some(ref input_output_total) = self.input_output_total {
+            write_value("InputOutputTotal", input_output_total.value(), writer)?;
+        }
+
+        if let Some(ref input_output_route) = self.input_output_route {
+            write_value("InputOutputRoute", input_output_route.value(), writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for IntubationDetailsType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        if let Some(ref intubation_approach) = self.intubation_approach {
+            write_value("IntubationApproach", intubation_approach.value(), writer)?;
+        }
+
+        if let Some(ref intubation_attempts) = self.intubation_attempts {
+            write_value("IntubationAttempts", intubation_attempts, writer)?;
+        }
+
+        if let Some(ref tube_size) = self.tube_size {
+            write_value("TubeSize", tube_size.value(), writer)?;
+        }
+
+        if let Some(ref tube_type) = self.tube_type {
+            write_value("TubeType", tube_type, writer)?;
+        }
+
+        if let Some(ref intubation_details_properties_set) = self.intubation_details_properties_set
+        {
+            write_child(
+                "IntubationDetailsPropertiesSet",
+                intubation_details_properties_set,
+                writer,
+            )?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for IntubationDetailsPropertiesType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children(
+            "IntubationDetailsProperties",
+            &self.intubation_details_properties,
+            writer,
+        )?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for IntubationDetailsPropertyType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value("IntubationProperty", &self.intubation_property, writer)?;
+
+        if let Some(ref intubation_details_property_value) = self.intubation_details_property_value
+        {
+            write_value(
+                "IntubationDetailsPropertyValue",
+                intubation_details_property_value,
+                writer,
+            )?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AnesthesiaDetailsSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children(
+            "AnesthesiaDetailsData",
+            &self.anesthesia_details_data,
+            writer,
+        )?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for AnesthesiaDetailsDataType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_value(
+            "AnesthesiaDetailsCategory",
+            self.anesthesia_details_category.value(),
+            writer,
+        )?;
+
+        if let Some(ref anesthesia_details_value) = self.anesthesia_details_value {
+            write_value("AnesthesiaDetailsValue", anesthesia_details_value, writer)?;
+        }
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
+
+impl WritableSchemaType for MedicationsTotalSetType {
+    fn write<W: Write>(&self, name: &str, writer: &mut EventWriter<W>) -> Result<(), AQIError> {
+        writer.write(XmlEvent::start_element(name))?;
+
+        write_children("MedicationsTotal", &self.medications_totals, writer)?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+}
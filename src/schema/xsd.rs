@@ -0,0 +1,834 @@
+//! # XSD validation
+//! Validates XML documents against the bundled AQI schema
+//!
+//! The bundled `AQISchema.xsd` describes the types in this crate, which are
+//! generated from it, rather than being AQI's published schema. Validating
+//! against it checks that documents are well-formed and consistent with
+//! those types, but doesn't replace validating against the published XSD
+//! before a submission.
+//!
+//! Only the subset of XML Schema used by `AQISchema.xsd` is supported: global
+//! elements, named complex types made of a single `xs:sequence`, and named
+//! simple types restricting a builtin or another simple type with
+//! `enumeration`, `pattern`, `length`, `minLength`, `maxLength`,
+//! `minInclusive` and `maxInclusive` facets. Schemas using anything else are
+//! rejected by `Schema::parse` rather than silently accepted.
+
+extern crate xml;
+
+use self::xml::common::{Position, TextPosition};
+use self::xml::name::OwnedName;
+use self::xml::reader::{EventReader, XmlEvent};
+use super::validate::child_path;
+use super::*;
+
+use chrono::DateTime;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+
+mod parse;
+
+const BUNDLED_SCHEMA: &str = include_str!("../../schemas/AQISchema.xsd");
+
+lazy_static! {
+    static ref AQI_SCHEMA: Schema = Schema::parse(BUNDLED_SCHEMA).unwrap();
+}
+
+/// A problem found while validating a document
+///
+/// `line` and `column` are one-based and point at the start of the element
+/// or text the diagnostic is about. `path` is the slash-separated element
+/// path from the root, with zero-based indices for repeatable elements.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub line: u64,
+    pub column: u64,
+    pub path: String,
+    pub kind: DiagnosticKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiagnosticKind {
+    /// The document is not well-formed XML, validation stops here
+    Syntax(String),
+    /// An element in the wrong namespace
+    Namespace {
+        element: String,
+        namespace: Option<String>,
+    },
+    /// An element the schema doesn't allow here
+    UnexpectedElement { element: String },
+    /// An element allowed by its parent, but not at this point in the sequence
+    OutOfOrder { element: String },
+    /// More occurrences of an element than `maxOccurs` allows
+    TooMany { element: String, max: usize },
+    /// Fewer occurrences of an element than `minOccurs` allows
+    Missing {
+        element: String,
+        min: usize,
+        found: usize,
+    },
+    /// Non-whitespace text in an element that only has element content
+    UnexpectedText,
+    /// Text content that isn't a valid value of the element's type
+    InvalidValue { value: String, reason: String },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+
+        match self.kind {
+            DiagnosticKind::Syntax(ref message) => write!(f, "{}", message),
+            DiagnosticKind::Namespace {
+                ref element,
+                ref namespace,
+            } => match *namespace {
+                Some(ref namespace) => write!(f, "{} is in namespace {}", element, namespace),
+                None => write!(f, "{} has no namespace", element),
+            },
+            DiagnosticKind::UnexpectedElement { ref element } => {
+                write!(f, "unexpected element {}", element)
+            }
+            DiagnosticKind::OutOfOrder { ref element } => {
+                write!(f, "{} is out of order", element)
+            }
+            DiagnosticKind::TooMany { ref element, max } => {
+                write!(f, "expected at most {} {}", max, element)
+            }
+            DiagnosticKind::Missing {
+                ref element,
+                min,
+                found,
+            } => write!(f, "expected at least {} {}, found {}", min, element, found),
+            DiagnosticKind::UnexpectedText => write!(f, "unexpected text content"),
+            DiagnosticKind::InvalidValue {
+                ref value,
+                ref reason,
+            } => write!(f, "invalid value {:?}: {}", value, reason),
+        }
+    }
+}
+
+/// A parsed XML schema
+pub struct Schema {
+    target_namespace: Option<String>,
+    elements: HashMap<String, TypeRef>,
+    complex_types: HashMap<String, Vec<Particle>>,
+    simple_types: HashMap<String, SimpleType>,
+}
+
+#[derive(Clone, Debug)]
+enum TypeRef {
+    Builtin(Builtin),
+    Named(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Builtin {
+    String,
+    Token,
+    Boolean,
+    Decimal,
+    Integer { min: Option<i64>, max: Option<u64> },
+    Date,
+    DateTime,
+}
+
+struct Particle {
+    name: String,
+    type_ref: TypeRef,
+    min: usize,
+    max: Option<usize>,
+}
+
+struct SimpleType {
+    base: TypeRef,
+    enumerations: Vec<String>,
+    patterns: Vec<Regex>,
+    length: Option<usize>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    min_inclusive: Option<f64>,
+    max_inclusive: Option<f64>,
+}
+
+impl Schema {
+    /// The bundled `AQISchema.xsd`
+    ///
+    /// There's a single bundled schema for every `AQIXMLVersion`. It's the
+    /// schema the crate's types are generated from, so a document that passes
+    /// it still has to be checked against AQI's published XSD for its year.
+    pub fn bundled() -> &'static Schema {
+        &AQI_SCHEMA
+    }
+
+    pub fn parse(source: &str) -> Result<Schema, AQIError> {
+        let parsed = parse::parse(source).map_err(AQIError::parse)?;
+
+        let mut schema = Schema {
+            target_namespace: parsed.target_namespace,
+            elements: HashMap::new(),
+            complex_types: HashMap::new(),
+            simple_types: HashMap::new(),
+        };
+
+        for element in parsed.elements {
+            schema
+                .elements
+                .insert(element.name, type_ref(element.type_name)?);
+        }
+
+        for complex_type in parsed.complex_types {
+            let particles = complex_type
+                .elements
+                .into_iter()
+                .map(|element| {
+                    Ok(Particle {
+                        name: element.name,
+                        type_ref: type_ref(element.type_name)?,
+                        min: element.min,
+                        max: element.max,
+                    })
+                })
+                .collect::<Result<_, AQIError>>()?;
+
+            schema.complex_types.insert(complex_type.name, particles);
+        }
+
+        for simple_type in parsed.simple_types {
+            let patterns = simple_type
+                .patterns
+                .iter()
+                .map(|pattern| {
+                    Regex::new(&format!("^(?:{})$", pattern))
+                        .map_err(|_| AQIError::parse(format!("Invalid pattern {:?}", pattern)))
+                })
+                .collect::<Result<_, AQIError>>()?;
+
+            schema.simple_types.insert(
+                simple_type.name,
+                SimpleType {
+                    base: type_ref(simple_type.base)?,
+                    enumerations: simple_type.enumerations,
+                    patterns,
+                    length: simple_type.length,
+                    min_length: simple_type.min_length,
+                    max_length: simple_type.max_length,
+                    min_inclusive: simple_type.min_inclusive,
+                    max_inclusive: simple_type.max_inclusive,
+                },
+            );
+        }
+
+        schema.check_references()?;
+
+        Ok(schema)
+    }
+
+    fn check_references(&self) -> Result<(), AQIError> {
+        let particles = self
+            .complex_types
+            .values()
+            .flat_map(|particles| particles.iter().map(|particle| &particle.type_ref));
+        let bases = self.simple_types.values().map(|simple| &simple.base);
+
+        for type_ref in self.elements.values().chain(particles).chain(bases) {
+            if let TypeRef::Named(ref name) = *type_ref {
+                if !self.complex_types.contains_key(name) && !self.simple_types.contains_key(name) {
+                    return Err(AQIError::parse(format!("Undefined type {}", name)));
+                }
+            }
+        }
+
+        for name in self.simple_types.keys() {
+            self.primitive(&TypeRef::Named(name.clone()), 0)?;
+        }
+
+        Ok(())
+    }
+
+    fn primitive(&self, type_ref: &TypeRef, depth: usize) -> Result<Builtin, AQIError> {
+        match *type_ref {
+            TypeRef::Builtin(builtin) => Ok(builtin),
+            TypeRef::Named(ref name) => match self.simple_types.get(name) {
+                Some(_) if depth > self.simple_types.len() => {
+                    Err(AQIError::parse(format!("Circular definition of {}", name)))
+                }
+                Some(simple) => self.primitive(&simple.base, depth + 1),
+                None => Err(AQIError::parse(format!("{} is not a simple type", name))),
+            },
+        }
+    }
+
+    /// Validates the document read from `source`
+    ///
+    /// Validation continues past invalid content to report as many problems
+    /// as possible, stopping only if the document isn't well-formed.
+    pub fn validate<R: Read>(&self, source: R) -> Vec<Diagnostic> {
+        let mut reader = EventReader::new(source);
+        let mut stack: Vec<Frame> = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        loop {
+            let event = match reader.next() {
+                Ok(event) => event,
+                Err(err) => {
+                    let path = stack
+                        .iter()
+                        .rev()
+                        .filter_map(Frame::path)
+                        .next()
+                        .unwrap_or("")
+                        .to_string();
+                    diagnostics.push(diagnostic(
+                        err.position(),
+                        path,
+                        DiagnosticKind::Syntax(err.msg().to_string()),
+                    ));
+                    break;
+                }
+            };
+            let position = reader.position();
+
+            match event {
+                XmlEvent::StartElement { name, .. } => {
+                    let frame = self.start_element(&mut stack, name, position, &mut diagnostics);
+                    stack.push(frame);
+                }
+                XmlEvent::EndElement { .. } => {
+                    if let Some(frame) = stack.pop() {
+                        self.end_element(frame, position, &mut diagnostics);
+                    }
+                }
+                XmlEvent::Characters(text) | XmlEvent::CData(text) => match stack.last_mut() {
+                    Some(Frame::Simple { text: content, .. }) => content.push_str(&text),
+                    Some(Frame::Complex { path, .. }) if !text.trim().is_empty() => {
+                        diagnostics.push(diagnostic(
+                            position,
+                            path.clone(),
+                            DiagnosticKind::UnexpectedText,
+                        ));
+                    }
+                    _ => {}
+                },
+                XmlEvent::Whitespace(text) => {
+                    if let Some(Frame::Simple { text: content, .. }) = stack.last_mut() {
+                        content.push_str(&text);
+                    }
+                }
+                XmlEvent::EndDocument => break,
+                _ => {}
+            }
+        }
+
+        diagnostics
+    }
+
+    fn start_element<'s>(
+        &'s self,
+        stack: &mut [Frame<'s>],
+        name: OwnedName,
+        position: TextPosition,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Frame<'s> {
+        let element = name.local_name;
+
+        let (path, type_ref) = match stack.last_mut() {
+            None => match self.elements.get(&element) {
+                Some(type_ref) => (element.clone(), type_ref),
+                None => {
+                    diagnostics.push(diagnostic(
+                        position,
+                        String::new(),
+                        DiagnosticKind::UnexpectedElement { element },
+                    ));
+                    return Frame::Skip;
+                }
+            },
+            Some(Frame::Complex {
+                path,
+                particles,
+                index,
+                count,
+            }) => match particles[*index..]
+                .iter()
+                .position(|particle| particle.name == element)
+            {
+                Some(offset) => {
+                    if offset == 0 {
+                        *count += 1;
+                    } else {
+                        check_remaining(
+                            path,
+                            &particles[*index..*index + offset],
+                            *count,
+                            position,
+                            diagnostics,
+                        );
+                        *index += offset;
+                        *count = 1;
+                    }
+
+                    let particle = &particles[*index];
+                    if let Some(max) = particle.max {
+                        if *count > max {
+                            diagnostics.push(diagnostic(
+                                position,
+                                path.clone(),
+                                DiagnosticKind::TooMany {
+                                    element: element.clone(),
+                                    max,
+                                },
+                            ));
+                        }
+                    }
+
+                    let path = if particle.max == Some(1) {
+                        child_path(path, &element)
+                    } else {
+                        format!("{}[{}]", child_path(path, &element), *count - 1)
+                    };
+
+                    (path, &particle.type_ref)
+                }
+                None => match particles.iter().find(|particle| particle.name == element) {
+                    Some(particle) => {
+                        diagnostics.push(diagnostic(
+                            position,
+                            path.clone(),
+                            DiagnosticKind::OutOfOrder {
+                                element: element.clone(),
+                            },
+                        ));
+
+                        (child_path(path, &element), &particle.type_ref)
+                    }
+                    None => {
+                        diagnostics.push(diagnostic(
+                            position,
+                            path.clone(),
+                            DiagnosticKind::UnexpectedElement { element },
+                        ));
+                        return Frame::Skip;
+                    }
+                },
+            },
+            Some(Frame::Simple { path, .. }) => {
+                diagnostics.push(diagnostic(
+                    position,
+                    path.clone(),
+                    DiagnosticKind::UnexpectedElement { element },
+                ));
+                return Frame::Skip;
+            }
+            Some(Frame::Skip) => return Frame::Skip,
+        };
+
+        if name.namespace != self.target_namespace {
+            diagnostics.push(diagnostic(
+                position,
+                path.clone(),
+                DiagnosticKind::Namespace {
+                    element: element.clone(),
+                    namespace: name.namespace,
+                },
+            ));
+        }
+
+        match *type_ref {
+            TypeRef::Named(ref type_name) if self.complex_types.contains_key(type_name) => {
+                Frame::Complex {
+                    path,
+                    particles: &self.complex_types[type_name],
+                    index: 0,
+                    count: 0,
+                }
+            }
+            _ => Frame::Simple {
+                path,
+                type_ref,
+                text: String::new(),
+                position,
+            },
+        }
+    }
+
+    fn end_element(&self, frame: Frame, position: TextPosition, diagnostics: &mut Vec<Diagnostic>) {
+        match frame {
+            Frame::Complex {
+                path,
+                particles,
+                index,
+                count,
+            } => {
+                check_remaining(&path, &particles[index..], count, position, diagnostics);
+            }
+            Frame::Simple {
+                path,
+                type_ref,
+                text,
+                position,
+            } => {
+                if let Err(reason) = self.check_value(type_ref, &text) {
+                    diagnostics.push(diagnostic(
+                        position,
+                        path,
+                        DiagnosticKind::InvalidValue {
+                            value: text,
+                            reason,
+                        },
+                    ));
+                }
+            }
+            Frame::Skip => {}
+        }
+    }
+
+    fn check_value(&self, type_ref: &TypeRef, value: &str) -> Result<(), String> {
+        // References were checked when the schema was parsed
+        let primitive = self.primitive(type_ref, 0).unwrap();
+
+        if primitive == Builtin::String {
+            self.check_facets(type_ref, value)
+        } else {
+            let collapsed = value.split_whitespace().collect::<Vec<_>>().join(" ");
+            self.check_facets(type_ref, &collapsed)
+        }
+    }
+
+    fn check_facets(&self, type_ref: &TypeRef, value: &str) -> Result<(), String> {
+        let (name, simple) = match *type_ref {
+            TypeRef::Builtin(builtin) => return builtin.check(value),
+            TypeRef::Named(ref name) => (name, &self.simple_types[name]),
+        };
+
+        self.check_facets(&simple.base, value)?;
+
+        if !simple.enumerations.is_empty()
+            && !simple.enumerations.iter().any(|allowed| allowed == value)
+        {
+            return Err(format!("not one of the values allowed by {}", name));
+        }
+
+        if !simple.patterns.is_empty() && !simple.patterns.iter().any(|re| re.is_match(value)) {
+            return Err(format!("doesn't match the pattern of {}", name));
+        }
+
+        let length = value.chars().count();
+        if let Some(expected) = simple.length {
+            if length != expected {
+                return Err(format!("{} must be {} characters", name, expected));
+            }
+        }
+        if let Some(min) = simple.min_length {
+            if length < min {
+                return Err(format!("{} must be at least {} characters", name, min));
+            }
+        }
+        if let Some(max) = simple.max_length {
+            if length > max {
+                return Err(format!("{} must be at most {} characters", name, max));
+            }
+        }
+
+        if simple.min_inclusive.is_some() || simple.max_inclusive.is_some() {
+            let number: f64 = value
+                .parse()
+                .map_err(|_| format!("{} must be a number", name))?;
+
+            if let Some(min) = simple.min_inclusive {
+                if number < min {
+                    return Err(format!("{} must be at least {}", name, min));
+                }
+            }
+            if let Some(max) = simple.max_inclusive {
+                if number > max {
+                    return Err(format!("{} must be at most {}", name, max));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Builtin {
+    fn from_name(name: &str) -> Option<Builtin> {
+        let builtin = match name {
+            "string" | "normalizedString" => Builtin::String,
+            "token" => Builtin::Token,
+            "boolean" => Builtin::Boolean,
+            "decimal" => Builtin::Decimal,
+            "integer" => Builtin::Integer {
+                min: None,
+                max: None,
+            },
+            "long" => Builtin::Integer {
+                min: Some(i64::MIN),
+                max: Some(i64::MAX as u64),
+            },
+            "int" => Builtin::Integer {
+                min: Some(i64::from(i32::MIN)),
+                max: Some(i32::MAX as u64),
+            },
+            "nonNegativeInteger" => Builtin::Integer {
+                min: Some(0),
+                max: None,
+            },
+            "positiveInteger" => Builtin::Integer {
+                min: Some(1),
+                max: None,
+            },
+            "unsignedLong" => Builtin::Integer {
+                min: Some(0),
+                max: Some(u64::MAX),
+            },
+            "unsignedInt" => Builtin::Integer {
+                min: Some(0),
+                max: Some(u64::from(u32::MAX)),
+            },
+            "unsignedByte" => Builtin::Integer {
+                min: Some(0),
+                max: Some(u64::from(u8::MAX)),
+            },
+            "date" => Builtin::Date,
+            "dateTime" => Builtin::DateTime,
+            _ => return None,
+        };
+
+        Some(builtin)
+    }
+
+    fn check(self, value: &str) -> Result<(), String> {
+        lazy_static! {
+            static ref DECIMAL: Regex = Regex::new(r"^[+-]?([0-9]+(\.[0-9]*)?|\.[0-9]+)$").unwrap();
+            static ref INTEGER: Regex = Regex::new(r"^[+-]?[0-9]+$").unwrap();
+            static ref TIMEZONE: Regex = Regex::new(r"(Z|[+-][0-9]{2}:[0-9]{2})$").unwrap();
+        }
+
+        let valid = match self {
+            Builtin::String | Builtin::Token => true,
+            Builtin::Boolean => ["true", "false", "1", "0"].contains(&value),
+            Builtin::Decimal => DECIMAL.is_match(value),
+            Builtin::Integer { min, max } => {
+                INTEGER.is_match(value) && {
+                    let negative = value.starts_with('-');
+                    let digits = value.trim_start_matches(['+', '-']);
+                    let digits = digits.trim_start_matches('0');
+
+                    if negative && !digits.is_empty() {
+                        match min {
+                            Some(min) => format!("-{}", digits).parse::<i64>().ok() >= Some(min),
+                            None => true,
+                        }
+                    } else {
+                        match max {
+                            Some(max) => {
+                                digits.is_empty() || digits.parse::<u64>().ok() <= Some(max)
+                            }
+                            None => true,
+                        }
+                    }
+                }
+            }
+            Builtin::Date => {
+                NaiveDate::parse_from_str(TIMEZONE.replace(value, "").as_ref(), "%Y-%m-%d").is_ok()
+            }
+            Builtin::DateTime => {
+                NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").is_ok()
+                    || DateTime::parse_from_rfc3339(value).is_ok()
+            }
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("not a valid {:?}", self).to_lowercase())
+        }
+    }
+}
+
+enum Frame<'s> {
+    Complex {
+        path: String,
+        particles: &'s [Particle],
+        index: usize,
+        count: usize,
+    },
+    Simple {
+        path: String,
+        type_ref: &'s TypeRef,
+        text: String,
+        position: TextPosition,
+    },
+    Skip,
+}
+
+impl<'s> Frame<'s> {
+    fn path(&self) -> Option<&str> {
+        match *self {
+            Frame::Complex { ref path, .. } | Frame::Simple { ref path, .. } => Some(path),
+            Frame::Skip => None,
+        }
+    }
+}
+
+/// Reports any particles in `particles` that haven't reached `minOccurs`,
+/// the first of which has been seen `count` times
+fn check_remaining(
+    path: &str,
+    particles: &[Particle],
+    count: usize,
+    position: TextPosition,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (i, particle) in particles.iter().enumerate() {
+        let found = if i == 0 { count } else { 0 };
+
+        if found < particle.min {
+            diagnostics.push(diagnostic(
+                position,
+                path.to_string(),
+                DiagnosticKind::Missing {
+                    element: particle.name.clone(),
+                    min: particle.min,
+                    found,
+                },
+            ));
+        }
+    }
+}
+
+fn diagnostic(position: TextPosition, path: String, kind: DiagnosticKind) -> Diagnostic {
+    Diagnostic {
+        line: position.row + 1,
+        column: position.column + 1,
+        path,
+        kind,
+    }
+}
+
+/// Builtin types are the only prefixed names in the supported subset
+fn type_ref(name: String) -> Result<TypeRef, AQIError> {
+    match name.find(':') {
+        Some(i) => Builtin::from_name(&name[i + 1..])
+            .map(TypeRef::Builtin)
+            .ok_or_else(|| AQIError::parse(format!("Unsupported builtin type {}", name))),
+        None => Ok(TypeRef::Named(name)),
+    }
+}
+
+impl AnesthesiaRecordsType {
+    /// Writes the document and validates it against the bundled schema
+    pub fn schema_diagnostics(&self) -> Result<Vec<Diagnostic>, AQIError> {
+        let document = self.write_document(Vec::new(), false)?;
+        Ok(Schema::bundled().validate(document.as_slice()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostics(source: &str) -> Vec<String> {
+        Schema::bundled()
+            .validate(source.as_bytes())
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn accepts_the_sample_submission() {
+        assert_eq!(diagnostics(SUBMISSION), Vec::<String>::new());
+        assert_eq!(submission().schema_diagnostics().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn reports_invalid_values_with_their_position() {
+        let source = SUBMISSION.replacen("<NPI>1234567890</NPI>", "<NPI>123</NPI>", 1);
+
+        assert_eq!(
+            diagnostics(&source),
+            vec![
+                "46:11: AnesthesiaRecords/AnesthesiaRecord[0]/AnesthesiaCase/AnesthesiaStaffSet/\
+                 AnesthesiaStaff[0]/NPI: invalid value \"123\": doesn't match the pattern of NPIType"
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_missing_and_out_of_order_elements() {
+        let source = SUBMISSION.replacen(
+            "<Age>90</Age>\n      <ASAClass>III</ASAClass>",
+            "<ASAClass>III</ASAClass>\n      <Age>90</Age>",
+            1,
+        );
+
+        assert_eq!(
+            diagnostics(&source),
+            vec![
+                "138:7: AnesthesiaRecords/AnesthesiaRecord[1]/PreOp: expected at least 1 Age, found 0",
+                "139:7: AnesthesiaRecords/AnesthesiaRecord[1]/PreOp: Age is out of order",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_elements_outside_the_namespace() {
+        let source = SUBMISSION.replacen(r#" xmlns="http://www.aqihq.org/AQIXMLSchema""#, "", 1);
+
+        assert_eq!(
+            diagnostics(&source)[0],
+            "2:1: AnesthesiaRecords: AnesthesiaRecords has no namespace"
+        );
+    }
+
+    #[test]
+    fn stops_at_malformed_xml() {
+        let diagnostics =
+            Schema::bundled().validate(SUBMISSION.replacen("</PreOp>", "</Pre>", 1).as_bytes());
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(matches!(diagnostics[0].kind, DiagnosticKind::Syntax(_)));
+    }
+
+    #[test]
+    fn schema_diagnostics_validate_the_written_document() {
+        let mut records = submission();
+        records.anesthesia_records[1]
+            .anesthesia_case
+            .anesthesia_method_set
+            .anesthesia_method
+            .clear();
+
+        let diagnostics = records.schema_diagnostics().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].path,
+            "AnesthesiaRecords/AnesthesiaRecord[1]/AnesthesiaCase/AnesthesiaMethodSet"
+        );
+        assert!(matches!(
+            diagnostics[0].kind,
+            DiagnosticKind::Missing { found: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn rejects_unsupported_schema_constructs() {
+        let err = Schema::parse(
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+                <xs:complexType name="A"><xs:choice/></xs:complexType>
+            </xs:schema>"#,
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(err.to_string(), "Unsupported choice in complexType");
+    }
+}
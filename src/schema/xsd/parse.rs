@@ -0,0 +1,190 @@
+//! Reads the subset of XML Schema described in `xsd` into its components,
+//! in schema order
+//!
+//! Shared with `aqi-nacor-codegen`, which includes this file directly, so it
+//! only depends on `xml-rs` and reports errors as plain strings.
+
+use xml::reader::{EventReader, XmlEvent};
+
+pub struct Schema {
+    pub target_namespace: Option<String>,
+    /// Global elements
+    pub elements: Vec<Element>,
+    pub complex_types: Vec<ComplexType>,
+    pub simple_types: Vec<SimpleType>,
+}
+
+pub struct ComplexType {
+    pub name: String,
+    /// Members of the type's `xs:sequence`
+    pub elements: Vec<Element>,
+}
+
+pub struct Element {
+    pub name: String,
+    /// Either a builtin with its `xs:` prefix or the name of a type in the schema
+    pub type_name: String,
+    pub min: usize,
+    /// `None` for `unbounded`
+    pub max: Option<usize>,
+}
+
+pub struct SimpleType {
+    pub name: String,
+    pub base: String,
+    pub enumerations: Vec<String>,
+    pub patterns: Vec<String>,
+    pub length: Option<usize>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub min_inclusive: Option<f64>,
+    pub max_inclusive: Option<f64>,
+}
+
+pub fn parse(source: &str) -> Result<Schema, String> {
+    let mut schema = Schema {
+        target_namespace: None,
+        elements: Vec::new(),
+        complex_types: Vec::new(),
+        simple_types: Vec::new(),
+    };
+    let mut stack: Vec<String> = Vec::new();
+    // Depth inside an `xs:annotation`, whose content is ignored
+    let mut annotation = 0;
+
+    for event in EventReader::new(source.as_bytes()) {
+        match event.map_err(|err| err.to_string())? {
+            XmlEvent::StartElement { .. } if annotation > 0 => annotation += 1,
+            XmlEvent::EndElement { .. } if annotation > 0 => annotation -= 1,
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let attribute = |key: &str| {
+                    attributes
+                        .iter()
+                        .find(|attribute| attribute.name.local_name == key)
+                        .map(|attribute| attribute.value.clone())
+                };
+                let required = |key: &str| {
+                    attribute(key).ok_or_else(|| format!("{} is missing {}", name.local_name, key))
+                };
+
+                match (stack.last().map(String::as_str), name.local_name.as_str()) {
+                    (None, "schema") => {
+                        schema.target_namespace = attribute("targetNamespace");
+                    }
+                    (Some("schema"), "annotation") => {
+                        annotation = 1;
+                        continue;
+                    }
+                    (Some("schema"), "element") => {
+                        schema.elements.push(Element {
+                            name: required("name")?,
+                            type_name: required("type")?,
+                            min: 1,
+                            max: Some(1),
+                        });
+                    }
+                    (Some("schema"), "complexType") => {
+                        schema.complex_types.push(ComplexType {
+                            name: required("name")?,
+                            elements: Vec::new(),
+                        });
+                    }
+                    (Some("complexType"), "sequence") => {}
+                    (Some("sequence"), "element") => {
+                        let element = Element {
+                            name: required("name")?,
+                            type_name: required("type")?,
+                            min: match attribute("minOccurs") {
+                                Some(min) => min
+                                    .parse()
+                                    .map_err(|_| format!("Invalid minOccurs {}", min))?,
+                                None => 1,
+                            },
+                            max: match attribute("maxOccurs").as_deref() {
+                                Some("unbounded") => None,
+                                Some(max) => Some(
+                                    max.parse()
+                                        .map_err(|_| format!("Invalid maxOccurs {}", max))?,
+                                ),
+                                None => Some(1),
+                            },
+                        };
+
+                        // The parent is always the last complex type pushed
+                        schema
+                            .complex_types
+                            .last_mut()
+                            .unwrap()
+                            .elements
+                            .push(element);
+                    }
+                    (Some("schema"), "simpleType") => {
+                        schema.simple_types.push(SimpleType {
+                            name: required("name")?,
+                            base: String::new(),
+                            enumerations: Vec::new(),
+                            patterns: Vec::new(),
+                            length: None,
+                            min_length: None,
+                            max_length: None,
+                            min_inclusive: None,
+                            max_inclusive: None,
+                        });
+                    }
+                    (Some("simpleType"), "restriction") => {
+                        schema.simple_types.last_mut().unwrap().base = required("base")?;
+                    }
+                    (Some("restriction"), facet) => {
+                        let value = required("value")?;
+                        let invalid = || format!("Invalid {} {:?}", facet, value);
+                        let simple = schema.simple_types.last_mut().unwrap();
+
+                        match facet {
+                            "enumeration" => simple.enumerations.push(value.clone()),
+                            "pattern" => simple.patterns.push(value.clone()),
+                            "length" => simple.length = Some(value.parse().map_err(|_| invalid())?),
+                            "minLength" => {
+                                simple.min_length = Some(value.parse().map_err(|_| invalid())?)
+                            }
+                            "maxLength" => {
+                                simple.max_length = Some(value.parse().map_err(|_| invalid())?)
+                            }
+                            "minInclusive" => {
+                                simple.min_inclusive = Some(value.parse().map_err(|_| invalid())?)
+                            }
+                            "maxInclusive" => {
+                                simple.max_inclusive = Some(value.parse().map_err(|_| invalid())?)
+                            }
+                            _ => return Err(format!("Unsupported facet {}", facet)),
+                        }
+                    }
+                    (parent, element) => {
+                        return Err(format!(
+                            "Unsupported {} in {}",
+                            element,
+                            parent.unwrap_or("document")
+                        ));
+                    }
+                }
+
+                stack.push(name.local_name);
+            }
+            XmlEvent::EndElement { .. } => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    if schema
+        .simple_types
+        .iter()
+        .any(|simple| simple.base.is_empty())
+    {
+        return Err("Every simpleType needs a restriction".to_string());
+    }
+
+    Ok(schema)
+}
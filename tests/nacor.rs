@@ -0,0 +1,280 @@
+//! Runs the `nacor` binary against the test submission
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output, Stdio};
+
+const SUBMISSION: &str = include_str!("../src/schema/testdata/submission.xml");
+
+/// Runs `nacor` in `dir` with `args`, writing `stdin` to it
+fn nacor(dir: &Path, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_nacor"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Commands that fail early exit without reading stdin
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+
+    child.wait_with_output().unwrap()
+}
+
+/// An empty directory of its own for each test
+fn directory(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("nacor-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+fn files(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+
+    names
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+/// The submission with just its header
+fn without_records() -> String {
+    let end = SUBMISSION.find("<AnesthesiaRecord>").unwrap();
+
+    format!("{}</AnesthesiaRecords>\n", &SUBMISSION[..end])
+}
+
+#[test]
+fn exits_with_the_outcome() {
+    let dir = directory("outcome");
+    fs::write(dir.join("submission.xml"), SUBMISSION).unwrap();
+
+    let output = nacor(&dir, &["validate", "submission.xml"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "");
+
+    let invalid = SUBMISSION.replacen("<PatientSex>Male", "<PatientSex>Bogus", 1);
+    let output = nacor(&dir, &["validate"], &invalid);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("AnesthesiaRecord[0]/Demographic/PatientSex"));
+
+    let output = nacor(&dir, &["validate", "--quiet", "-"], &invalid);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+
+    assert_eq!(nacor(&dir, &[], "").status.code(), Some(2));
+    assert_eq!(nacor(&dir, &["bogus"], "").status.code(), Some(2));
+    assert_eq!(
+        nacor(&dir, &["validate", "missing.xml"], "").status.code(),
+        Some(2)
+    );
+    assert_eq!(
+        nacor(&dir, &["validate", "--from", "yaml"], SUBMISSION)
+            .status
+            .code(),
+        Some(2)
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn converts_between_stdin_and_stdout() {
+    let dir = directory("convert");
+
+    let output = nacor(&dir, &["convert", "--to", "json"], SUBMISSION);
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["record_header"]["practice_id"], "1234");
+
+    let output = nacor(
+        &dir,
+        &["convert", "--to", "xml", "--pretty", "-"],
+        &stdout(&output),
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), SUBMISSION);
+
+    let output = nacor(&dir, &["convert", "-o", "submission.json"], SUBMISSION);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(
+            &fs::read_to_string(dir.join("submission.json")).unwrap()
+        )
+        .unwrap(),
+        json
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn splits_and_merges_back() {
+    let dir = directory("split");
+    fs::write(dir.join("submission.xml"), SUBMISSION).unwrap();
+
+    let output = nacor(
+        &dir,
+        &["split", "-n", "1", "--pretty", "submission.xml"],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "submission-001.xml\nsubmission-002.xml\n");
+    assert_eq!(
+        files(&dir),
+        ["submission-001.xml", "submission-002.xml", "submission.xml"]
+    );
+
+    let output = nacor(
+        &dir,
+        &[
+            "merge",
+            "--pretty",
+            "submission-001.xml",
+            "submission-002.xml",
+        ],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), SUBMISSION);
+
+    // The same records again are left out
+    let output = nacor(
+        &dir,
+        &[
+            "merge",
+            "-o",
+            "merged.xml",
+            "submission.xml",
+            "submission-002.xml",
+        ],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already merged"));
+
+    assert_eq!(nacor(&dir, &["merge"], "").status.code(), Some(2));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn refuses_to_split_without_records() {
+    let dir = directory("split-empty");
+
+    let output = nacor(&dir, &["split", "-n", "1", "-o", "out"], &without_records());
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stdout(&output), "");
+
+    let invalid = SUBMISSION.replace("<PatientSex>Male", "<PatientSex>Bogus");
+    let output = nacor(&dir, &["split", "-n", "1", "-o", "out"], &invalid);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(files(&dir).is_empty());
+
+    assert_eq!(
+        nacor(&dir, &["split", "-n", "0"], SUBMISSION).status.code(),
+        Some(2)
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn leaves_no_files_when_a_split_fails() {
+    let dir = directory("split-failed");
+    fs::create_dir(dir.join("out-002.xml")).unwrap();
+
+    let output = nacor(&dir, &["split", "-n", "1", "-o", "out"], SUBMISSION);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(files(&dir), ["out-002.xml"]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn deidentifies() {
+    let dir = directory("deidentify");
+    fs::write(dir.join("key"), "secret\n").unwrap();
+
+    let output = nacor(&dir, &["deidentify", "--verbose"], SUBMISSION);
+    assert_eq!(output.status.code(), Some(0));
+    let deidentified = stdout(&output);
+    assert!(deidentified.contains("<AnesthesiaRecords"));
+    assert!(!deidentified.contains("MRN1"));
+    assert!(!deidentified.contains("1930-05-06"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Demographic/PatientID: removed"));
+
+    let output = nacor(
+        &dir,
+        &[
+            "deidentify",
+            "--limited",
+            "--key-file",
+            "key",
+            "--max-days",
+            "30",
+            "--crosswalk",
+            "crosswalk.csv",
+            "-o",
+            "limited.xml",
+        ],
+        SUBMISSION,
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let limited = fs::read_to_string(dir.join("limited.xml")).unwrap();
+    assert!(!limited.contains("MRN1"));
+    assert!(!limited.contains("Left knee"));
+    assert!(fs::read_to_string(dir.join("crosswalk.csv"))
+        .unwrap()
+        .contains("MRN1"));
+
+    // The same key gives the same pseudonyms and offsets
+    let output = nacor(
+        &dir,
+        &[
+            "deidentify",
+            "--limited",
+            "--key-file",
+            "key",
+            "--max-days",
+            "30",
+        ],
+        SUBMISSION,
+    );
+    assert_eq!(stdout(&output), limited);
+
+    for args in &[
+        &["deidentify", "--limited"][..],
+        &["deidentify", "--key-file", "key"][..],
+        &["deidentify", "--crosswalk", "crosswalk.csv"][..],
+        &[
+            "deidentify",
+            "--limited",
+            "--key-file",
+            "key",
+            "--max-days",
+            "0",
+        ][..],
+        &["deidentify", "--limited", "--key-file", "missing"][..],
+    ] {
+        assert_eq!(
+            nacor(&dir, args, SUBMISSION).status.code(),
+            Some(2),
+            "{:?}",
+            args
+        );
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}